@@ -0,0 +1,28 @@
+-- Every writer stores `open_hours` as `[start, end)`; an unbounded end means "until close".
+-- Existing rows are rewritten to that form. Empty ranges and ranges without a start have
+-- no meaning as a slot and make this migration fail until they are fixed or removed.
+CREATE FUNCTION normalize_open_hours() RETURNS trigger AS $$
+BEGIN
+  IF NOT isempty(NEW.open_hours) THEN
+    NEW.open_hours := tstzrange(lower(NEW.open_hours), upper(NEW.open_hours), '[)');
+  END IF;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER schedule_entries_normalize_open_hours
+  BEFORE INSERT OR UPDATE OF open_hours ON schedule_entries
+  FOR EACH ROW EXECUTE FUNCTION normalize_open_hours();
+
+UPDATE schedule_entries SET open_hours = open_hours
+WHERE NOT isempty(open_hours) AND NOT (lower_inc(open_hours) AND NOT upper_inc(open_hours));
+
+ALTER TABLE schedule_entries
+  ADD CONSTRAINT schedule_entries_open_hours_check
+    CHECK (NOT isempty(open_hours) AND NOT lower_inf(open_hours)),
+  ADD CONSTRAINT schedule_entries_check
+    CHECK (lower_inc(open_hours) AND NOT upper_inc(open_hours)),
+  ADD COLUMN all_day BOOLEAN NOT NULL DEFAULT FALSE,
+  -- Cancelled entries are kept for history and hidden from schedules by default.
+  ADD COLUMN status TEXT NOT NULL DEFAULT 'confirmed'
+    CHECK (status IN ('confirmed', 'tentative', 'cancelled'));
@@ -0,0 +1,46 @@
+-- `brewery_name` and `food_vendor_name` are copies of the parents' names so schedule reads
+-- need no joins. They are set here on every write that changes a parent id and follow
+-- renames through the `*_sync_schedule_names` triggers. An unknown parent leaves an empty
+-- name and then fails the foreign key check, which names the offending column.
+CREATE FUNCTION set_schedule_entry_names() RETURNS trigger AS $$
+BEGIN
+  NEW.brewery_name := COALESCE((SELECT name FROM breweries WHERE id = NEW.brewery_id), '');
+  NEW.food_vendor_name := COALESCE((SELECT name FROM food_vendors WHERE id = NEW.food_vendor_id), '');
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER schedule_entries_set_names
+  BEFORE INSERT OR UPDATE OF brewery_id, food_vendor_id ON schedule_entries
+  FOR EACH ROW EXECUTE FUNCTION set_schedule_entry_names();
+
+CREATE FUNCTION sync_brewery_schedule_names() RETURNS trigger AS $$
+BEGIN
+  UPDATE schedule_entries SET brewery_name = NEW.name WHERE brewery_id = NEW.id;
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION sync_vendor_schedule_names() RETURNS trigger AS $$
+BEGIN
+  UPDATE schedule_entries SET food_vendor_name = NEW.name WHERE food_vendor_id = NEW.id;
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER breweries_sync_schedule_names
+  AFTER UPDATE OF name ON breweries
+  FOR EACH ROW WHEN (OLD.name IS DISTINCT FROM NEW.name)
+  EXECUTE FUNCTION sync_brewery_schedule_names();
+CREATE TRIGGER food_vendors_sync_schedule_names
+  AFTER UPDATE OF name ON food_vendors
+  FOR EACH ROW WHEN (OLD.name IS DISTINCT FROM NEW.name)
+  EXECUTE FUNCTION sync_vendor_schedule_names();
+
+-- Names written before the triggers may have drifted from their parents.
+UPDATE schedule_entries e SET brewery_name = b.name
+FROM breweries b
+WHERE b.id = e.brewery_id AND e.brewery_name <> b.name;
+UPDATE schedule_entries e SET food_vendor_name = v.name
+FROM food_vendors v
+WHERE v.id = e.food_vendor_id AND e.food_vendor_name <> v.name;
@@ -0,0 +1,25 @@
+-- Admin corrections layered over scraped entries by `ScheduleRepo::get_schedules_within`.
+-- `cancel` and `edit` match entries by brewery, vendor and overlapping hours rather than
+-- by id, so they still apply after a re-scrape recreates the row. A cancelled match is
+-- reported with status `cancelled`.
+CREATE TABLE schedule_overrides (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  kind TEXT NOT NULL CHECK (kind IN ('add', 'cancel', 'edit')),
+  brewery_id UUID NOT NULL REFERENCES breweries(id) ON DELETE CASCADE,
+  -- Slot being cancelled or edited.
+  target_vendor_id UUID REFERENCES food_vendors(id) ON DELETE CASCADE,
+  target_hours TSTZRANGE,
+  -- Slot being added, or the replacement for an edit.
+  food_vendor_id UUID REFERENCES food_vendors(id) ON DELETE CASCADE,
+  open_hours TSTZRANGE CHECK (NOT isempty(open_hours) AND NOT lower_inf(open_hours)),
+  all_day BOOLEAN NOT NULL DEFAULT FALSE,
+  reason TEXT NOT NULL,
+  author TEXT NOT NULL,
+  -- The override stops applying after this instant.
+  expires_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK ((kind = 'add') = (target_hours IS NULL AND target_vendor_id IS NULL)),
+  CHECK ((kind = 'cancel') = (open_hours IS NULL AND food_vendor_id IS NULL))
+);
+
+CREATE INDEX schedule_overrides_brewery_idx ON schedule_overrides(brewery_id);
@@ -0,0 +1,21 @@
+-- Standing slots such as "every Thursday 5-9pm", expanded into `schedule_entries` with
+-- source `recurring` for the rolling horizon. Dates and times are local to `LOCAL_TZ`.
+CREATE TABLE recurring_schedules (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  brewery_id UUID NOT NULL REFERENCES breweries(id) ON DELETE CASCADE,
+  food_vendor_id UUID NOT NULL REFERENCES food_vendors(id) ON DELETE CASCADE,
+  -- RFC 5545 RRULE subset: FREQ=DAILY or WEEKLY, optional INTERVAL and BYDAY.
+  rrule TEXT NOT NULL,
+  start_time TIME NOT NULL,
+  -- An end before `start_time` is on the next day; NULL means "until close".
+  end_time TIME,
+  effective_from DATE NOT NULL,
+  effective_until DATE,
+  -- Local dates the slot is skipped, such as holidays.
+  exceptions DATE[] NOT NULL DEFAULT '{}',
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (effective_until IS NULL OR effective_until >= effective_from),
+  CHECK (end_time IS NULL OR end_time <> start_time)
+);
+
+CREATE INDEX recurring_schedules_brewery_idx ON recurring_schedules(brewery_id);
@@ -0,0 +1,42 @@
+-- When a scraper last finished a pass over the brewery; schedules past it are unknown.
+ALTER TABLE breweries ADD COLUMN last_scraped_at TIMESTAMPTZ;
+
+-- One scraper pass over a brewery's schedule from one source. The entries it added,
+-- removed or changed are kept in `scrape_run_changes` so lineup churn can be charted.
+CREATE TABLE scrape_runs (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  brewery_id UUID NOT NULL REFERENCES breweries(id) ON DELETE CASCADE,
+  source TEXT NOT NULL,
+  -- Span the source covered. Entries from the same source starting in it are replaced.
+  -- Unknown for failed runs.
+  scraped_hours TSTZRANGE CHECK (NOT isempty(scraped_hours)),
+  started_at TIMESTAMPTZ NOT NULL,
+  finished_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  outcome TEXT NOT NULL CHECK (outcome IN ('succeeded', 'failed')),
+  entries_parsed INTEGER NOT NULL DEFAULT 0,
+  entries_inserted INTEGER NOT NULL DEFAULT 0,
+  entries_updated INTEGER NOT NULL DEFAULT 0,
+  entries_deleted INTEGER NOT NULL DEFAULT 0,
+  error TEXT,
+  CHECK ((outcome = 'failed') = (error IS NOT NULL)),
+  CHECK (outcome = 'failed' OR scraped_hours IS NOT NULL)
+);
+
+CREATE INDEX scrape_runs_brewery_idx ON scrape_runs(brewery_id, started_at);
+
+-- Vendor names are copied at write time so the history survives renames and deletes.
+CREATE TABLE scrape_run_changes (
+  id BIGSERIAL PRIMARY KEY,
+  run_id UUID NOT NULL REFERENCES scrape_runs(id) ON DELETE CASCADE,
+  kind TEXT NOT NULL CHECK (kind IN ('added', 'removed', 'time_shifted', 'vendor_swapped')),
+  -- Not a foreign key: removed entries no longer exist.
+  schedule_entry_id UUID NOT NULL,
+  before_vendor_id UUID,
+  before_vendor_name TEXT,
+  before_hours TSTZRANGE,
+  after_vendor_id UUID,
+  after_vendor_name TEXT,
+  after_hours TSTZRANGE
+);
+
+CREATE INDEX scrape_run_changes_run_idx ON scrape_run_changes(run_id, id);
@@ -0,0 +1,18 @@
+-- Problems the schedule check found in `schedule_entries`, kept for review. A later check
+-- removes open issues that no longer apply; dismissed issues stay dismissed.
+CREATE TABLE schedule_issues (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  kind TEXT NOT NULL CHECK (kind IN ('vendor_overlap', 'implausible_duration', 'outside_business_hours', 'past_dated')),
+  schedule_entry_id UUID NOT NULL REFERENCES schedule_entries(id) ON DELETE CASCADE,
+  -- The other entry of a `vendor_overlap`.
+  related_entry_id UUID REFERENCES schedule_entries(id) ON DELETE CASCADE,
+  detail TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'dismissed')),
+  detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  reviewed_at TIMESTAMPTZ,
+  reviewed_by TEXT,
+  UNIQUE NULLS NOT DISTINCT (kind, schedule_entry_id, related_entry_id),
+  CHECK ((kind = 'vendor_overlap') = (related_entry_id IS NOT NULL))
+);
+
+CREATE INDEX schedule_issues_status_idx ON schedule_issues(status, detected_at);
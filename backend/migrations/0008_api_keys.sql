@@ -0,0 +1,12 @@
+-- Admin API credentials. Keys are shown once at mint time; only the argon2 hash is kept.
+CREATE TABLE api_keys (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name TEXT NOT NULL,
+  -- Public part of the key, used to find the row before verifying the hash.
+  prefix TEXT NOT NULL UNIQUE,
+  key_hash TEXT NOT NULL,
+  scopes TEXT[] NOT NULL CHECK (scopes <@ ARRAY['schedules:write', 'catalog:write', 'scrape:trigger']),
+  expires_at TIMESTAMPTZ,
+  revoked_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
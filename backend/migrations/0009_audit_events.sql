@@ -0,0 +1,62 @@
+-- Append-only change history written by triggers on audited tables. Writers tag their
+-- transaction with `app.actor` and `app.request_id` via `set_config(..., true)`.
+CREATE TABLE audit_events (
+  id BIGSERIAL PRIMARY KEY,
+  occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  -- `api_key:<id>`, `scraper:<source>`, `import:<file>`, `recurring`, `repair`, `seed`,
+  -- or `db:<role>` for untagged writes.
+  actor TEXT NOT NULL,
+  request_id TEXT,
+  entity_type TEXT NOT NULL,
+  entity_id UUID NOT NULL,
+  action TEXT NOT NULL CHECK (action IN ('insert', 'update', 'delete')),
+  before JSONB,
+  after JSONB
+);
+
+CREATE INDEX audit_events_entity_idx ON audit_events(entity_id, id);
+
+CREATE FUNCTION record_audit_event() RETURNS trigger AS $$
+DECLARE
+  old_row JSONB := CASE WHEN TG_OP = 'INSERT' THEN NULL ELSE to_jsonb(OLD) END;
+  new_row JSONB := CASE WHEN TG_OP = 'DELETE' THEN NULL ELSE to_jsonb(NEW) END;
+BEGIN
+  INSERT INTO audit_events (actor, request_id, entity_type, entity_id, action, before, after)
+  VALUES (
+    COALESCE(NULLIF(current_setting('app.actor', true), ''), 'db:' || current_user),
+    NULLIF(current_setting('app.request_id', true), ''),
+    TG_TABLE_NAME,
+    (COALESCE(new_row, old_row) ->> 'id')::uuid,
+    lower(TG_OP),
+    old_row,
+    new_row
+  );
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION reject_audit_change() RETURNS trigger AS $$
+BEGIN
+  RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_append_only
+  BEFORE UPDATE OR DELETE OR TRUNCATE ON audit_events
+  FOR EACH STATEMENT EXECUTE FUNCTION reject_audit_change();
+
+CREATE TRIGGER breweries_audit
+  AFTER INSERT OR UPDATE OR DELETE ON breweries
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER food_vendors_audit
+  AFTER INSERT OR UPDATE OR DELETE ON food_vendors
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER schedule_entries_audit
+  AFTER INSERT OR UPDATE OR DELETE ON schedule_entries
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER schedule_overrides_audit
+  AFTER INSERT OR UPDATE OR DELETE ON schedule_overrides
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER recurring_schedules_audit
+  AFTER INSERT OR UPDATE OR DELETE ON recurring_schedules
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
@@ -0,0 +1,34 @@
+-- Regular opening hours in `LOCAL_TZ`, one row per open period. A brewery without rows has
+-- unknown hours rather than being always closed.
+CREATE TABLE brewery_hours (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  brewery_id UUID NOT NULL REFERENCES breweries(id) ON DELETE CASCADE,
+  -- ISO weekday, 1 = Monday.
+  weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 1 AND 7),
+  opens_at TIME NOT NULL,
+  -- A close before `opens_at` is on the next day.
+  closes_at TIME NOT NULL CHECK (closes_at <> opens_at),
+  UNIQUE (brewery_id, weekday, opens_at)
+);
+
+-- Local dates whose weekly hours are replaced: closed all day when `opens_at` is NULL,
+-- otherwise open for the one period given, as on holidays.
+CREATE TABLE brewery_closures (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  brewery_id UUID NOT NULL REFERENCES breweries(id) ON DELETE CASCADE,
+  on_date DATE NOT NULL,
+  opens_at TIME,
+  closes_at TIME,
+  reason TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (brewery_id, on_date),
+  CHECK ((opens_at IS NULL) = (closes_at IS NULL)),
+  CHECK (closes_at <> opens_at)
+);
+
+CREATE TRIGGER brewery_hours_audit
+  AFTER INSERT OR UPDATE OR DELETE ON brewery_hours
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER brewery_closures_audit
+  AFTER INSERT OR UPDATE OR DELETE ON brewery_closures
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
@@ -0,0 +1,36 @@
+-- Vendor tags by kind. Slugs are unique across kinds, so admin input and filters name a tag
+-- by slug alone.
+CREATE TABLE tags (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  kind TEXT NOT NULL CHECK (kind IN ('cuisine', 'dietary', 'price')),
+  slug TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+  name TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE food_vendor_tags (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  food_vendor_id UUID NOT NULL REFERENCES food_vendors(id) ON DELETE CASCADE,
+  tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+  UNIQUE (food_vendor_id, tag_id)
+);
+
+CREATE INDEX food_vendor_tags_tag_id_idx ON food_vendor_tags (tag_id);
+
+-- Dietary options and price levels every deployment starts with; cuisines are added as
+-- vendors need them.
+INSERT INTO tags (kind, slug, name)
+VALUES
+  ('dietary', 'vegetarian', 'Vegetarian options'),
+  ('dietary', 'vegan', 'Vegan options'),
+  ('dietary', 'gluten-free', 'Gluten-free options'),
+  ('price', 'price-1', '$'),
+  ('price', 'price-2', '$$'),
+  ('price', 'price-3', '$$$');
+
+CREATE TRIGGER tags_audit
+  AFTER INSERT OR UPDATE OR DELETE ON tags
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+CREATE TRIGGER food_vendor_tags_audit
+  AFTER INSERT OR UPDATE OR DELETE ON food_vendor_tags
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
@@ -0,0 +1,32 @@
+-- Each ingest of a vendor's food menu or a brewery's taplist that differs from the previous
+-- one becomes a new version; earlier versions are kept.
+CREATE TABLE menu_versions (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  food_vendor_id UUID REFERENCES food_vendors(id) ON DELETE CASCADE,
+  brewery_id UUID REFERENCES breweries(id) ON DELETE CASCADE,
+  version INTEGER NOT NULL CHECK (version > 0),
+  source_url TEXT,
+  fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK ((food_vendor_id IS NULL) <> (brewery_id IS NULL)),
+  UNIQUE (food_vendor_id, version),
+  UNIQUE (brewery_id, version)
+);
+
+CREATE TABLE menu_items (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  menu_version_id UUID NOT NULL REFERENCES menu_versions(id) ON DELETE CASCADE,
+  -- Order on the source menu.
+  position INTEGER NOT NULL,
+  section TEXT,
+  name TEXT NOT NULL,
+  description TEXT,
+  price_cents INTEGER CHECK (price_cents >= 0),
+  -- Dietary tag slugs such as `vegan`.
+  dietary TEXT[] NOT NULL DEFAULT '{}',
+  UNIQUE (menu_version_id, position)
+);
+
+-- Items are written with their version and never change, so only versions are audited.
+CREATE TRIGGER menu_versions_audit
+  AFTER INSERT OR UPDATE OR DELETE ON menu_versions
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
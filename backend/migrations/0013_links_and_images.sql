@@ -0,0 +1,33 @@
+-- Contact and social links of a vendor or brewery, at most one of each kind. Every value
+-- is a URL: `tel:` for phone and `mailto:` for email.
+CREATE TABLE links (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  food_vendor_id UUID REFERENCES food_vendors(id) ON DELETE CASCADE,
+  brewery_id UUID REFERENCES breweries(id) ON DELETE CASCADE,
+  kind TEXT NOT NULL CHECK (kind IN ('instagram', 'facebook', 'phone', 'email', 'ordering')),
+  url TEXT NOT NULL,
+  CHECK ((food_vendor_id IS NULL) <> (brewery_id IS NULL)),
+  UNIQUE (food_vendor_id, kind),
+  UNIQUE (brewery_id, kind)
+);
+
+-- Logo and hero images, hosted elsewhere, with credit to show next to them.
+CREATE TABLE images (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  food_vendor_id UUID REFERENCES food_vendors(id) ON DELETE CASCADE,
+  brewery_id UUID REFERENCES breweries(id) ON DELETE CASCADE,
+  kind TEXT NOT NULL CHECK (kind IN ('logo', 'hero')),
+  url TEXT NOT NULL,
+  attribution TEXT,
+  CHECK ((food_vendor_id IS NULL) <> (brewery_id IS NULL)),
+  UNIQUE (food_vendor_id, kind),
+  UNIQUE (brewery_id, kind)
+);
+
+CREATE TRIGGER links_audit
+  AFTER INSERT OR UPDATE OR DELETE ON links
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
+
+CREATE TRIGGER images_audit
+  AFTER INSERT OR UPDATE OR DELETE ON images
+  FOR EACH ROW EXECUTE FUNCTION record_audit_event();
@@ -0,0 +1,26 @@
+-- Structured provenance for schedule entries, so bad data can be traced back to the page
+-- and parser that produced it. `source` keeps naming the writer, such as a scraper.
+ALTER TABLE schedule_entries
+  ADD COLUMN source_kind TEXT NOT NULL DEFAULT 'manual'
+    CHECK (source_kind IN ('scraper', 'ics', 'manual', 'recurring')),
+  ADD COLUMN source_url TEXT,
+  -- The scrape run that last added or changed the entry.
+  ADD COLUMN scrape_run_id UUID REFERENCES scrape_runs(id) ON DELETE SET NULL,
+  ADD COLUMN parser_version TEXT,
+  ADD COLUMN confidence DOUBLE PRECISION NOT NULL DEFAULT 1
+    CHECK (confidence BETWEEN 0 AND 1);
+
+UPDATE schedule_entries e SET
+  source_kind = CASE
+    WHEN e.source IN ('manual', 'import', 'seed') THEN 'manual'
+    WHEN e.source = 'recurring' THEN 'recurring'
+    WHEN e.source = 'ics' THEN 'ics'
+    ELSE 'scraper'
+  END,
+  scrape_run_id = (
+    SELECT c.run_id FROM scrape_run_changes c
+    JOIN scrape_runs r ON r.id = c.run_id
+    WHERE c.schedule_entry_id = e.id AND c.kind <> 'removed'
+    ORDER BY r.started_at DESC
+    LIMIT 1
+  );
@@ -0,0 +1,13 @@
+-- "Until close" entries are stored without an upper bound. For overlap tests they end
+-- when their local day does, as the schedule check treats them; otherwise a past
+-- until-close entry would overlap every later window.
+CREATE FUNCTION slot_hours(hours TSTZRANGE, tz TEXT) RETURNS TSTZRANGE AS $$
+  SELECT CASE
+    WHEN upper_inf(hours) THEN TSTZRANGE(
+      lower(hours),
+      (date_trunc('day', lower(hours) AT TIME ZONE tz) + INTERVAL '1 day') AT TIME ZONE tz,
+      '[)'
+    )
+    ELSE hours
+  END;
+$$ LANGUAGE sql STABLE;
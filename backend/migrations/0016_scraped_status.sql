@@ -0,0 +1,13 @@
+-- Scrapers report each slot's status, so a cancelled slot is kept as `cancelled` instead
+-- of disappearing. Changes record the status on each side.
+ALTER TABLE scrape_run_changes
+  DROP CONSTRAINT scrape_run_changes_kind_check,
+  ADD CONSTRAINT scrape_run_changes_kind_check
+    CHECK (kind IN ('added', 'removed', 'time_shifted', 'vendor_swapped', 'status_changed')),
+  ADD COLUMN before_status TEXT,
+  ADD COLUMN after_status TEXT;
+
+-- Scrapers could not set a status before this.
+UPDATE scrape_run_changes SET
+  before_status = CASE WHEN before_hours IS NOT NULL THEN 'confirmed' END,
+  after_status = CASE WHEN after_hours IS NOT NULL THEN 'confirmed' END;
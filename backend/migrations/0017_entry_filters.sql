@@ -0,0 +1,33 @@
+-- Predicates shared by the schedule and stats queries, so they can't drift apart.
+
+-- Whether the override targets the entry: same brewery and vendor, overlapping slots.
+CREATE FUNCTION override_targets(o schedule_overrides, e schedule_entries, tz TEXT)
+RETURNS BOOLEAN AS $$
+  SELECT o.brewery_id = e.brewery_id
+    AND o.target_vendor_id = e.food_vendor_id
+    AND slot_hours(o.target_hours, tz) && slot_hours(e.open_hours, tz);
+$$ LANGUAGE sql STABLE;
+
+-- Whether a cancel override targets the entry. Expired ones count: a cancelled slot
+-- didn't happen, even once its override has expired.
+CREATE FUNCTION cancelled_by_override(e schedule_entries, tz TEXT) RETURNS BOOLEAN AS $$
+  SELECT EXISTS (
+    SELECT 1 FROM schedule_overrides o
+    WHERE o.kind = 'cancel' AND override_targets(o, e, tz)
+  );
+$$ LANGUAGE sql STABLE;
+
+-- Whether the vendor has any of `cuisines` (or `cuisines` is empty) and all of `dietary`.
+CREATE FUNCTION vendor_matches_tags(vendor_id UUID, cuisines TEXT[], dietary TEXT[])
+RETURNS BOOLEAN AS $$
+  SELECT (cardinality(cuisines) = 0 OR EXISTS (
+    SELECT 1 FROM food_vendor_tags vt
+    JOIN tags t ON t.id = vt.tag_id
+    WHERE vt.food_vendor_id = vendor_id AND t.kind = 'cuisine' AND t.slug = ANY(cuisines)
+  ))
+  AND (
+    SELECT count(*) FROM food_vendor_tags vt
+    JOIN tags t ON t.id = vt.tag_id
+    WHERE vt.food_vendor_id = vendor_id AND t.kind = 'dietary' AND t.slug = ANY(dietary)
+  ) = cardinality(dietary);
+$$ LANGUAGE sql STABLE;
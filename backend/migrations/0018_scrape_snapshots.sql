@@ -0,0 +1,3 @@
+-- Identifies exactly what a run parsed, such as `sha256:<hex>` of the fetched page or
+-- feed, so an entry's provenance can be traced back to the source content.
+ALTER TABLE scrape_runs ADD COLUMN snapshot_id TEXT;
@@ -0,0 +1,82 @@
+-- Schedule entries in a window with schedule overrides applied, so the schedule and stats
+-- queries agree on what happened. A cancel override reports its entry as `cancelled`, an
+-- edit replaces its entry's vendor and hours (reinstating a cancelled entry), and add
+-- overrides appear as rows of their own. Only the newest cancel or edit applies to an
+-- entry. Overrides that expired by `active_at` are ignored; stats pass `-infinity` so an
+-- override keeps correcting its slot once it has expired.
+CREATE FUNCTION merged_schedule_entries(
+  window_start TIMESTAMPTZ,
+  window_end TIMESTAMPTZ,
+  tz TEXT,
+  active_at TIMESTAMPTZ
+)
+RETURNS TABLE (
+  id UUID,
+  brewery_id UUID,
+  brewery_name TEXT,
+  food_vendor_id UUID,
+  food_vendor_name TEXT,
+  open_hours TSTZRANGE,
+  all_day BOOLEAN,
+  status TEXT,
+  source TEXT,
+  source_kind TEXT,
+  source_url TEXT,
+  scrape_run_id UUID,
+  parser_version TEXT,
+  confidence DOUBLE PRECISION,
+  updated_at TIMESTAMPTZ,
+  -- Set when an override added or edited the row.
+  override_id UUID
+) AS $$
+  WITH active_overrides AS (
+    SELECT * FROM schedule_overrides o
+    WHERE o.expires_at IS NULL OR o.expires_at > active_at
+  ),
+  -- Newest cancel/edit override per entry in the window.
+  entry_overrides AS (
+    SELECT DISTINCT ON (e.id) e.id AS entry_id, o.*
+    FROM schedule_entries e
+    JOIN active_overrides o ON o.kind IN ('cancel', 'edit') AND override_targets(o, e, tz)
+    WHERE lower(e.open_hours) < window_end
+      AND slot_hours(e.open_hours, tz) && TSTZRANGE(window_start, window_end, '[)')
+    ORDER BY e.id, o.created_at DESC
+  ),
+  merged AS (
+    SELECT
+      e.id, e.brewery_id, e.brewery_name, e.food_vendor_id, e.food_vendor_name, e.open_hours,
+      e.all_day, e.status, e.source, e.source_kind, e.source_url, e.scrape_run_id,
+      e.parser_version, e.confidence, e.updated_at, NULL::uuid AS override_id
+    FROM schedule_entries e
+    WHERE NOT EXISTS (SELECT 1 FROM entry_overrides eo WHERE eo.entry_id = e.id)
+    UNION ALL
+    SELECT
+      e.id, e.brewery_id, e.brewery_name, e.food_vendor_id, e.food_vendor_name, e.open_hours,
+      e.all_day, 'cancelled', e.source, e.source_kind, e.source_url, e.scrape_run_id,
+      e.parser_version, e.confidence, eo.created_at, eo.id
+    FROM schedule_entries e
+    JOIN entry_overrides eo ON eo.entry_id = e.id AND eo.kind = 'cancel'
+    UNION ALL
+    SELECT
+      e.id, e.brewery_id, e.brewery_name, eo.food_vendor_id, v.name, eo.open_hours,
+      eo.all_day, CASE e.status WHEN 'cancelled' THEN 'confirmed' ELSE e.status END,
+      'manual', 'manual', NULL, NULL, NULL, 1, eo.created_at, eo.id
+    FROM schedule_entries e
+    JOIN entry_overrides eo ON eo.entry_id = e.id AND eo.kind = 'edit'
+    JOIN food_vendors v ON v.id = eo.food_vendor_id
+    UNION ALL
+    SELECT
+      o.id, o.brewery_id, b.name, o.food_vendor_id, v.name, o.open_hours,
+      o.all_day, 'confirmed', 'manual', 'manual', NULL, NULL, NULL, 1, o.created_at, o.id
+    FROM active_overrides o
+    JOIN breweries b ON b.id = o.brewery_id
+    JOIN food_vendors v ON v.id = o.food_vendor_id
+    WHERE o.kind = 'add'
+  )
+  SELECT * FROM merged m
+  WHERE lower(m.open_hours) < window_end
+    AND slot_hours(m.open_hours, tz) && TSTZRANGE(window_start, window_end, '[)');
+$$ LANGUAGE sql STABLE;
+
+-- Stats now read cancellations through `merged_schedule_entries`.
+DROP FUNCTION cancelled_by_override(schedule_entries, TEXT);
@@ -1,7 +1,35 @@
+pub mod admin;
 mod breweries;
+mod error;
 mod schedules;
+mod stats;
+mod tags;
 mod vendors;
 
-pub use breweries::get_breweries;
+use crate::db::TagFilter;
+
+pub use breweries::{get_breweries, get_brewery, get_brewery_taplist};
 pub use schedules::get_schedules;
-pub use vendors::get_vendors;
+pub use stats::{get_brewery_stats, get_vendor_stats};
+pub use tags::get_tags;
+pub use vendors::{get_vendor, get_vendor_menu, get_vendors};
+
+/// Builds a tag filter from comma-separated `cuisine` and `dietary` query values.
+/// Slugs are lowercased and repeats dropped.
+fn tag_filter(cuisine: Option<&str>, dietary: Option<&str>) -> TagFilter {
+    let slugs = |value: Option<&str>| {
+        let mut slugs: Vec<String> = value
+            .unwrap_or_default()
+            .split(',')
+            .map(|slug| slug.trim().to_lowercase())
+            .filter(|slug| !slug.is_empty())
+            .collect();
+        slugs.sort();
+        slugs.dedup();
+        slugs
+    };
+    TagFilter {
+        cuisines: slugs(cuisine),
+        dietary: slugs(dietary),
+    }
+}
@@ -0,0 +1,148 @@
+mod audit;
+mod breweries;
+mod import;
+mod links;
+mod recurring_schedules;
+mod schedule_issues;
+mod schedule_overrides;
+mod schedules;
+mod scrape_runs;
+mod tags;
+mod vendors;
+
+use crate::db::Db;
+use crate::domain::{ApiKey, DomainError, Scope};
+use crate::middleware::{X_REQUEST_ID, admin_auth_middleware, require_scope};
+use crate::state::AppState;
+use axum::Router;
+use axum::extract::{FromRef, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::middleware::{Next, from_fn, from_fn_with_state};
+use axum::routing::{delete, get, patch, post, put};
+
+/// Write routes for correcting data by hand, mounted under `/api/admin`. Every route
+/// needs an API key; each write group also needs its scope.
+pub fn router(state: AppState) -> Router<AppState> {
+    let catalog = Router::new()
+        .route("/breweries", post(breweries::create_brewery))
+        .route(
+            "/breweries/{id}",
+            patch(breweries::update_brewery).delete(breweries::delete_brewery),
+        )
+        .route(
+            "/breweries/{id}/hours",
+            get(breweries::get_brewery_hours).put(breweries::set_weekly_hours),
+        )
+        .route(
+            "/breweries/{id}/closures",
+            post(breweries::create_brewery_closure),
+        )
+        .route(
+            "/breweries/{id}/closures/{closure_id}",
+            delete(breweries::delete_brewery_closure),
+        )
+        .route("/breweries/{id}/links", put(links::set_brewery_links))
+        .route("/breweries/{id}/images", put(links::set_brewery_images))
+        .route("/vendors", post(vendors::create_vendor))
+        .route(
+            "/vendors/{id}",
+            patch(vendors::update_vendor).delete(vendors::delete_vendor),
+        )
+        .route("/vendors/{id}/tags", put(tags::set_vendor_tags))
+        .route("/vendors/{id}/links", put(links::set_vendor_links))
+        .route("/vendors/{id}/images", put(links::set_vendor_images))
+        .route("/tags", post(tags::create_tag))
+        .route_layer(from_fn(|request: Request, next: Next| {
+            require_scope(Scope::CatalogWrite, request, next)
+        }));
+
+    let schedules = Router::new()
+        .route("/schedules", post(schedules::create_schedule))
+        .route(
+            "/schedules/{id}",
+            patch(schedules::update_schedule).delete(schedules::delete_schedule),
+        )
+        .route(
+            "/schedule-overrides",
+            get(schedule_overrides::get_schedule_overrides)
+                .post(schedule_overrides::create_schedule_override),
+        )
+        .route(
+            "/schedule-overrides/{id}",
+            delete(schedule_overrides::delete_schedule_override),
+        )
+        .route(
+            "/schedule-issues/check",
+            post(schedule_issues::check_schedules),
+        )
+        .route(
+            "/schedule-issues/{id}",
+            patch(schedule_issues::review_schedule_issue),
+        )
+        .route(
+            "/recurring-schedules",
+            get(recurring_schedules::get_recurring_schedules)
+                .post(recurring_schedules::create_recurring_schedule),
+        )
+        .route(
+            "/recurring-schedules/{id}",
+            delete(recurring_schedules::delete_recurring_schedule),
+        )
+        .route_layer(from_fn(|request: Request, next: Next| {
+            require_scope(Scope::SchedulesWrite, request, next)
+        }));
+
+    // Imports write breweries, vendors and schedule entries, so they need both scopes.
+    let import = Router::new()
+        .route("/import", post(import::import_records))
+        .route_layer(from_fn(|request: Request, next: Next| {
+            require_scope(Scope::SchedulesWrite, request, next)
+        }))
+        .route_layer(from_fn(|request: Request, next: Next| {
+            require_scope(Scope::CatalogWrite, request, next)
+        }));
+
+    Router::new()
+        .route("/audit", get(audit::get_audit_events))
+        .route("/scrape-status", get(scrape_runs::get_scrape_status))
+        .route(
+            "/schedule-issues",
+            get(schedule_issues::get_schedule_issues),
+        )
+        .route(
+            "/scrape-runs/{id}/diff",
+            get(scrape_runs::get_scrape_run_diff),
+        )
+        .merge(catalog)
+        .merge(schedules)
+        .merge(import)
+        .route_layer(from_fn_with_state(state, admin_auth_middleware))
+}
+
+/// A `Db` whose writes are attributed to the request's API key and `x-request-id` in
+/// the audit log. Only usable behind `admin_auth_middleware`.
+pub struct AuditedDb(pub Db);
+
+impl<S> FromRequestParts<S> for AuditedDb
+where
+    Db: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let key = parts
+            .extensions
+            .get::<ApiKey>()
+            .ok_or(DomainError::Unauthorized)?;
+        let request_id = parts
+            .headers
+            .get(X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let db = Db::from_ref(state);
+        Ok(AuditedDb(
+            db.with_audit(format!("api_key:{}", key.id), request_id),
+        ))
+    }
+}
@@ -0,0 +1,27 @@
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{AuditEvents, DomainError};
+use axum::Json;
+use axum::extract::{Query, State};
+use log::error;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct AuditQueryParams {
+    entity_id: Option<Uuid>,
+}
+
+#[axum::debug_handler]
+pub async fn get_audit_events(
+    State(db): State<Db>,
+    Query(params): Query<AuditQueryParams>,
+) -> Result<Json<AuditEvents>, DomainError> {
+    match domain::get_audit_events(&db, params.entity_id).await {
+        Ok(events) => Ok(Json(events)),
+        Err(e) => {
+            error!("Failed to get audit events: {}", e);
+            Err(e)
+        }
+    }
+}
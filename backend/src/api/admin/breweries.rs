@@ -0,0 +1,129 @@
+use crate::api::admin::AuditedDb;
+use crate::config::Config;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{
+    Brewery, BreweryClosure, BreweryHours, CreateBrewery, CreateBreweryClosure, DomainError,
+    SetWeeklyHours, UpdateBrewery,
+};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use log::error;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_brewery(
+    AuditedDb(db): AuditedDb,
+    Json(input): Json<CreateBrewery>,
+) -> Result<(StatusCode, Json<Brewery>), DomainError> {
+    match domain::create_brewery(&db, input).await {
+        Ok(brewery) => Ok((StatusCode::CREATED, Json(brewery))),
+        Err(e) => {
+            error!("Failed to create brewery: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn update_brewery(
+    AuditedDb(db): AuditedDb,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateBrewery>,
+) -> Result<Json<Brewery>, DomainError> {
+    let now = jiff::Timestamp::now();
+    match domain::update_brewery(&db, id, input, now, &config.local_tz).await {
+        Ok(brewery) => Ok(Json(brewery)),
+        Err(e) => {
+            error!("Failed to update brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_brewery(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, DomainError> {
+    match domain::delete_brewery(&db, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!("Failed to delete brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn get_brewery_hours(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<BreweryHours>, DomainError> {
+    let today = jiff::Timestamp::now()
+        .to_zoned(config.local_tz.clone())
+        .date();
+    match domain::get_brewery_hours(&db, id, today).await {
+        Ok(hours) => Ok(Json(hours)),
+        Err(e) => {
+            error!("Failed to get hours of brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_weekly_hours(
+    AuditedDb(db): AuditedDb,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetWeeklyHours>,
+) -> Result<Json<BreweryHours>, DomainError> {
+    let today = jiff::Timestamp::now()
+        .to_zoned(config.local_tz.clone())
+        .date();
+    match domain::set_weekly_hours(&db, id, input, today).await {
+        Ok(hours) => Ok(Json(hours)),
+        Err(e) => {
+            error!("Failed to set hours of brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_brewery_closure(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<CreateBreweryClosure>,
+) -> Result<(StatusCode, Json<BreweryClosure>), DomainError> {
+    match domain::create_brewery_closure(&db, id, input).await {
+        Ok(closure) => Ok((StatusCode::CREATED, Json(closure))),
+        Err(e) => {
+            error!("Failed to create closure for brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_brewery_closure(
+    AuditedDb(db): AuditedDb,
+    Path((id, closure_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, DomainError> {
+    match domain::delete_brewery_closure(&db, id, closure_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!(
+                "Failed to delete closure {} of brewery {}: {}",
+                closure_id, id, e
+            );
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::api::admin::AuditedDb;
+use crate::domain;
+use crate::domain::{DomainError, FieldError, ImportFormat, ImportKind, ImportReport};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::Query;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderMap, StatusCode};
+use log::error;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ImportQueryParams {
+    /// Records in a CSV body; JSON bodies name their sections.
+    kind: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Imports a JSON document or, with `Content-Type: text/csv`, one CSV file. Responds
+/// 422 with the report when any record was rejected, in which case nothing is written.
+#[axum::debug_handler(state = AppState)]
+pub async fn import_records(
+    AuditedDb(db): AuditedDb,
+    Query(params): Query<ImportQueryParams>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<(StatusCode, Json<ImportReport>), DomainError> {
+    let is_csv = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/csv"));
+    let format = if is_csv {
+        let kind = params
+            .kind
+            .as_deref()
+            .ok_or("is required for CSV imports".to_string())
+            .and_then(str::parse::<ImportKind>)
+            .map_err(|message| {
+                DomainError::Validation(vec![FieldError {
+                    field: "kind",
+                    message,
+                }])
+            })?;
+        ImportFormat::Csv(kind)
+    } else {
+        ImportFormat::Json
+    };
+
+    match domain::import_records(&db, format, &body, params.dry_run).await {
+        Ok(report) if report.has_errors() => Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(report))),
+        Ok(report) => Ok((StatusCode::OK, Json(report))),
+        Err(e) => {
+            error!("Failed to import records: {}", e);
+            Err(e)
+        }
+    }
+}
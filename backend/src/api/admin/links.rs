@@ -0,0 +1,69 @@
+use crate::api::admin::AuditedDb;
+use crate::db::VendorOrBrewery;
+use crate::domain;
+use crate::domain::{DomainError, Images, Links, SetImages, SetLinks};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::Path;
+use log::error;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_vendor_links(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetLinks>,
+) -> Result<Json<Links>, DomainError> {
+    match domain::set_links(&db, VendorOrBrewery::Vendor(id), input).await {
+        Ok(links) => Ok(Json(links)),
+        Err(e) => {
+            error!("Failed to set links of vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_brewery_links(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetLinks>,
+) -> Result<Json<Links>, DomainError> {
+    match domain::set_links(&db, VendorOrBrewery::Brewery(id), input).await {
+        Ok(links) => Ok(Json(links)),
+        Err(e) => {
+            error!("Failed to set links of brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_vendor_images(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetImages>,
+) -> Result<Json<Images>, DomainError> {
+    match domain::set_images(&db, VendorOrBrewery::Vendor(id), input).await {
+        Ok(images) => Ok(Json(images)),
+        Err(e) => {
+            error!("Failed to set images of vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_brewery_images(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetImages>,
+) -> Result<Json<Images>, DomainError> {
+    match domain::set_images(&db, VendorOrBrewery::Brewery(id), input).await {
+        Ok(images) => Ok(Json(images)),
+        Err(e) => {
+            error!("Failed to set images of brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::api::admin::AuditedDb;
+use crate::config::Config;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{
+    CreateRecurringSchedule, DomainError, RecurringHorizon, RecurringSchedule, RecurringSchedules,
+};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use log::error;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[axum::debug_handler]
+pub async fn get_recurring_schedules(
+    State(db): State<Db>,
+) -> Result<Json<RecurringSchedules>, DomainError> {
+    match domain::get_recurring_schedules(&db).await {
+        Ok(schedules) => Ok(Json(schedules)),
+        Err(e) => {
+            error!("Failed to get recurring schedules: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_recurring_schedule(
+    AuditedDb(db): AuditedDb,
+    State(config): State<Arc<Config>>,
+    Json(input): Json<CreateRecurringSchedule>,
+) -> Result<(StatusCode, Json<RecurringSchedule>), DomainError> {
+    let horizon = RecurringHorizon::new(
+        jiff::Timestamp::now(),
+        &config.local_tz,
+        config.horizon_days,
+    );
+    match domain::create_recurring_schedule(&db, input, &horizon).await {
+        Ok(schedule) => Ok((StatusCode::CREATED, Json(schedule))),
+        Err(e) => {
+            error!("Failed to create recurring schedule: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_recurring_schedule(
+    AuditedDb(db): AuditedDb,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, DomainError> {
+    let horizon = RecurringHorizon::new(
+        jiff::Timestamp::now(),
+        &config.local_tz,
+        config.horizon_days,
+    );
+    match domain::delete_recurring_schedule(&db, id, &horizon).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!("Failed to delete recurring schedule {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
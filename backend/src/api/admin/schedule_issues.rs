@@ -0,0 +1,64 @@
+use crate::api::admin::AuditedDb;
+use crate::config::Config;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{
+    ApiKey, DomainError, IssueStatus, ReviewScheduleIssue, ScheduleCheck, ScheduleIssue,
+    ScheduleIssues,
+};
+use crate::state::AppState;
+use axum::extract::{Path, Query, State};
+use axum::{Extension, Json};
+use log::error;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct IssueQueryParams {
+    status: Option<IssueStatus>,
+}
+
+#[axum::debug_handler]
+pub async fn get_schedule_issues(
+    State(db): State<Db>,
+    Query(params): Query<IssueQueryParams>,
+) -> Result<Json<ScheduleIssues>, DomainError> {
+    match domain::get_schedule_issues(&db, params.status).await {
+        Ok(issues) => Ok(Json(issues)),
+        Err(e) => {
+            error!("Failed to get schedule issues: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn check_schedules(
+    AuditedDb(db): AuditedDb,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<ScheduleCheck>, DomainError> {
+    match domain::check_schedules(&db, jiff::Timestamp::now(), &config.local_tz).await {
+        Ok(check) => Ok(Json(check)),
+        Err(e) => {
+            error!("Failed to check schedules: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn review_schedule_issue(
+    AuditedDb(db): AuditedDb,
+    Extension(key): Extension<ApiKey>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<ReviewScheduleIssue>,
+) -> Result<Json<ScheduleIssue>, DomainError> {
+    match domain::review_schedule_issue(&db, id, input, &key.name).await {
+        Ok(issue) => Ok(Json(issue)),
+        Err(e) => {
+            error!("Failed to review schedule issue {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::api::admin::AuditedDb;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{
+    ApiKey, CreateScheduleOverride, DomainError, ScheduleOverride, ScheduleOverrides,
+};
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use log::error;
+use uuid::Uuid;
+
+#[axum::debug_handler]
+pub async fn get_schedule_overrides(
+    State(db): State<Db>,
+) -> Result<Json<ScheduleOverrides>, DomainError> {
+    match domain::get_schedule_overrides(&db).await {
+        Ok(overrides) => Ok(Json(overrides)),
+        Err(e) => {
+            error!("Failed to get schedule overrides: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_schedule_override(
+    AuditedDb(db): AuditedDb,
+    Extension(key): Extension<ApiKey>,
+    Json(input): Json<CreateScheduleOverride>,
+) -> Result<(StatusCode, Json<ScheduleOverride>), DomainError> {
+    match domain::create_schedule_override(&db, input, &key.name).await {
+        Ok(schedule_override) => Ok((StatusCode::CREATED, Json(schedule_override))),
+        Err(e) => {
+            error!("Failed to create schedule override: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_schedule_override(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, DomainError> {
+    match domain::delete_schedule_override(&db, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!("Failed to delete schedule override {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::api::admin::AuditedDb;
+use crate::domain;
+use crate::domain::{CreateSchedule, DomainError, Schedule, UpdateSchedule};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use log::error;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_schedule(
+    AuditedDb(db): AuditedDb,
+    Json(input): Json<CreateSchedule>,
+) -> Result<(StatusCode, Json<Schedule>), DomainError> {
+    match domain::create_schedule(&db, input).await {
+        Ok(schedule) => Ok((StatusCode::CREATED, Json(schedule))),
+        Err(e) => {
+            error!("Failed to create schedule entry: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn update_schedule(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateSchedule>,
+) -> Result<Json<Schedule>, DomainError> {
+    match domain::update_schedule(&db, id, input).await {
+        Ok(schedule) => Ok(Json(schedule)),
+        Err(e) => {
+            error!("Failed to update schedule entry {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_schedule(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, DomainError> {
+    match domain::cancel_schedule(&db, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!("Failed to cancel schedule entry {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::config::Config;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{DomainError, ScrapeRunDiff, ScrapeStatus};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::{Path, State};
+use log::error;
+use sqlx::types::chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn get_scrape_status(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<ScrapeStatus>, DomainError> {
+    let interval = Duration::from_hours(config.scrape_interval_hours);
+    match domain::get_scrape_status(&db, interval, Utc::now()).await {
+        Ok(status) => Ok(Json(status)),
+        Err(e) => {
+            error!("Failed to get scrape status: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler]
+pub async fn get_scrape_run_diff(
+    State(db): State<Db>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ScrapeRunDiff>, DomainError> {
+    match domain::get_scrape_run_diff(&db, id).await {
+        Ok(diff) => Ok(Json(diff)),
+        Err(e) => {
+            error!("Failed to get diff for scrape run {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
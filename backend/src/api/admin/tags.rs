@@ -0,0 +1,38 @@
+use crate::api::admin::AuditedDb;
+use crate::domain;
+use crate::domain::{CreateTag, DomainError, SetVendorTags, Tag, VendorTags};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use log::error;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_tag(
+    AuditedDb(db): AuditedDb,
+    Json(input): Json<CreateTag>,
+) -> Result<(StatusCode, Json<Tag>), DomainError> {
+    match domain::create_tag(&db, input).await {
+        Ok(tag) => Ok((StatusCode::CREATED, Json(tag))),
+        Err(e) => {
+            error!("Failed to create tag: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn set_vendor_tags(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetVendorTags>,
+) -> Result<Json<VendorTags>, DomainError> {
+    match domain::set_vendor_tags(&db, id, input).await {
+        Ok(tags) => Ok(Json(tags)),
+        Err(e) => {
+            error!("Failed to set tags of vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
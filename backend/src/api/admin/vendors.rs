@@ -0,0 +1,52 @@
+use crate::api::admin::AuditedDb;
+use crate::domain;
+use crate::domain::{CreateVendor, DomainError, UpdateVendor, Vendor};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use log::error;
+use uuid::Uuid;
+
+#[axum::debug_handler(state = AppState)]
+pub async fn create_vendor(
+    AuditedDb(db): AuditedDb,
+    Json(input): Json<CreateVendor>,
+) -> Result<(StatusCode, Json<Vendor>), DomainError> {
+    match domain::create_vendor(&db, input).await {
+        Ok(vendor) => Ok((StatusCode::CREATED, Json(vendor))),
+        Err(e) => {
+            error!("Failed to create vendor: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn update_vendor(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+    Json(input): Json<UpdateVendor>,
+) -> Result<Json<Vendor>, DomainError> {
+    match domain::update_vendor(&db, id, input).await {
+        Ok(vendor) => Ok(Json(vendor)),
+        Err(e) => {
+            error!("Failed to update vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn delete_vendor(
+    AuditedDb(db): AuditedDb,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, DomainError> {
+    match domain::delete_vendor(&db, id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!("Failed to delete vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
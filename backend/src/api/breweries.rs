@@ -1,18 +1,55 @@
-use crate::db::Db;
+use crate::api::vendors::MenuQueryParams;
+use crate::config::Config;
+use crate::db::{Db, VendorOrBrewery};
 use crate::domain;
-use crate::domain::Breweries;
-use axum::extract::State;
-use axum::http::StatusCode;
+use crate::domain::{Breweries, BreweryDetail, DomainError, Menu};
+use crate::state::AppState;
 use axum::Json;
+use axum::extract::{Path, Query, State};
 use log::error;
+use std::sync::Arc;
+use uuid::Uuid;
 
-#[axum::debug_handler]
-pub async fn get_breweries(State(db): State<Db>) -> Result<Json<Breweries>, StatusCode> {
-    match domain::get_breweries(&db).await {
+#[axum::debug_handler(state = AppState)]
+pub async fn get_breweries(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<Breweries>, DomainError> {
+    match domain::get_breweries(&db, jiff::Timestamp::now(), &config.local_tz).await {
         Ok(breweries) => Ok(Json(breweries)),
         Err(e) => {
             error!("Failed to retrieve breweries: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn get_brewery(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<BreweryDetail>, DomainError> {
+    match domain::get_brewery(&db, id, jiff::Timestamp::now(), &config.local_tz).await {
+        Ok(brewery) => Ok(Json(brewery)),
+        Err(e) => {
+            error!("Failed to retrieve brewery {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler]
+pub async fn get_brewery_taplist(
+    State(db): State<Db>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<MenuQueryParams>,
+) -> Result<Json<Menu>, DomainError> {
+    match domain::get_menu(&db, VendorOrBrewery::Brewery(id), params.version).await {
+        Ok(taplist) => Ok(Json(taplist)),
+        Err(e) => {
+            error!("Failed to retrieve taplist of brewery {}: {}", id, e);
+            Err(e)
         }
     }
 }
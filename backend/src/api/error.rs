@@ -0,0 +1,155 @@
+use crate::domain::{DomainError, FieldError};
+use axum::Json;
+use axum::http::StatusCode;
+use axum::http::header::{RETRY_AFTER, WWW_AUTHENTICATE};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+/// Seconds clients should wait before retrying when the pool is exhausted.
+const RETRY_AFTER_SECS: &str = "5";
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldError>,
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(ErrorBody {
+            error: message.into(),
+            fields: Vec::new(),
+        }),
+    )
+        .into_response()
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        match self {
+            DomainError::RepoUnavailable(_) => {
+                let mut response =
+                    error_response(StatusCode::SERVICE_UNAVAILABLE, "service unavailable");
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, RETRY_AFTER_SECS.parse().unwrap());
+                response
+            }
+            DomainError::Timeout => {
+                error_response(StatusCode::GATEWAY_TIMEOUT, "request timed out")
+            }
+            DomainError::NotFound => error_response(StatusCode::NOT_FOUND, "not found"),
+            DomainError::Validation(fields) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorBody {
+                    error: "validation failed".to_string(),
+                    fields,
+                }),
+            )
+                .into_response(),
+            DomainError::Unauthorized => {
+                let mut response = error_response(StatusCode::UNAUTHORIZED, self.to_string());
+                response
+                    .headers_mut()
+                    .insert(WWW_AUTHENTICATE, "Bearer".parse().unwrap());
+                response
+            }
+            DomainError::Forbidden(_) => error_response(StatusCode::FORBIDDEN, self.to_string()),
+            DomainError::Conflict(reason) => error_response(StatusCode::CONFLICT, reason),
+            DomainError::OutsideHorizon { .. } => {
+                error_response(StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
+            DomainError::InvalidRow { id, .. } => error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("invalid stored data for row {}", id),
+            ),
+            DomainError::Repo(_) => {
+                error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use crate::domain::Scope;
+    use uuid::Uuid;
+
+    #[test]
+    fn repo_unavailable_is_503_with_retry_after() {
+        let err = DomainError::from(RepoError::Unavailable(sqlx::Error::PoolTimedOut));
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[RETRY_AFTER], RETRY_AFTER_SECS);
+    }
+
+    #[test]
+    fn timeout_is_504() {
+        let response = DomainError::Timeout.into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[test]
+    fn not_found_is_404() {
+        let response = DomainError::NotFound.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn invalid_row_is_500() {
+        let err = DomainError::InvalidRow {
+            id: Uuid::nil(),
+            reason: "vendor name is empty",
+        };
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn outside_horizon_is_422() {
+        let err = DomainError::OutsideHorizon {
+            data_available_until: "2026-02-08T00:00:00+00:00".to_string(),
+        };
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn validation_is_422() {
+        let err = DomainError::Validation(vec![FieldError {
+            field: "name",
+            message: "must not be empty".to_string(),
+        }]);
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn conflict_is_409() {
+        let response = DomainError::Conflict("brewery still has schedule entries").into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn unauthorized_is_401_with_bearer_challenge() {
+        let response = DomainError::Unauthorized.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[WWW_AUTHENTICATE], "Bearer");
+    }
+
+    #[test]
+    fn forbidden_is_403() {
+        let response = DomainError::Forbidden(Scope::CatalogWrite).into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -1,35 +1,87 @@
-use crate::db::Db;
+use crate::api::tag_filter;
+use crate::config::Config;
+use crate::db::{Db, ScheduleFilter, TagFilter};
 use crate::domain;
-use crate::domain::VendorSchedules;
+use crate::domain::{DomainError, SchedulesEnvelope};
+use crate::state::AppState;
 use axum::body::Body;
 use axum::extract::rejection::QueryRejection;
-use axum::extract::{FromRequestParts, Query, Request, State};
-use axum::http::request::Parts;
+use axum::extract::{FromRef, FromRequestParts, Query, State};
 use axum::http::StatusCode;
+use axum::http::request::Parts;
 use axum::response::{IntoResponse, Response};
 use axum::{Json, RequestPartsExt};
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::{Span, Timestamp, Zoned};
 use log::{error, warn};
 use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Raw query parameters. A window is one start form (`start_hour_utc`, `start` or
+/// `date`) plus one end form (`duration_hours`, `duration` or `end`); `date` alone
+/// covers that local day. `cuisine` and `dietary` take comma-separated tag slugs.
 #[derive(Deserialize)]
 pub struct GetSchedulesQueryParams {
-    start_hour_utc: String,
-    duration_hours: u64,
+    start_hour_utc: Option<String>,
+    duration_hours: Option<u64>,
+    start: Option<String>,
+    end: Option<String>,
+    duration: Option<String>,
+    date: Option<String>,
+    tz: Option<String>,
+    #[serde(default)]
+    include_warnings: bool,
+    #[serde(default)]
+    include_cancelled: bool,
+    #[serde(default)]
+    include_predicted: bool,
+    cuisine: Option<String>,
+    dietary: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct GetSchedulesInput {
-    start_hour_utc: jiff::Timestamp,
-    duration_hours: u64,
+    start: Timestamp,
+    end: Timestamp,
+    include_warnings: bool,
+    include_cancelled: bool,
+    include_predicted: bool,
+    tags: TagFilter,
 }
 
 #[derive(Debug, Error)]
 pub enum GetSchedulesRejection {
+    #[error("Missing window start. Provide one of start_hour_utc, start or date")]
+    MissingStart,
+    #[error("Conflicting window start. Provide only one of start_hour_utc, start or date")]
+    ConflictingStart,
+    #[error("Missing window end. Provide one of duration_hours, duration or end")]
+    MissingEnd,
+    #[error("Conflicting window end. Provide only one of duration_hours, duration or end")]
+    ConflictingEnd,
     #[error("Invalid start_hour_utc. Must be a valid ISO 8601 timestamp with hour precision")]
     InvalidStartHourUtc,
-    #[error("Invalid duration_hours. Must be between 1 and 168 hours")]
-    InvalidDurationHours,
+    #[error("Invalid start. Must be an RFC 3339 timestamp")]
+    InvalidStart,
+    #[error("Invalid end. Must be an RFC 3339 timestamp")]
+    InvalidEnd,
+    #[error("Invalid date. Must be formatted as YYYY-MM-DD")]
+    InvalidDate,
+    #[error("Invalid tz. Must be an IANA time zone name such as America/Los_Angeles")]
+    InvalidTimeZone,
+    #[error("tz is only valid together with date")]
+    TimeZoneWithoutDate,
+    #[error("Invalid duration. Must be a positive ISO 8601 duration such as PT12H or P2D")]
+    InvalidDuration,
+    #[error("Invalid duration_hours. Must be between 1 and {max_hours} hours")]
+    InvalidDurationHours { max_hours: u64 },
+    #[error("Invalid window. end must be after start")]
+    EmptyWindow,
+    #[error("Invalid window. Must not exceed {max_hours} hours")]
+    WindowTooLarge { max_hours: u64 },
     #[error(transparent)]
     QueryRejection(#[from] QueryRejection),
 }
@@ -43,48 +95,157 @@ impl IntoResponse for GetSchedulesRejection {
     }
 }
 
-impl<S: Sync> FromRequestParts<S> for GetSchedulesInput {
+impl<S> FromRequestParts<S> for GetSchedulesInput
+where
+    S: Sync,
+    Arc<Config>: FromRef<S>,
+{
     type Rejection = GetSchedulesRejection;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Arc::<Config>::from_ref(state);
         let params = parts.extract::<Query<GetSchedulesQueryParams>>().await?;
-        let start = match format!("{}:00:00Z", params.start_hour_utc).parse::<jiff::Timestamp>() {
-            Ok(ts) => ts,
-            Err(e) => {
-                warn!(
-                    "Failed to parse start_hour_utc '{}': {}",
-                    params.start_hour_utc, e
-                );
-                return Err(GetSchedulesRejection::InvalidStartHourUtc);
+        let max_hours = config.max_window_hours;
+
+        if params.tz.is_some() && params.date.is_none() {
+            return Err(GetSchedulesRejection::TimeZoneWithoutDate);
+        }
+
+        let start = match (&params.start_hour_utc, &params.start, &params.date) {
+            (Some(hour), None, None) => parse_start_hour_utc(hour)?,
+            (None, Some(start), None) => start
+                .parse::<Timestamp>()
+                .map(|ts| ts.to_zoned(TimeZone::UTC))
+                .map_err(|e| {
+                    warn!("Failed to parse start '{}': {}", start, e);
+                    GetSchedulesRejection::InvalidStart
+                })?,
+            (None, None, Some(date)) => {
+                let tz = match &params.tz {
+                    Some(name) => TimeZone::get(name).map_err(|e| {
+                        warn!("Failed to resolve tz '{}': {}", name, e);
+                        GetSchedulesRejection::InvalidTimeZone
+                    })?,
+                    None => config.local_tz.clone(),
+                };
+                parse_local_date(date, tz)?
+            }
+            (None, None, None) => return Err(GetSchedulesRejection::MissingStart),
+            _ => return Err(GetSchedulesRejection::ConflictingStart),
+        };
+
+        let end = match (params.duration_hours, &params.duration, &params.end) {
+            (Some(hours), None, None) => {
+                if hours < 1 || hours > max_hours {
+                    warn!(
+                        "Invalid duration_hours. Must be between 1 and {} hours: {}",
+                        max_hours, hours
+                    );
+                    return Err(GetSchedulesRejection::InvalidDurationHours { max_hours });
+                }
+                start.timestamp() + Duration::from_hours(hours)
             }
+            (None, Some(duration), None) => add_iso_duration(&start, duration)?,
+            (None, None, Some(end)) => end.parse::<Timestamp>().map_err(|e| {
+                warn!("Failed to parse end '{}': {}", end, e);
+                GetSchedulesRejection::InvalidEnd
+            })?,
+            // A bare local date covers that whole day.
+            (None, None, None) if params.date.is_some() => add_iso_duration(&start, "P1D")?,
+            (None, None, None) => return Err(GetSchedulesRejection::MissingEnd),
+            _ => return Err(GetSchedulesRejection::ConflictingEnd),
         };
 
-        if params.duration_hours < 1 || params.duration_hours > 168 {
+        let start = start.timestamp();
+        if end <= start {
+            return Err(GetSchedulesRejection::EmptyWindow);
+        }
+        if end.duration_since(start) > jiff::SignedDuration::from_hours(max_hours as i64) {
             warn!(
-                "Invalid duration_hours. Must be between 1 and 168 hours: {}",
-                params.duration_hours
+                "Schedule window {} to {} exceeds {} hours",
+                start, end, max_hours
             );
-            return Err(GetSchedulesRejection::InvalidDurationHours);
+            return Err(GetSchedulesRejection::WindowTooLarge { max_hours });
         }
 
         Ok(GetSchedulesInput {
-            start_hour_utc: start,
-            duration_hours: params.duration_hours,
+            start,
+            end,
+            include_warnings: params.include_warnings,
+            include_cancelled: params.include_cancelled,
+            include_predicted: params.include_predicted,
+            tags: tag_filter(params.cuisine.as_deref(), params.dietary.as_deref()),
         })
     }
 }
 
+/// Parses the legacy `YYYY-MM-DDTHH` UTC hour bucket.
+fn parse_start_hour_utc(value: &str) -> Result<Zoned, GetSchedulesRejection> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        warn!("Failed to parse start_hour_utc '{}': {}", value, e);
+        GetSchedulesRejection::InvalidStartHourUtc
+    };
+    let datetime = jiff::fmt::strtime::parse("%Y-%m-%dT%H", value)
+        .and_then(|tm| tm.to_datetime())
+        .map_err(|e| invalid(&e))?;
+    datetime.to_zoned(TimeZone::UTC).map_err(|e| invalid(&e))
+}
+
+/// Resolves a local `YYYY-MM-DD` to the start of that day in `tz`.
+fn parse_local_date(value: &str, tz: TimeZone) -> Result<Zoned, GetSchedulesRejection> {
+    value
+        .parse::<Date>()
+        .and_then(|date| date.to_zoned(tz))
+        .and_then(|zoned| zoned.start_of_day())
+        .map_err(|e| {
+            warn!("Failed to parse date '{}': {}", value, e);
+            GetSchedulesRejection::InvalidDate
+        })
+}
+
+/// Adds an ISO 8601 duration in the start's time zone, so `P1D` follows local days.
+fn add_iso_duration(start: &Zoned, value: &str) -> Result<Timestamp, GetSchedulesRejection> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        warn!("Invalid duration '{}': {}", value, e);
+        GetSchedulesRejection::InvalidDuration
+    };
+    let span = value.parse::<Span>().map_err(|e| invalid(&e))?;
+    if !span.is_positive() {
+        return Err(invalid(&"duration must be positive"));
+    }
+    start
+        .checked_add(span)
+        .map(|end| end.timestamp())
+        .map_err(|e| invalid(&e))
+}
+
 /// Handles schedule queries after query extraction/validation by `GetSchedulesInput`.
-#[axum::debug_handler]
+#[axum::debug_handler(state = AppState)]
 pub async fn get_schedules(
     State(db): State<Db>,
+    State(config): State<Arc<Config>>,
     params: GetSchedulesInput,
-) -> Result<Json<VendorSchedules>, StatusCode> {
-    match domain::get_schedules(&db, params.start_hour_utc, params.duration_hours).await {
-        Ok(schedules) => Ok(Json(schedules)),
+) -> Result<Json<SchedulesEnvelope>, DomainError> {
+    let filter = ScheduleFilter {
+        include_cancelled: params.include_cancelled,
+        tags: params.tags,
+    };
+    let result = domain::get_schedules(
+        &db,
+        params.start,
+        params.end,
+        &filter,
+        params.include_predicted,
+        config.horizon_days,
+        &config.local_tz,
+    )
+    .await;
+    match result {
+        Ok(schedules) if params.include_warnings => Ok(Json(schedules)),
+        Ok(schedules) => Ok(Json(schedules.without_warnings())),
         Err(e) => {
             error!("Failed to retrieve schedules: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
         }
     }
 }
@@ -92,6 +253,7 @@ pub async fn get_schedules(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::extract::Request;
 
     #[tokio::test]
     async fn test_missing_query_params() {
@@ -101,11 +263,11 @@ mod tests {
             .unwrap()
             .into_parts();
 
-        let input = GetSchedulesInput::from_request_parts(&mut parts, &())
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
             .await
             .unwrap_err();
 
-        assert!(matches!(input, GetSchedulesRejection::QueryRejection(_)));
+        assert!(matches!(input, GetSchedulesRejection::MissingEnd));
         assert_eq!(input.into_response().status(), StatusCode::BAD_REQUEST);
     }
 
@@ -117,11 +279,14 @@ mod tests {
             .unwrap()
             .into_parts();
 
-        let input = GetSchedulesInput::from_request_parts(&mut parts, &())
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
             .await
             .unwrap_err();
 
-        assert!(matches!(input, GetSchedulesRejection::InvalidDurationHours));
+        assert!(matches!(
+            input,
+            GetSchedulesRejection::InvalidDurationHours { .. }
+        ));
         assert_eq!(input.into_response().status(), StatusCode::BAD_REQUEST);
     }
 
@@ -133,11 +298,14 @@ mod tests {
             .unwrap()
             .into_parts();
 
-        let input = GetSchedulesInput::from_request_parts(&mut parts, &())
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
             .await
             .unwrap_err();
 
-        assert!(matches!(input, GetSchedulesRejection::InvalidDurationHours));
+        assert!(matches!(
+            input,
+            GetSchedulesRejection::InvalidDurationHours { .. }
+        ));
         assert_eq!(input.into_response().status(), StatusCode::BAD_REQUEST);
     }
 
@@ -149,7 +317,7 @@ mod tests {
             .unwrap()
             .into_parts();
 
-        let input = GetSchedulesInput::from_request_parts(&mut parts, &())
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
             .await
             .unwrap_err();
 
@@ -165,14 +333,249 @@ mod tests {
             .unwrap()
             .into_parts();
 
-        let input = GetSchedulesInput::from_request_parts(&mut parts, &())
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
             .await
             .unwrap();
 
-        assert_eq!(
-            input.start_hour_utc,
-            "2024-01-01T12:00:00Z".parse().unwrap()
-        );
-        assert_eq!(input.duration_hours, 12);
+        assert_eq!(input.start, "2024-01-01T12:00:00Z".parse().unwrap());
+        assert_eq!(input.end, "2024-01-02T00:00:00Z".parse().unwrap());
+        assert!(!input.include_warnings);
+    }
+
+    #[tokio::test]
+    async fn test_include_warnings() {
+        let (mut parts, _body) = Request::builder()
+            .uri("/schedules?start_hour_utc=2024-01-01T12&duration_hours=12&include_warnings=true")
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+
+        let input = GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(Config::default()))
+            .await
+            .unwrap();
+
+        assert!(input.include_warnings);
+    }
+
+    #[tokio::test]
+    async fn test_include_cancelled() {
+        let input = extract(
+            "/schedules?date=2024-01-01&include_cancelled=true",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(input.include_cancelled);
+        assert!(!input.include_warnings);
+    }
+
+    #[tokio::test]
+    async fn test_include_predicted() {
+        let input = extract(
+            "/schedules?date=2024-01-01&include_predicted=true",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(input.include_predicted);
+        assert!(!input.include_cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_tag_filters() {
+        let input = extract(
+            "/schedules?date=2024-01-01&cuisine=Mexican,%20cajun,mexican&dietary=vegan,",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(input.tags.cuisines, ["cajun", "mexican"]);
+        assert_eq!(input.tags.dietary, ["vegan"]);
+    }
+
+    async fn extract(
+        uri: &str,
+        config: Config,
+    ) -> Result<GetSchedulesInput, GetSchedulesRejection> {
+        let (mut parts, _body) = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+            .into_parts();
+
+        GetSchedulesInput::from_request_parts(&mut parts, &Arc::new(config)).await
+    }
+
+    #[tokio::test]
+    async fn test_non_numeric_duration_hours() {
+        let input = extract(
+            "/schedules?start_hour_utc=2024-01-01T00&duration_hours=abc",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::QueryRejection(_)));
+    }
+
+    #[tokio::test]
+    async fn test_rfc3339_start_and_end() {
+        let input = extract(
+            "/schedules?start=2024-01-01T12:30:00-08:00&end=2024-01-02T00:00:00Z",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(input.start, "2024-01-01T20:30:00Z".parse().unwrap());
+        assert_eq!(input.end, "2024-01-02T00:00:00Z".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_start_with_iso_duration() {
+        let input = extract(
+            "/schedules?start=2024-01-01T00:00:00Z&duration=P2D",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(input.end, "2024-01-03T00:00:00Z".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_local_date_covers_local_day() {
+        let input = extract(
+            "/schedules?date=2024-03-10&tz=America/Los_Angeles",
+            Config::default(),
+        )
+        .await
+        .unwrap();
+
+        // DST starts on 2024-03-10 in Los Angeles, so the local day is 23 hours long.
+        assert_eq!(input.start, "2024-03-10T08:00:00Z".parse().unwrap());
+        assert_eq!(input.end, "2024-03-11T07:00:00Z".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_local_date_uses_configured_tz() {
+        let config = Config {
+            local_tz: TimeZone::UTC,
+            ..Config::default()
+        };
+        let input = extract("/schedules?date=2024-01-01", config).await.unwrap();
+
+        assert_eq!(input.start, "2024-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(input.end, "2024-01-02T00:00:00Z".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tz() {
+        let input = extract(
+            "/schedules?date=2024-01-01&tz=Mars/Olympus",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::InvalidTimeZone));
+    }
+
+    #[tokio::test]
+    async fn test_tz_without_date() {
+        let input = extract(
+            "/schedules?start=2024-01-01T00:00:00Z&duration=PT1H&tz=UTC",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::TimeZoneWithoutDate));
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_start() {
+        let input = extract(
+            "/schedules?start_hour_utc=2024-01-01T00&start=2024-01-01T00:00:00Z&duration_hours=1",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::ConflictingStart));
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_end() {
+        let input = extract(
+            "/schedules?start_hour_utc=2024-01-01T00&duration_hours=1&duration=PT1H",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::ConflictingEnd));
+    }
+
+    #[tokio::test]
+    async fn test_end_before_start() {
+        let input = extract(
+            "/schedules?start=2024-01-02T00:00:00Z&end=2024-01-01T00:00:00Z",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::EmptyWindow));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_iso_duration() {
+        let input = extract(
+            "/schedules?start=2024-01-01T00:00:00Z&duration=two-days",
+            Config::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(input, GetSchedulesRejection::InvalidDuration));
+    }
+
+    #[tokio::test]
+    async fn test_window_above_configured_max() {
+        let config = Config {
+            max_window_hours: 24,
+            ..Config::default()
+        };
+        let input = extract("/schedules?start=2024-01-01T00:00:00Z&duration=P2D", config)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            input,
+            GetSchedulesRejection::WindowTooLarge { max_hours: 24 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_duration_hours_above_configured_max() {
+        let config = Config {
+            max_window_hours: 24,
+            ..Config::default()
+        };
+        let input = extract(
+            "/schedules?start_hour_utc=2024-01-01T00&duration_hours=25",
+            config,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            input,
+            GetSchedulesRejection::InvalidDurationHours { max_hours: 24 }
+        ));
     }
 }
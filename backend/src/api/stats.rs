@@ -0,0 +1,48 @@
+use crate::config::Config;
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{BreweryStatsReport, DomainError, VendorStatsReport};
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::{Query, State};
+use log::error;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Overrides the configured lookback.
+#[derive(Deserialize)]
+pub struct StatsQueryParams {
+    days: Option<u64>,
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn get_vendor_stats(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+    Query(params): Query<StatsQueryParams>,
+) -> Result<Json<VendorStatsReport>, DomainError> {
+    let days = params.days.unwrap_or(config.stats_lookback_days);
+    match domain::get_vendor_stats(&db, jiff::Timestamp::now(), days, &config.local_tz).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => {
+            error!("Failed to retrieve vendor stats: {}", e);
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler(state = AppState)]
+pub async fn get_brewery_stats(
+    State(db): State<Db>,
+    State(config): State<Arc<Config>>,
+    Query(params): Query<StatsQueryParams>,
+) -> Result<Json<BreweryStatsReport>, DomainError> {
+    let days = params.days.unwrap_or(config.stats_lookback_days);
+    match domain::get_brewery_stats(&db, jiff::Timestamp::now(), days, &config.local_tz).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => {
+            error!("Failed to retrieve brewery stats: {}", e);
+            Err(e)
+        }
+    }
+}
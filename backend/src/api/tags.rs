@@ -0,0 +1,17 @@
+use crate::db::Db;
+use crate::domain;
+use crate::domain::{DomainError, Tags};
+use axum::Json;
+use axum::extract::State;
+use log::error;
+
+#[axum::debug_handler]
+pub async fn get_tags(State(db): State<Db>) -> Result<Json<Tags>, DomainError> {
+    match domain::get_tags(&db).await {
+        Ok(tags) => Ok(Json(tags)),
+        Err(e) => {
+            error!("Failed to retrieve tags: {}", e);
+            Err(e)
+        }
+    }
+}
@@ -1,18 +1,66 @@
-use crate::db::Db;
+use crate::api::tag_filter;
+use crate::db::{Db, VendorOrBrewery};
 use crate::domain;
-use crate::domain::Vendors;
-use axum::extract::State;
-use axum::http::StatusCode;
+use crate::domain::{DomainError, Menu, VendorDetail, Vendors};
 use axum::Json;
+use axum::extract::{Path, Query, State};
 use log::error;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Comma-separated tag slugs; see `tag_filter`.
+#[derive(Deserialize)]
+pub struct VendorQueryParams {
+    cuisine: Option<String>,
+    dietary: Option<String>,
+}
 
 #[axum::debug_handler]
-pub async fn get_vendors(State(db): State<Db>) -> Result<Json<Vendors>, StatusCode> {
-    match domain::get_vendors(&db).await {
+pub async fn get_vendors(
+    State(db): State<Db>,
+    Query(params): Query<VendorQueryParams>,
+) -> Result<Json<Vendors>, DomainError> {
+    let filter = tag_filter(params.cuisine.as_deref(), params.dietary.as_deref());
+    match domain::get_vendors(&db, &filter).await {
         Ok(vendors) => Ok(Json(vendors)),
         Err(e) => {
             error!("Failed to retrieve vendors: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e)
+        }
+    }
+}
+
+#[axum::debug_handler]
+pub async fn get_vendor(
+    State(db): State<Db>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<VendorDetail>, DomainError> {
+    match domain::get_vendor(&db, id).await {
+        Ok(vendor) => Ok(Json(vendor)),
+        Err(e) => {
+            error!("Failed to retrieve vendor {}: {}", id, e);
+            Err(e)
+        }
+    }
+}
+
+/// Selects an earlier menu version; the latest by default.
+#[derive(Deserialize)]
+pub struct MenuQueryParams {
+    pub version: Option<i32>,
+}
+
+#[axum::debug_handler]
+pub async fn get_vendor_menu(
+    State(db): State<Db>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<MenuQueryParams>,
+) -> Result<Json<Menu>, DomainError> {
+    match domain::get_menu(&db, VendorOrBrewery::Vendor(id), params.version).await {
+        Ok(menu) => Ok(Json(menu)),
+        Err(e) => {
+            error!("Failed to retrieve menu of vendor {}: {}", id, e);
+            Err(e)
         }
     }
 }
@@ -0,0 +1,484 @@
+use crate::config::Config;
+use crate::db::{BreweryRepo, Db, MigrationState, VendorOrBrewery, VendorRepo};
+use crate::domain::{
+    DomainError, ImportFormat, ImportKind, RecurringHorizon, Scope, ScrapeOrigin, ScrapeResult,
+    build_scrape_result, check_schedules, import_records, materialize_recurring, mint_api_key,
+    record_failed_scrape_run, record_menu, record_scrape_run, repair_schedule_names,
+    revoke_api_key,
+};
+use crate::scraper;
+use crate::scraper::Source;
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use log::{error, warn};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Ballard Trucks backend. Runs the HTTP server when no command is given.
+#[derive(Parser)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Apply pending database migrations, then run the HTTP server.
+    Serve,
+    /// Manage database schema migrations.
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// Manage admin API keys.
+    #[command(subcommand)]
+    Keys(KeysCommand),
+    /// Record scraper output.
+    #[command(subcommand)]
+    Scrape(ScrapeCommand),
+    /// Import breweries, vendors and schedule entries from a JSON or CSV file.
+    Import(ImportArgs),
+    /// Manage recurring schedules.
+    #[command(subcommand)]
+    Recurring(RecurringCommand),
+    /// Find and fix inconsistent data.
+    #[command(subcommand)]
+    Repair(RepairCommand),
+}
+
+#[derive(clap::Args)]
+pub struct ImportArgs {
+    /// A `.csv` file holds one kind of record; anything else is read as JSON.
+    file: PathBuf,
+    /// Records in a CSV file (breweries, vendors or schedules).
+    #[arg(long)]
+    kind: Option<ImportKind>,
+    /// Validate and report without writing anything.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Subcommand)]
+pub enum MigrateCommand {
+    /// Apply pending migrations.
+    Run,
+    /// List migrations and whether each is applied, pending or modified since it ran.
+    Status,
+    /// Mark migrations as applied without running them, for a database created from
+    /// `db/schema.sql` before migrations existed.
+    Baseline {
+        /// Last migration the existing schema already contains.
+        #[arg(long, default_value_t = 1)]
+        version: i64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KeysCommand {
+    /// Create a key and print it. The key cannot be shown again.
+    Mint {
+        /// Who or what the key is for.
+        #[arg(long)]
+        name: String,
+        /// Granted scope; repeat for several (schedules:write, catalog:write, scrape:trigger).
+        #[arg(long = "scope", required = true)]
+        scopes: Vec<Scope>,
+        /// Days until the key expires. Keys without expiry last until revoked.
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// Revoke a key by id.
+    Revoke { id: Uuid },
+}
+
+#[derive(Subcommand)]
+pub enum ScrapeCommand {
+    /// Apply a scrape result (JSON: brewery_id, source, window_start, window_end and
+    /// entries) and print what changed.
+    Apply { file: PathBuf },
+    /// Scrape a source and apply the entries it lists to a brewery.
+    Run {
+        #[arg(long, value_enum)]
+        source: Source,
+        /// Page or feed to fetch.
+        #[arg(long, required_unless_present = "fixture", conflicts_with = "fixture")]
+        url: Option<String>,
+        /// Saved page or feed to parse instead of fetching.
+        #[arg(long)]
+        fixture: Option<PathBuf>,
+        /// Brewery whose schedule the source lists.
+        #[arg(long, required_unless_present = "dry_run")]
+        brewery: Option<Uuid>,
+        /// Print the parsed entries without connecting to the database.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Read a vendor's menu or a brewery's taplist from its schema.org structured data
+    /// and store it as a new version if it changed.
+    Menu {
+        /// Vendor whose menu to read, from its `menu` URL unless another source is given.
+        #[arg(long, conflicts_with = "brewery", required_unless_present_any = ["brewery", "dry_run"])]
+        vendor: Option<Uuid>,
+        /// Brewery whose taplist to read, from its `drink_menu` URL unless another source
+        /// is given.
+        #[arg(long)]
+        brewery: Option<Uuid>,
+        /// Page or JSON-LD document to fetch.
+        #[arg(long, group = "source")]
+        url: Option<String>,
+        /// Saved page or JSON-LD document to parse instead of fetching.
+        #[arg(long, group = "source")]
+        fixture: Option<PathBuf>,
+        /// Print the parsed items without connecting to the database.
+        #[arg(long, requires = "source")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RecurringCommand {
+    /// Expand recurring schedules into entries for the schedule horizon and print what
+    /// changed. Run daily from cron so the horizon keeps rolling forward.
+    Materialize,
+}
+
+#[derive(Subcommand)]
+pub enum RepairCommand {
+    /// Copy current brewery and vendor names onto schedule entries whose copies drifted.
+    Names {
+        /// List drifted entries without changing them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+pub async fn run_migrate(db: &Db, command: MigrateCommand) -> anyhow::Result<()> {
+    match command {
+        MigrateCommand::Run => {
+            let applied = db.run_migrations().await?;
+            if applied.is_empty() {
+                println!("Database is up to date");
+            }
+            for version in applied {
+                println!("applied {}", version);
+            }
+        }
+        MigrateCommand::Status => {
+            let status = db.migration_status().await?;
+            for migration in &status {
+                let installed_on = migration
+                    .installed_on
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default();
+                println!(
+                    "{:>4}  {:<8}  {:<32}  {}",
+                    migration.version,
+                    migration.state.as_str(),
+                    migration.description,
+                    installed_on
+                );
+            }
+            if status
+                .iter()
+                .any(|m| matches!(m.state, MigrationState::Modified | MigrationState::Failed))
+            {
+                anyhow::bail!("Applied migrations do not match this build");
+            }
+        }
+        MigrateCommand::Baseline { version } => {
+            for version in db.baseline_migrations(version).await? {
+                println!("baselined {}", version);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_keys(db: &Db, command: KeysCommand) -> anyhow::Result<()> {
+    match command {
+        KeysCommand::Mint {
+            name,
+            scopes,
+            expires_in_days,
+        } => {
+            let expires_at = expires_in_days.map(|days| {
+                jiff::Timestamp::now() + jiff::SignedDuration::from_hours(i64::from(days) * 24)
+            });
+            let minted = mint_api_key(db, &name, &scopes, expires_at)
+                .await
+                .map_err(describe)?;
+
+            let scopes: Vec<_> = minted.key.scopes.iter().map(|s| s.as_str()).collect();
+            println!("id:      {}", minted.key.id);
+            println!("scopes:  {}", scopes.join(", "));
+            match minted.expires_at {
+                Some(expires_at) => println!("expires: {}", expires_at),
+                None => println!("expires: never"),
+            }
+            println!("key:     {}", minted.token);
+        }
+        KeysCommand::Revoke { id } => {
+            revoke_api_key(db, id).await.map_err(|e| match e {
+                DomainError::NotFound => anyhow::anyhow!("No active key with id {}", id),
+                e => describe(e),
+            })?;
+            println!("Revoked key {}", id);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_scrape(command: ScrapeCommand) -> anyhow::Result<()> {
+    match command {
+        ScrapeCommand::Apply { file } => {
+            let json = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let result: ScrapeResult = serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", file.display()))?;
+            let db = Db::init()
+                .await?
+                .with_audit(format!("scraper:{}", result.source), None);
+            let (brewery_id, source, started_at) =
+                (result.brewery_id, result.source.clone(), result.started_at);
+            let snapshot_id = result.origin.snapshot_id.clone();
+            finish_scrape(
+                &db,
+                brewery_id,
+                &source,
+                started_at,
+                snapshot_id,
+                Ok(result),
+            )
+            .await
+        }
+        ScrapeCommand::Run {
+            source,
+            url,
+            fixture,
+            brewery,
+            dry_run,
+        } => {
+            let local_tz = Config::from_env()?.local_tz;
+            let started_at = jiff::Timestamp::now();
+            let mut origin = ScrapeOrigin {
+                kind: source.kind(),
+                source_url: url.clone(),
+                parser_version: Some(source.parser_version().to_string()),
+                snapshot_id: None,
+            };
+            let parsed = async {
+                let body = match (url, fixture) {
+                    (Some(url), _) => scraper::fetch(&url).await?,
+                    (None, Some(fixture)) => std::fs::read_to_string(&fixture)
+                        .with_context(|| format!("Failed to read {}", fixture.display()))?,
+                    (None, None) => unreachable!("clap requires --url or --fixture"),
+                };
+                origin.snapshot_id = Some(scraper::snapshot_id(&body));
+                Ok(scraper::parse(source, &body, &local_tz)?)
+            }
+            .await;
+
+            if dry_run {
+                let parsed: Vec<_> = parsed?;
+                println!("{}", serde_json::to_string_pretty(&parsed)?);
+                return Ok(());
+            }
+            let brewery_id = brewery.context("--brewery is required unless --dry-run is set")?;
+            let db = Db::init()
+                .await?
+                .with_audit(format!("scraper:{}", source), None);
+            let snapshot_id = origin.snapshot_id.clone();
+            let result = match parsed {
+                Ok(parsed) => build_scrape_result(
+                    &db,
+                    brewery_id,
+                    source.as_str(),
+                    origin,
+                    started_at,
+                    parsed,
+                )
+                .await
+                .map_err(describe),
+                Err(e) => Err(e),
+            };
+            finish_scrape(
+                &db,
+                brewery_id,
+                source.as_str(),
+                started_at,
+                snapshot_id,
+                result,
+            )
+            .await
+        }
+        ScrapeCommand::Menu {
+            vendor,
+            brewery,
+            url,
+            fixture,
+            dry_run,
+        } => {
+            let db = if dry_run {
+                None
+            } else {
+                Some(
+                    Db::init()
+                        .await?
+                        .with_audit("scraper:menu".to_string(), None),
+                )
+            };
+            let owner = match (vendor, brewery) {
+                (Some(id), _) => Some(VendorOrBrewery::Vendor(id)),
+                (None, Some(id)) => Some(VendorOrBrewery::Brewery(id)),
+                (None, None) => None,
+            };
+            let url = match (url, &fixture, &db, owner) {
+                (Some(url), _, _, _) => Some(url),
+                (None, Some(_), _, _) => None,
+                (None, None, Some(db), Some(owner)) => Some(menu_url(db, owner).await?),
+                (None, None, _, _) => unreachable!("clap requires a source for dry runs"),
+            };
+            let body = match (&url, fixture) {
+                (Some(url), _) => scraper::fetch(url).await?,
+                (None, Some(fixture)) => std::fs::read_to_string(&fixture)
+                    .with_context(|| format!("Failed to read {}", fixture.display()))?,
+                (None, None) => unreachable!("a URL is looked up when no fixture is given"),
+            };
+            let items = scraper::parse_menu(&body)?;
+
+            let (Some(db), Some(owner)) = (db, owner) else {
+                println!("{}", serde_json::to_string_pretty(&items)?);
+                return Ok(());
+            };
+            let ingest = record_menu(&db, owner, url.as_deref(), items)
+                .await
+                .map_err(describe)?;
+            println!("{}", serde_json::to_string_pretty(&ingest)?);
+            Ok(())
+        }
+    }
+}
+
+/// The menu page recorded for a vendor, or the taplist page for a brewery.
+async fn menu_url(db: &Db, owner: VendorOrBrewery) -> anyhow::Result<String> {
+    let url = match owner {
+        VendorOrBrewery::Vendor(id) => {
+            let vendor = db.get_vendor(id).await?;
+            vendor.with_context(|| format!("No vendor {}", id))?.menu
+        }
+        VendorOrBrewery::Brewery(id) => {
+            let brewery = db.get_brewery(id).await?;
+            brewery
+                .with_context(|| format!("No brewery {}", id))?
+                .drink_menu
+        }
+    };
+    url.context("No menu URL is recorded; pass --url or --fixture")
+}
+
+/// Applies the result, prints the diff and re-runs the schedule check, or records the run
+/// as failed with the snapshot of whatever was fetched.
+async fn finish_scrape(
+    db: &Db,
+    brewery_id: Uuid,
+    source: &str,
+    started_at: jiff::Timestamp,
+    snapshot_id: Option<String>,
+    result: anyhow::Result<ScrapeResult>,
+) -> anyhow::Result<()> {
+    let local_tz = Config::from_env()?.local_tz;
+    let diff = match result {
+        Ok(result) => record_scrape_run(db, result, &local_tz)
+            .await
+            .map_err(describe),
+        Err(e) => Err(e),
+    };
+    match diff {
+        Ok(diff) => {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+            match check_schedules(db, jiff::Timestamp::now(), &local_tz).await {
+                Ok(check) if check.opened() > 0 => {
+                    warn!("Schedule check opened {} new issues", check.opened())
+                }
+                Ok(_) => {}
+                Err(e) => error!("Failed to check schedules after scrape: {}", e),
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let message = format!("{:#}", e);
+            let failed =
+                record_failed_scrape_run(db, brewery_id, source, started_at, snapshot_id, &message);
+            if let Err(record_err) = failed.await {
+                error!("Failed to record failed scrape run: {}", record_err);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Prints the import report and fails when any record was rejected.
+pub async fn run_import(db: &Db, args: ImportArgs) -> anyhow::Result<()> {
+    let body = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let format = if args.file.extension().is_some_and(|ext| ext == "csv") {
+        ImportFormat::Csv(args.kind.context("--kind is required for CSV files")?)
+    } else {
+        ImportFormat::Json
+    };
+    let name = args.file.file_name().unwrap_or_default().to_string_lossy();
+    let db = db.with_audit(format!("import:{}", name), None);
+
+    let report = import_records(&db, format, &body, args.dry_run)
+        .await
+        .map_err(describe)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if report.has_errors() {
+        anyhow::bail!("Import rejected; nothing was written");
+    }
+    Ok(())
+}
+
+pub async fn run_recurring(db: &Db, command: RecurringCommand) -> anyhow::Result<()> {
+    match command {
+        RecurringCommand::Materialize => {
+            let config = Config::from_env()?;
+            let horizon = RecurringHorizon::new(
+                jiff::Timestamp::now(),
+                &config.local_tz,
+                config.horizon_days,
+            );
+            let db = db.with_audit("recurring".to_string(), None);
+            let materialized = materialize_recurring(&db, &horizon)
+                .await
+                .map_err(describe)?;
+            println!("{}", serde_json::to_string_pretty(&materialized)?);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_repair(db: &Db, command: RepairCommand) -> anyhow::Result<()> {
+    match command {
+        RepairCommand::Names { dry_run } => {
+            let db = db.with_audit("repair".to_string(), None);
+            let repair = repair_schedule_names(&db, dry_run)
+                .await
+                .map_err(describe)?;
+            println!("{}", serde_json::to_string_pretty(&repair)?);
+        }
+    }
+    Ok(())
+}
+
+/// Spells out field errors, which the HTTP layer sends as JSON instead.
+fn describe(e: DomainError) -> anyhow::Error {
+    match e {
+        DomainError::Validation(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|f| format!("{} {}", f.field, f.message))
+                .collect();
+            anyhow::anyhow!("Invalid input: {}", fields.join("; "))
+        }
+        e => e.into(),
+    }
+}
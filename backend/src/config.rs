@@ -0,0 +1,129 @@
+use crate::domain::MAX_LOOKBACK_DAYS;
+use anyhow::{Context, bail};
+use jiff::tz::TimeZone;
+use std::env;
+use std::ops::RangeInclusive;
+
+/// Accepted `SCHEDULES_MAX_WINDOW_HOURS`: up to a leap year, so window arithmetic cannot
+/// overflow.
+const MAX_WINDOW_HOURS: RangeInclusive<u64> = 1..=8784;
+
+/// Accepted `SCHEDULES_HORIZON_DAYS`. Zero would put every window past the horizon.
+const HORIZON_DAYS: RangeInclusive<u64> = 1..=366;
+
+/// Accepted `STATS_LOOKBACK_DAYS`, the same range a request's `days` may use.
+const STATS_LOOKBACK_DAYS: RangeInclusive<u64> = 1..=MAX_LOOKBACK_DAYS;
+
+/// Runtime settings shared by handlers through `AppState`.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Time zone for local-day inputs such as `date=YYYY-MM-DD` without `tz`.
+    pub local_tz: TimeZone,
+    /// Largest accepted `/api/schedules` window, in hours.
+    pub max_window_hours: u64,
+    /// Days after a brewery's last scrape its schedule is assumed complete.
+    pub horizon_days: u64,
+    /// Hours between scheduled scrapes of each brewery.
+    pub scrape_interval_hours: u64,
+    /// Days of history `/api/stats/*` covers unless a request sets `days`.
+    pub stats_lookback_days: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            local_tz: TimeZone::get("America/Los_Angeles").unwrap_or(TimeZone::UTC),
+            max_window_hours: 168,
+            horizon_days: 7,
+            scrape_interval_hours: 12,
+            stats_lookback_days: 365,
+        }
+    }
+}
+
+impl Config {
+    /// Loads settings from environment variables, falling back to `Config::default()`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let defaults = Config::default();
+
+        let local_tz = match env::var("LOCAL_TZ") {
+            Ok(name) => TimeZone::get(&name)
+                .with_context(|| format!("Unknown LOCAL_TZ time zone '{}'", name))?,
+            Err(_) => defaults.local_tz,
+        };
+        let max_window_hours = env_in_range(
+            "SCHEDULES_MAX_WINDOW_HOURS",
+            defaults.max_window_hours,
+            MAX_WINDOW_HOURS,
+        )?;
+
+        let horizon_days = env_in_range(
+            "SCHEDULES_HORIZON_DAYS",
+            defaults.horizon_days,
+            HORIZON_DAYS,
+        )?;
+
+        let scrape_interval_hours = match env::var("SCRAPE_INTERVAL_HOURS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("Failed to parse SCRAPE_INTERVAL_HOURS environment variable")?,
+            Err(_) => defaults.scrape_interval_hours,
+        };
+
+        let stats_lookback_days = env_in_range(
+            "STATS_LOOKBACK_DAYS",
+            defaults.stats_lookback_days,
+            STATS_LOOKBACK_DAYS,
+        )?;
+
+        Ok(Config {
+            local_tz,
+            max_window_hours,
+            horizon_days,
+            scrape_interval_hours,
+            stats_lookback_days,
+        })
+    }
+}
+
+/// Reads the whole number in the environment variable `name`, or `default` when it is unset.
+fn env_in_range(name: &str, default: u64, range: RangeInclusive<u64>) -> anyhow::Result<u64> {
+    match env::var(name) {
+        Ok(value) => parse_in_range(name, &value, range),
+        Err(_) => Ok(default),
+    }
+}
+
+fn parse_in_range(name: &str, value: &str, range: RangeInclusive<u64>) -> anyhow::Result<u64> {
+    let parsed = value
+        .parse::<u64>()
+        .with_context(|| format!("Failed to parse {} environment variable", name))?;
+    if !range.contains(&parsed) {
+        bail!(
+            "{} must be between {} and {}, got {}",
+            name,
+            range.start(),
+            range.end(),
+            parsed
+        );
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_in_range_rejects_values_outside_the_range() {
+        let parse = |value| parse_in_range("SCHEDULES_MAX_WINDOW_HOURS", value, MAX_WINDOW_HOURS);
+
+        assert_eq!(parse("168").unwrap(), 168);
+        assert_eq!(parse("8784").unwrap(), 8784);
+        for value in ["0", "8785", "18446744073709551615", "-1", "a week"] {
+            assert!(parse(value).is_err(), "{} was accepted", value);
+        }
+        assert!(parse_in_range("SCHEDULES_HORIZON_DAYS", "0", HORIZON_DAYS).is_err());
+        assert!(parse_in_range("STATS_LOOKBACK_DAYS", "3651", STATS_LOOKBACK_DAYS).is_err());
+    }
+}
@@ -1,19 +1,112 @@
+mod api_key_repo;
+mod audit_repo;
+mod brewery_hours_repo;
 mod brewery_repo;
+mod error;
+#[cfg(test)]
+pub mod fixture;
+mod import_repo;
+mod link_repo;
+mod menu_repo;
+mod migrations;
+mod recurring_schedule_repo;
+mod schedule_issue_repo;
+mod schedule_name_repo;
+mod schedule_override_repo;
 mod schedule_repo;
+mod scrape_run_repo;
+mod stats_repo;
+mod tag_repo;
 mod vendor_repo;
 
 use anyhow::Context;
-pub use brewery_repo::{BreweryRepo, BreweryRow};
-pub use schedule_repo::{ScheduleRepo, ScheduleRow};
+pub use api_key_repo::{ApiKeyRepo, ApiKeyRow, ApiKeyWrite};
+pub use audit_repo::{AuditEventRow, AuditRepo};
+pub use brewery_hours_repo::{
+    BreweryClosureRow, BreweryClosureWrite, BreweryHoursRepo, WeeklyHoursRow, WeeklyHoursWrite,
+};
+pub use brewery_repo::{BreweryRepo, BreweryRow, BreweryWrite};
+pub use error::RepoError;
+pub use import_repo::{ImportRepo, ImportScheduleWrite, ImportWrite, ParentRef};
+pub use link_repo::{ImageRow, ImageWrite, LinkRepo, LinkRow, LinkWrite};
+pub use menu_repo::{MenuItemRow, MenuItemWrite, MenuRepo, MenuVersionRow};
+pub use migrations::MigrationState;
+pub use recurring_schedule_repo::{
+    MaterializeCounts, RECURRING_SOURCE, RecurringEntryWrite, RecurringScheduleRepo,
+    RecurringScheduleRow, RecurringScheduleWrite,
+};
+pub use schedule_issue_repo::{
+    IssueSyncCounts, ScheduleIssueRepo, ScheduleIssueRow, ScheduleIssueWrite,
+};
+pub use schedule_name_repo::{DriftedNameRow, ScheduleNameRepo};
+pub use schedule_override_repo::{
+    ScheduleOverrideRepo, ScheduleOverrideRow, ScheduleOverrideWrite, SlotWrite,
+};
+pub use schedule_repo::{
+    BreweryFreshnessRow, ProvenanceRow, ScheduleFilter, ScheduleRepo, ScheduleRow, ScheduleWrite,
+};
+pub use scrape_run_repo::{
+    FailedScrapeRunWrite, ScheduleChangeWrite, ScrapeRunChangeRow, ScrapeRunRepo, ScrapeRunRow,
+    ScrapeRunWrite, ScrapeStatusRow,
+};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
-use sqlx::{Pool, Postgres};
+use sqlx::{PgConnection, Pool, Postgres, Transaction};
+pub use stats_repo::{
+    AppearanceStatsRow, PartnerCountRow, StatsRepo, StatsSubject, WeekdayCountRow,
+    WeekdayPatternRow,
+};
 use std::env;
 use std::time::Duration;
-pub use vendor_repo::{VendorRepo, VendorRow};
+pub use tag_repo::{TagFilter, TagRepo, TagRow, TagWrite, VendorTagRow};
+use uuid::Uuid;
+pub use vendor_repo::{VendorRepo, VendorRow, VendorWrite};
 
 #[derive(Clone)]
 pub struct Db {
     pool: Pool<Postgres>,
+    audit: AuditContext,
+}
+
+/// The parent of rows that belong to either a vendor or a brewery, such as menus and
+/// links, stored as a `food_vendor_id` and `brewery_id` pair with exactly one set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VendorOrBrewery {
+    Vendor(Uuid),
+    Brewery(Uuid),
+}
+
+impl VendorOrBrewery {
+    /// Splits into the `food_vendor_id` and `brewery_id` columns.
+    fn columns(self) -> (Option<Uuid>, Option<Uuid>) {
+        match self {
+            VendorOrBrewery::Vendor(id) => (Some(id), None),
+            VendorOrBrewery::Brewery(id) => (None, Some(id)),
+        }
+    }
+
+    /// Locks the vendor or brewery row for the rest of the transaction; fails with
+    /// `NotFound` when it doesn't exist.
+    async fn lock(self, conn: &mut PgConnection) -> Result<(), RepoError> {
+        let (query, id) = match self {
+            VendorOrBrewery::Vendor(id) => (
+                "SELECT id FROM public.food_vendors WHERE id = $1 FOR UPDATE;",
+                id,
+            ),
+            VendorOrBrewery::Brewery(id) => (
+                "SELECT id FROM public.breweries WHERE id = $1 FOR UPDATE;",
+                id,
+            ),
+        };
+        sqlx::query(query).bind(id).fetch_one(conn).await?;
+        Ok(())
+    }
+}
+
+/// Who is writing, recorded by the `audit_events` triggers.
+#[derive(Clone, Default)]
+struct AuditContext {
+    actor: Option<String>,
+    request_id: Option<String>,
 }
 
 impl Db {
@@ -47,10 +140,39 @@ impl Db {
             }
         };
 
-        Ok(Db { pool })
+        Ok(Db {
+            pool,
+            audit: AuditContext::default(),
+        })
+    }
+
+    /// Returns a handle whose writes are attributed to `actor` (for example
+    /// `api_key:<id>` or `scraper:<source>`) and `request_id` in the audit log.
+    pub fn with_audit(&self, actor: String, request_id: Option<String>) -> Db {
+        Db {
+            pool: self.pool.clone(),
+            audit: AuditContext {
+                actor: Some(actor),
+                request_id,
+            },
+        }
     }
 
     pub(crate) fn pool(&self) -> &Pool<Postgres> {
         &self.pool
     }
+
+    /// Opens a transaction tagged with this handle's audit context. Writes to audited
+    /// tables should go through it so their audit rows name the actor and request.
+    pub(crate) async fn begin_write(&self) -> Result<Transaction<'static, Postgres>, RepoError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "SELECT set_config('app.actor', $1, true), set_config('app.request_id', $2, true);",
+        )
+        .bind(self.audit.actor.as_deref().unwrap_or_default())
+        .bind(self.audit.request_id.as_deref().unwrap_or_default())
+        .execute(&mut *tx)
+        .await?;
+        Ok(tx)
+    }
 }
@@ -0,0 +1,76 @@
+use crate::db::{Db, RepoError};
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone, sqlx::FromRow)]
+pub struct ApiKeyRow {
+    pub id: Uuid,
+    pub name: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Column values written when a key is minted.
+pub struct ApiKeyWrite {
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<jiff::Timestamp>,
+}
+
+/// Storage for hashed admin API keys.
+pub trait ApiKeyRepo {
+    /// Looks up an unrevoked key by its public prefix. Expiry is checked by the caller.
+    async fn get_active_api_key(&self, prefix: &str) -> Result<Option<ApiKeyRow>, RepoError>;
+
+    async fn create_api_key(&self, key: &ApiKeyWrite) -> Result<ApiKeyRow, RepoError>;
+
+    /// Returns `false` when no unrevoked key matched `id`.
+    async fn revoke_api_key(&self, id: Uuid) -> Result<bool, RepoError>;
+}
+
+impl ApiKeyRepo for Db {
+    async fn get_active_api_key(&self, prefix: &str) -> Result<Option<ApiKeyRow>, RepoError> {
+        sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+    SELECT * FROM public.api_keys
+    WHERE prefix = $1 AND revoked_at IS NULL;
+                "#,
+        )
+        .bind(prefix)
+        .fetch_optional(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_api_key(&self, key: &ApiKeyWrite) -> Result<ApiKeyRow, RepoError> {
+        sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+    INSERT INTO public.api_keys (name, prefix, key_hash, scopes, expires_at)
+    VALUES ($1, $2, $3, $4, $5::timestamptz)
+    RETURNING *;
+                "#,
+        )
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(&key.scopes)
+        .bind(key.expires_at.map(|ts| ts.to_string()))
+        .fetch_one(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn revoke_api_key(&self, id: Uuid) -> Result<bool, RepoError> {
+        sqlx::query(
+            "UPDATE public.api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL;",
+        )
+        .bind(id)
+        .execute(self.pool())
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(RepoError::from)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::db::{Db, RepoError};
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct AuditEventRow {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub actor: String,
+    pub request_id: Option<String>,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub action: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Read access to the append-only `audit_events` table, which is filled by triggers.
+pub trait AuditRepo {
+    /// Returns the newest events first, optionally only those for `entity_id`.
+    async fn get_audit_events(
+        &self,
+        entity_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AuditEventRow>, RepoError>;
+}
+
+impl AuditRepo for Db {
+    async fn get_audit_events(
+        &self,
+        entity_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AuditEventRow>, RepoError> {
+        sqlx::query_as::<_, AuditEventRow>(
+            r#"
+    SELECT * FROM public.audit_events
+    WHERE $1::uuid IS NULL OR entity_id = $1
+    ORDER BY id DESC
+    LIMIT $2;
+                "#,
+        )
+        .bind(entity_id)
+        .bind(limit)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::db::{Db, RepoError};
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct WeeklyHoursRow {
+    pub id: Uuid,
+    pub brewery_id: Uuid,
+    pub weekday: i16,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct BreweryClosureRow {
+    pub id: Uuid,
+    pub brewery_id: Uuid,
+    pub on_date: NaiveDate,
+    pub opens_at: Option<NaiveTime>,
+    pub closes_at: Option<NaiveTime>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One weekly open period. Times are local to `LOCAL_TZ`.
+pub struct WeeklyHoursWrite {
+    /// ISO weekday, 1 = Monday.
+    pub weekday: i16,
+    pub opens_at: jiff::civil::Time,
+    pub closes_at: jiff::civil::Time,
+}
+
+/// A closed day, or a day with special hours when `hours` is set.
+pub struct BreweryClosureWrite {
+    pub brewery_id: Uuid,
+    pub on_date: jiff::civil::Date,
+    pub hours: Option<(jiff::civil::Time, jiff::civil::Time)>,
+    pub reason: String,
+}
+
+/// Read/write access for brewery opening hours and closures.
+pub trait BreweryHoursRepo {
+    /// Returns the weekly hours of every brewery.
+    async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError>;
+
+    /// Returns closures of every brewery dated `from` through `until`.
+    async fn get_brewery_closures(
+        &self,
+        from: jiff::civil::Date,
+        until: jiff::civil::Date,
+    ) -> Result<Vec<BreweryClosureRow>, RepoError>;
+
+    /// Replaces all weekly hours of a brewery.
+    async fn replace_weekly_hours(
+        &self,
+        brewery_id: Uuid,
+        hours: &[WeeklyHoursWrite],
+    ) -> Result<Vec<WeeklyHoursRow>, RepoError>;
+
+    /// Stores a closure, replacing any the brewery already has on that date.
+    async fn upsert_brewery_closure(
+        &self,
+        closure: &BreweryClosureWrite,
+    ) -> Result<BreweryClosureRow, RepoError>;
+
+    /// Returns `false` when no closure of the brewery matched `id`.
+    async fn delete_brewery_closure(&self, brewery_id: Uuid, id: Uuid) -> Result<bool, RepoError>;
+}
+
+impl BreweryHoursRepo for Db {
+    async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+        sqlx::query_as::<_, WeeklyHoursRow>(
+            r#"
+    SELECT * FROM public.brewery_hours
+    ORDER BY brewery_id, weekday, opens_at;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_brewery_closures(
+        &self,
+        from: jiff::civil::Date,
+        until: jiff::civil::Date,
+    ) -> Result<Vec<BreweryClosureRow>, RepoError> {
+        sqlx::query_as::<_, BreweryClosureRow>(
+            r#"
+    SELECT * FROM public.brewery_closures
+    WHERE on_date BETWEEN $1::date AND $2::date
+    ORDER BY brewery_id, on_date;
+                "#,
+        )
+        .bind(from.to_string())
+        .bind(until.to_string())
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn replace_weekly_hours(
+        &self,
+        brewery_id: Uuid,
+        hours: &[WeeklyHoursWrite],
+    ) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        // Locks the brewery so concurrent replacements apply one after the other, and
+        // reports an unknown brewery even when `hours` is empty.
+        sqlx::query("SELECT id FROM public.breweries WHERE id = $1 FOR UPDATE;")
+            .bind(brewery_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM public.brewery_hours WHERE brewery_id = $1;")
+            .bind(brewery_id)
+            .execute(&mut *tx)
+            .await?;
+        let mut rows = Vec::with_capacity(hours.len());
+        for period in hours {
+            let row = sqlx::query_as::<_, WeeklyHoursRow>(
+                r#"
+    INSERT INTO public.brewery_hours (brewery_id, weekday, opens_at, closes_at)
+    VALUES ($1, $2, $3::time, $4::time)
+    RETURNING *;
+                    "#,
+            )
+            .bind(brewery_id)
+            .bind(period.weekday)
+            .bind(period.opens_at.to_string())
+            .bind(period.closes_at.to_string())
+            .fetch_one(&mut *tx)
+            .await?;
+            rows.push(row);
+        }
+        tx.commit().await?;
+        rows.sort_by_key(|row| (row.weekday, row.opens_at));
+        Ok(rows)
+    }
+
+    async fn upsert_brewery_closure(
+        &self,
+        closure: &BreweryClosureWrite,
+    ) -> Result<BreweryClosureRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = sqlx::query_as::<_, BreweryClosureRow>(
+            r#"
+    INSERT INTO public.brewery_closures (brewery_id, on_date, opens_at, closes_at, reason)
+    VALUES ($1, $2::date, $3::time, $4::time, $5)
+    ON CONFLICT (brewery_id, on_date) DO UPDATE SET
+      opens_at = EXCLUDED.opens_at, closes_at = EXCLUDED.closes_at, reason = EXCLUDED.reason
+    RETURNING *;
+                "#,
+        )
+        .bind(closure.brewery_id)
+        .bind(closure.on_date.to_string())
+        .bind(closure.hours.map(|(opens_at, _)| opens_at.to_string()))
+        .bind(closure.hours.map(|(_, closes_at)| closes_at.to_string()))
+        .bind(&closure.reason)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn delete_brewery_closure(&self, brewery_id: Uuid, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result =
+            sqlx::query("DELETE FROM public.brewery_closures WHERE id = $1 AND brewery_id = $2;")
+                .bind(id)
+                .bind(brewery_id)
+                .execute(&mut *tx)
+                .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,5 +1,5 @@
-use crate::db::Db;
-use anyhow::Context;
+use crate::db::{Db, RepoError};
+use sqlx::PgConnection;
 use uuid::Uuid;
 
 #[derive(sqlx::FromRow)]
@@ -15,13 +15,38 @@ pub struct BreweryRow {
     pub food_schedule: Option<String>,
 }
 
-/// Read access for brewery rows.
+/// Column values written by brewery inserts and updates.
+pub struct BreweryWrite {
+    pub name: String,
+    pub notes: Option<String>,
+    pub website: Option<String>,
+    pub address: Option<String>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    pub drink_menu: Option<String>,
+    pub food_schedule: Option<String>,
+}
+
+/// Read/write access for brewery rows.
 pub trait BreweryRepo {
-    async fn get_breweries(&self) -> anyhow::Result<Vec<BreweryRow>>;
+    async fn get_breweries(&self) -> Result<Vec<BreweryRow>, RepoError>;
+
+    async fn get_brewery(&self, id: Uuid) -> Result<Option<BreweryRow>, RepoError>;
+
+    async fn create_brewery(&self, brewery: &BreweryWrite) -> Result<BreweryRow, RepoError>;
+
+    async fn update_brewery(
+        &self,
+        id: Uuid,
+        brewery: &BreweryWrite,
+    ) -> Result<Option<BreweryRow>, RepoError>;
+
+    /// Returns `false` when no row matched `id`.
+    async fn delete_brewery(&self, id: Uuid) -> Result<bool, RepoError>;
 }
 
 impl BreweryRepo for Db {
-    async fn get_breweries(&self) -> anyhow::Result<Vec<BreweryRow>> {
+    async fn get_breweries(&self) -> Result<Vec<BreweryRow>, RepoError> {
         sqlx::query_as::<_, BreweryRow>(
             r#"
     SELECT * FROM public.breweries
@@ -30,6 +55,98 @@ impl BreweryRepo for Db {
         )
         .fetch_all(self.pool())
         .await
-        .context("Failed to fetch breweries")
+        .map_err(RepoError::from)
+    }
+
+    async fn get_brewery(&self, id: Uuid) -> Result<Option<BreweryRow>, RepoError> {
+        sqlx::query_as::<_, BreweryRow>(
+            r#"
+    SELECT * FROM public.breweries
+    WHERE id = $1;
+                "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_brewery(&self, brewery: &BreweryWrite) -> Result<BreweryRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = insert_brewery(&mut tx, brewery).await?;
+        tx.commit().await?;
+        Ok(row)
     }
+
+    async fn update_brewery(
+        &self,
+        id: Uuid,
+        brewery: &BreweryWrite,
+    ) -> Result<Option<BreweryRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = update_brewery_row(&mut tx, id, brewery).await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn delete_brewery(&self, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query("DELETE FROM public.breweries WHERE id = $1;")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+pub(super) async fn insert_brewery(
+    conn: &mut PgConnection,
+    brewery: &BreweryWrite,
+) -> Result<BreweryRow, sqlx::Error> {
+    sqlx::query_as::<_, BreweryRow>(
+        r#"
+    INSERT INTO public.breweries
+      (name, notes, website, address, lat, lng, drink_menu, food_schedule)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+    RETURNING *;
+            "#,
+    )
+    .bind(&brewery.name)
+    .bind(&brewery.notes)
+    .bind(&brewery.website)
+    .bind(&brewery.address)
+    .bind(brewery.lat)
+    .bind(brewery.lng)
+    .bind(&brewery.drink_menu)
+    .bind(&brewery.food_schedule)
+    .fetch_one(conn)
+    .await
+}
+
+pub(super) async fn update_brewery_row(
+    conn: &mut PgConnection,
+    id: Uuid,
+    brewery: &BreweryWrite,
+) -> Result<Option<BreweryRow>, sqlx::Error> {
+    sqlx::query_as::<_, BreweryRow>(
+        r#"
+    UPDATE public.breweries SET
+      name = $2, notes = $3, website = $4, address = $5,
+      lat = $6, lng = $7, drink_menu = $8, food_schedule = $9
+    WHERE id = $1
+    RETURNING *;
+            "#,
+    )
+    .bind(id)
+    .bind(&brewery.name)
+    .bind(&brewery.notes)
+    .bind(&brewery.website)
+    .bind(&brewery.address)
+    .bind(brewery.lat)
+    .bind(brewery.lng)
+    .bind(&brewery.drink_menu)
+    .bind(&brewery.food_schedule)
+    .fetch_optional(conn)
+    .await
 }
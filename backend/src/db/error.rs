@@ -0,0 +1,74 @@
+use thiserror::Error;
+
+/// Postgres `query_canceled`, raised when `statement_timeout` fires.
+const PG_QUERY_CANCELED: &str = "57014";
+/// Postgres `foreign_key_violation`.
+const PG_FOREIGN_KEY_VIOLATION: &str = "23503";
+/// Postgres `exclusion_violation`.
+const PG_EXCLUSION_VIOLATION: &str = "23P01";
+
+/// Failure reported by repository implementations.
+#[derive(Debug, Error)]
+pub enum RepoError {
+    #[error("database unavailable: {0}")]
+    Unavailable(#[source] sqlx::Error),
+    #[error("database query timed out")]
+    Timeout,
+    #[error("row not found")]
+    NotFound,
+    #[error("foreign key constraint {constraint} violated")]
+    ForeignKeyViolation { constraint: String },
+    #[error("exclusion constraint {constraint} violated")]
+    ExclusionViolation { constraint: String },
+    #[error("database query failed: {0}")]
+    Query(#[source] sqlx::Error),
+}
+
+impl From<sqlx::Error> for RepoError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::RowNotFound => RepoError::NotFound,
+            sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_) => RepoError::Unavailable(value),
+            sqlx::Error::Database(ref db_err)
+                if db_err.code().as_deref() == Some(PG_QUERY_CANCELED) =>
+            {
+                RepoError::Timeout
+            }
+            sqlx::Error::Database(ref db_err)
+                if db_err.code().as_deref() == Some(PG_FOREIGN_KEY_VIOLATION) =>
+            {
+                RepoError::ForeignKeyViolation {
+                    constraint: db_err.constraint().unwrap_or_default().to_string(),
+                }
+            }
+            sqlx::Error::Database(ref db_err)
+                if db_err.code().as_deref() == Some(PG_EXCLUSION_VIOLATION) =>
+            {
+                RepoError::ExclusionViolation {
+                    constraint: db_err.constraint().unwrap_or_default().to_string(),
+                }
+            }
+            _ => RepoError::Query(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_timeout_is_unavailable() {
+        let err = RepoError::from(sqlx::Error::PoolTimedOut);
+        assert!(matches!(err, RepoError::Unavailable(_)));
+    }
+
+    #[test]
+    fn row_not_found_is_not_found() {
+        let err = RepoError::from(sqlx::Error::RowNotFound);
+        assert!(matches!(err, RepoError::NotFound));
+    }
+}
@@ -0,0 +1,133 @@
+//! Rows for tests that need Postgres. They use the database named by the `DB_*` variables
+//! and are marked `#[ignore]`; run them with `cargo test -- --ignored`.
+
+use crate::db::{Db, ScheduleFilter, ScheduleRepo, ScheduleRow};
+use uuid::Uuid;
+
+/// A brewery, vendor and one upcoming hour-long entry between them. Dropping it removes
+/// them along with every entry at the brewery and everything that cascades from them,
+/// also when the test fails.
+pub struct Fixture {
+    pub db: Db,
+    pub brewery_id: Uuid,
+    pub vendor_id: Uuid,
+    pub schedule_id: Uuid,
+    pub start: jiff::Timestamp,
+    /// Vendors added with `add_vendor`.
+    extra_vendor_ids: Vec<Uuid>,
+}
+
+impl Fixture {
+    /// Migrates the database first.
+    pub async fn new(vendor_name: &str) -> Fixture {
+        let db = Db::init().await.expect("database reachable");
+        db.run_migrations().await.expect("database migrated");
+        let suffix = Uuid::new_v4();
+        let (brewery_id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO breweries (name, last_scraped_at) VALUES ($1, NOW()) RETURNING id",
+        )
+        .bind(format!("Test Brewery {}", suffix))
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+        let (vendor_id,): (Uuid,) =
+            sqlx::query_as("INSERT INTO food_vendors (name) VALUES ($1) RETURNING id")
+                .bind(vendor_name)
+                .fetch_one(db.pool())
+                .await
+                .unwrap();
+        let start = jiff::Timestamp::now()
+            .round(jiff::Unit::Hour)
+            .unwrap()
+            .checked_add(jiff::SignedDuration::from_hours(24))
+            .unwrap();
+        let (schedule_id,): (Uuid,) = sqlx::query_as(
+            r#"
+    INSERT INTO schedule_entries (brewery_id, food_vendor_id, open_hours, source)
+    VALUES ($1, $2, TSTZRANGE($3::timestamptz, $3::timestamptz + INTERVAL '1 hour'), 'test')
+    RETURNING id
+            "#,
+        )
+        .bind(brewery_id)
+        .bind(vendor_id)
+        .bind(start.to_string())
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+        Fixture {
+            db,
+            brewery_id,
+            vendor_id,
+            schedule_id,
+            start,
+            extra_vendor_ids: Vec::new(),
+        }
+    }
+
+    /// Creates another vendor, removed with the fixture.
+    pub async fn add_vendor(&mut self, name: &str) -> Uuid {
+        let (id,): (Uuid,) =
+            sqlx::query_as("INSERT INTO food_vendors (name) VALUES ($1) RETURNING id")
+                .bind(name)
+                .fetch_one(self.db.pool())
+                .await
+                .unwrap();
+        self.extra_vendor_ids.push(id);
+        id
+    }
+
+    /// The fixture's entry as `get_schedules_within` lists it for its hour, if at all.
+    pub async fn listed_entry(&self, filter: &ScheduleFilter) -> Option<ScheduleRow> {
+        self.db
+            .get_schedules_within(
+                self.start,
+                self.start + jiff::SignedDuration::from_hours(1),
+                "America/Los_Angeles",
+                filter,
+                1000,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|row| row.id == self.schedule_id)
+    }
+}
+
+impl Drop for Fixture {
+    /// Deletes on a thread with its own runtime and connection, since the test's runtime
+    /// can't be blocked on from here and may be unwinding from a failed assertion.
+    fn drop(&mut self) {
+        let brewery_id = self.brewery_id;
+        let mut vendor_ids = self.extra_vendor_ids.clone();
+        vendor_ids.push(self.vendor_id);
+        let cleanup = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(async move {
+                let db = Db::init().await?;
+                sqlx::query(
+                    "DELETE FROM schedule_entries WHERE brewery_id = $1 OR food_vendor_id = ANY($2)",
+                )
+                .bind(brewery_id)
+                .bind(&vendor_ids)
+                .execute(db.pool())
+                .await?;
+                sqlx::query("DELETE FROM food_vendors WHERE id = ANY($1)")
+                    .bind(&vendor_ids)
+                    .execute(db.pool())
+                    .await?;
+                sqlx::query("DELETE FROM breweries WHERE id = $1")
+                    .bind(brewery_id)
+                    .execute(db.pool())
+                    .await?;
+                anyhow::Ok(())
+            })
+        });
+        match cleanup.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("Failed to remove test rows: {:#}", e),
+            Err(_) => eprintln!("Failed to remove test rows: cleanup panicked"),
+        }
+    }
+}
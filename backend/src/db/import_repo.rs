@@ -0,0 +1,118 @@
+use crate::db::brewery_repo::{insert_brewery, update_brewery_row};
+use crate::db::schedule_repo::{insert_schedule, update_schedule_row};
+use crate::db::vendor_repo::{insert_vendor, update_vendor_row};
+use crate::db::{BreweryWrite, Db, RepoError, ScheduleRow, ScheduleWrite, VendorWrite};
+use uuid::Uuid;
+
+/// A brewery or vendor referenced by an imported schedule entry: a stored row, or the
+/// row created from `ImportWrite::new_breweries` / `new_vendors` at this index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParentRef {
+    Existing(Uuid),
+    New(usize),
+}
+
+/// A schedule entry to insert whose parents may be created by the same import.
+pub struct ImportScheduleWrite {
+    pub brewery: ParentRef,
+    pub vendor: ParentRef,
+    pub start: jiff::Timestamp,
+    pub end: Option<jiff::Timestamp>,
+    pub all_day: bool,
+    pub status: String,
+    pub source: String,
+}
+
+/// Every row an import creates or updates, applied in one transaction.
+#[derive(Default)]
+pub struct ImportWrite {
+    pub new_breweries: Vec<BreweryWrite>,
+    pub updated_breweries: Vec<(Uuid, BreweryWrite)>,
+    pub new_vendors: Vec<VendorWrite>,
+    pub updated_vendors: Vec<(Uuid, VendorWrite)>,
+    pub new_schedules: Vec<ImportScheduleWrite>,
+    pub updated_schedules: Vec<(Uuid, ScheduleWrite)>,
+}
+
+/// Access for bulk imports of breweries, vendors and schedule entries.
+pub trait ImportRepo {
+    /// Returns stored entries matching any `(brewery_id, vendor_id, start)` key.
+    async fn get_schedules_by_start(
+        &self,
+        keys: &[(Uuid, Uuid, jiff::Timestamp)],
+    ) -> Result<Vec<ScheduleRow>, RepoError>;
+
+    /// Writes the whole import or, on any error, none of it.
+    async fn apply_import(&self, import: &ImportWrite) -> Result<(), RepoError>;
+}
+
+impl ImportRepo for Db {
+    async fn get_schedules_by_start(
+        &self,
+        keys: &[(Uuid, Uuid, jiff::Timestamp)],
+    ) -> Result<Vec<ScheduleRow>, RepoError> {
+        let brewery_ids: Vec<Uuid> = keys.iter().map(|key| key.0).collect();
+        let vendor_ids: Vec<Uuid> = keys.iter().map(|key| key.1).collect();
+        let starts: Vec<String> = keys.iter().map(|key| key.2.to_string()).collect();
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"
+    SELECT DISTINCT se.*
+    FROM public.schedule_entries se
+    JOIN unnest($1::uuid[], $2::uuid[], $3::text[]) AS k (brewery_id, vendor_id, start_at)
+      ON se.brewery_id = k.brewery_id
+     AND se.food_vendor_id = k.vendor_id
+     AND lower(se.open_hours) = k.start_at::timestamptz;
+                "#,
+        )
+        .bind(brewery_ids)
+        .bind(vendor_ids)
+        .bind(starts)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn apply_import(&self, import: &ImportWrite) -> Result<(), RepoError> {
+        let mut tx = self.begin_write().await?;
+
+        let mut brewery_ids = Vec::with_capacity(import.new_breweries.len());
+        for brewery in &import.new_breweries {
+            brewery_ids.push(insert_brewery(&mut tx, brewery).await?.id);
+        }
+        for (id, brewery) in &import.updated_breweries {
+            update_brewery_row(&mut tx, *id, brewery).await?;
+        }
+
+        let mut vendor_ids = Vec::with_capacity(import.new_vendors.len());
+        for vendor in &import.new_vendors {
+            vendor_ids.push(insert_vendor(&mut tx, vendor).await?.id);
+        }
+        for (id, vendor) in &import.updated_vendors {
+            update_vendor_row(&mut tx, *id, vendor).await?;
+        }
+
+        let resolve = |parent: ParentRef, created: &[Uuid]| match parent {
+            ParentRef::Existing(id) => id,
+            ParentRef::New(index) => created[index],
+        };
+        for schedule in &import.new_schedules {
+            let write = ScheduleWrite {
+                brewery_id: resolve(schedule.brewery, &brewery_ids),
+                food_vendor_id: resolve(schedule.vendor, &vendor_ids),
+                start: schedule.start,
+                end: schedule.end,
+                all_day: schedule.all_day,
+                status: schedule.status.clone(),
+                source: schedule.source.clone(),
+                source_kind: "manual".to_string(),
+            };
+            insert_schedule(&mut tx, &write).await?;
+        }
+        for (id, schedule) in &import.updated_schedules {
+            update_schedule_row(&mut tx, *id, schedule).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use crate::db::{Db, RepoError, VendorOrBrewery};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct LinkRow {
+    pub id: Uuid,
+    pub kind: String,
+    pub url: String,
+}
+
+pub struct LinkWrite {
+    pub kind: String,
+    pub url: String,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct ImageRow {
+    pub id: Uuid,
+    pub kind: String,
+    pub url: String,
+    pub attribution: Option<String>,
+}
+
+pub struct ImageWrite {
+    pub kind: String,
+    pub url: String,
+    pub attribution: Option<String>,
+}
+
+/// Read/write access for the links and images of vendors and breweries.
+pub trait LinkRepo {
+    async fn get_links(&self, owner: VendorOrBrewery) -> Result<Vec<LinkRow>, RepoError>;
+
+    /// Replaces the owner's links; at most one per kind.
+    async fn set_links(
+        &self,
+        owner: VendorOrBrewery,
+        links: &[LinkWrite],
+    ) -> Result<Vec<LinkRow>, RepoError>;
+
+    async fn get_images(&self, owner: VendorOrBrewery) -> Result<Vec<ImageRow>, RepoError>;
+
+    /// Replaces the owner's images; at most one per kind.
+    async fn set_images(
+        &self,
+        owner: VendorOrBrewery,
+        images: &[ImageWrite],
+    ) -> Result<Vec<ImageRow>, RepoError>;
+}
+
+impl LinkRepo for Db {
+    async fn get_links(&self, owner: VendorOrBrewery) -> Result<Vec<LinkRow>, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        sqlx::query_as::<_, LinkRow>(
+            r#"
+    SELECT id, kind, url FROM public.links
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+    ORDER BY kind;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn set_links(
+        &self,
+        owner: VendorOrBrewery,
+        links: &[LinkWrite],
+    ) -> Result<Vec<LinkRow>, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        let kinds: Vec<&str> = links.iter().map(|link| link.kind.as_str()).collect();
+        let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+        let mut tx = self.begin_write().await?;
+        // Concurrent replacements apply one after the other, and an unknown owner is
+        // reported even when `links` is empty.
+        owner.lock(&mut tx).await?;
+        sqlx::query(
+            r#"
+    DELETE FROM public.links
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+      AND kind <> ALL($3);
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .execute(&mut *tx)
+        .await?;
+        // Only changed URLs are updated, so unchanged links leave no audit rows.
+        sqlx::query(
+            r#"
+    UPDATE public.links SET url = new.url
+    FROM UNNEST($3::text[], $4::text[]) AS new(kind, url)
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+      AND links.kind = new.kind AND links.url <> new.url;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .bind(&urls)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+    INSERT INTO public.links (food_vendor_id, brewery_id, kind, url)
+    SELECT $1, $2, new.kind, new.url FROM UNNEST($3::text[], $4::text[]) AS new(kind, url)
+    WHERE NOT EXISTS (
+      SELECT 1 FROM public.links
+      WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+        AND kind = new.kind
+    );
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .bind(&urls)
+        .execute(&mut *tx)
+        .await?;
+        let rows = sqlx::query_as::<_, LinkRow>(
+            r#"
+    SELECT id, kind, url FROM public.links
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+    ORDER BY kind;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    async fn get_images(&self, owner: VendorOrBrewery) -> Result<Vec<ImageRow>, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        sqlx::query_as::<_, ImageRow>(
+            r#"
+    SELECT id, kind, url, attribution FROM public.images
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+    ORDER BY kind;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn set_images(
+        &self,
+        owner: VendorOrBrewery,
+        images: &[ImageWrite],
+    ) -> Result<Vec<ImageRow>, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        let kinds: Vec<&str> = images.iter().map(|image| image.kind.as_str()).collect();
+        let urls: Vec<&str> = images.iter().map(|image| image.url.as_str()).collect();
+        let attributions: Vec<Option<&str>> = images
+            .iter()
+            .map(|image| image.attribution.as_deref())
+            .collect();
+        let mut tx = self.begin_write().await?;
+        owner.lock(&mut tx).await?;
+        sqlx::query(
+            r#"
+    DELETE FROM public.images
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+      AND kind <> ALL($3);
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+    UPDATE public.images SET url = new.url, attribution = new.attribution
+    FROM UNNEST($3::text[], $4::text[], $5::text[]) AS new(kind, url, attribution)
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+      AND images.kind = new.kind
+      AND (images.url, images.attribution) IS DISTINCT FROM (new.url, new.attribution);
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .bind(&urls)
+        .bind(&attributions)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+    INSERT INTO public.images (food_vendor_id, brewery_id, kind, url, attribution)
+    SELECT $1, $2, new.kind, new.url, new.attribution
+    FROM UNNEST($3::text[], $4::text[], $5::text[]) AS new(kind, url, attribution)
+    WHERE NOT EXISTS (
+      SELECT 1 FROM public.images
+      WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+        AND kind = new.kind
+    );
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(&kinds)
+        .bind(&urls)
+        .bind(&attributions)
+        .execute(&mut *tx)
+        .await?;
+        let rows = sqlx::query_as::<_, ImageRow>(
+            r#"
+    SELECT id, kind, url, attribution FROM public.images
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+    ORDER BY kind;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::db::{Db, RepoError, VendorOrBrewery};
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct MenuVersionRow {
+    pub id: Uuid,
+    pub version: i32,
+    pub source_url: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+pub struct MenuItemRow {
+    pub section: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price_cents: Option<i32>,
+    pub dietary: Vec<String>,
+}
+
+/// One item of a new menu version, in menu order.
+pub struct MenuItemWrite {
+    pub section: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price_cents: Option<i32>,
+    pub dietary: Vec<String>,
+}
+
+/// Read/write access for versioned menus.
+pub trait MenuRepo {
+    /// Returns the given version of a menu with its items, or the latest when `version`
+    /// is `None`.
+    async fn get_menu(
+        &self,
+        owner: VendorOrBrewery,
+        version: Option<i32>,
+    ) -> Result<Option<(MenuVersionRow, Vec<MenuItemRow>)>, RepoError>;
+
+    /// Stores `items` as the owner's next version.
+    async fn insert_menu_version(
+        &self,
+        owner: VendorOrBrewery,
+        source_url: Option<&str>,
+        items: &[MenuItemWrite],
+    ) -> Result<MenuVersionRow, RepoError>;
+}
+
+impl MenuRepo for Db {
+    async fn get_menu(
+        &self,
+        owner: VendorOrBrewery,
+        version: Option<i32>,
+    ) -> Result<Option<(MenuVersionRow, Vec<MenuItemRow>)>, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        let Some(menu) = sqlx::query_as::<_, MenuVersionRow>(
+            r#"
+    SELECT id, version, source_url, fetched_at FROM public.menu_versions
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1
+      AND brewery_id IS NOT DISTINCT FROM $2
+      AND ($3::integer IS NULL OR version = $3)
+    ORDER BY version DESC
+    LIMIT 1;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(version)
+        .fetch_optional(self.pool())
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let items = sqlx::query_as::<_, MenuItemRow>(
+            r#"
+    SELECT section, name, description, price_cents, dietary
+    FROM public.menu_items
+    WHERE menu_version_id = $1
+    ORDER BY position;
+                "#,
+        )
+        .bind(menu.id)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(Some((menu, items)))
+    }
+
+    async fn insert_menu_version(
+        &self,
+        owner: VendorOrBrewery,
+        source_url: Option<&str>,
+        items: &[MenuItemWrite],
+    ) -> Result<MenuVersionRow, RepoError> {
+        let (vendor_id, brewery_id) = owner.columns();
+        let mut tx = self.begin_write().await?;
+        // Concurrent ingests get consecutive versions.
+        owner.lock(&mut tx).await?;
+        let menu = sqlx::query_as::<_, MenuVersionRow>(
+            r#"
+    INSERT INTO public.menu_versions (food_vendor_id, brewery_id, version, source_url)
+    SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3
+    FROM public.menu_versions
+    WHERE food_vendor_id IS NOT DISTINCT FROM $1 AND brewery_id IS NOT DISTINCT FROM $2
+    RETURNING id, version, source_url, fetched_at;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(brewery_id)
+        .bind(source_url)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (position, item) in items.iter().enumerate() {
+            sqlx::query(
+                r#"
+    INSERT INTO public.menu_items
+      (menu_version_id, position, section, name, description, price_cents, dietary)
+    VALUES ($1, $2, $3, $4, $5, $6, $7);
+                    "#,
+            )
+            .bind(menu.id)
+            .bind(position as i32)
+            .bind(&item.section)
+            .bind(&item.name)
+            .bind(&item.description)
+            .bind(item.price_cents)
+            .bind(&item.dietary)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(menu)
+    }
+}
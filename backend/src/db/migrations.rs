@@ -0,0 +1,334 @@
+use crate::db::Db;
+use anyhow::{Context, bail};
+use sqlx::migrate::Migrator;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Numbered schema changes in `backend/migrations`, embedded at build time. Applied
+/// versions and their checksums are recorded in `_sqlx_migrations`.
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Where one migration stands against the database.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied, but the embedded file no longer matches the recorded checksum.
+    Modified,
+    /// Started and did not finish; needs manual repair.
+    Failed,
+    /// Applied by a newer build; not embedded in this one.
+    Unknown,
+}
+
+impl MigrationState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MigrationState::Applied => "applied",
+            MigrationState::Pending => "pending",
+            MigrationState::Modified => "modified",
+            MigrationState::Failed => "failed",
+            MigrationState::Unknown => "unknown",
+        }
+    }
+}
+
+pub struct MigrationStatusRow {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+    pub installed_on: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedRow {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+impl Db {
+    /// Applies pending migrations and returns their versions. Fails without changing
+    /// anything when an applied migration was modified, or when the database already has
+    /// tables but no migration history (see `baseline_migrations`).
+    pub async fn run_migrations(&self) -> anyhow::Result<Vec<i64>> {
+        let status = self.migration_status().await?;
+        if status.iter().all(|m| m.state == MigrationState::Pending) && self.has_schema().await? {
+            bail!(
+                "database has tables but no migration history; \
+                 mark its existing schema as applied with `migrate baseline`"
+            );
+        }
+        MIGRATOR
+            .run(self.pool())
+            .await
+            .context("Failed to run migrations")?;
+        Ok(status
+            .into_iter()
+            .filter(|m| m.state == MigrationState::Pending)
+            .map(|m| m.version)
+            .collect())
+    }
+
+    /// Lists embedded and applied migrations by version. Does not write anything.
+    pub async fn migration_status(&self) -> anyhow::Result<Vec<MigrationStatusRow>> {
+        let mut applied: HashMap<i64, AppliedRow> = self
+            .applied_migrations()
+            .await?
+            .into_iter()
+            .map(|row| (row.version, row))
+            .collect();
+
+        let mut status: Vec<MigrationStatusRow> = MIGRATOR
+            .iter()
+            .map(|migration| {
+                let row = applied.remove(&migration.version);
+                let state = match &row {
+                    None => MigrationState::Pending,
+                    Some(row) if !row.success => MigrationState::Failed,
+                    Some(row) if *row.checksum != *migration.checksum => MigrationState::Modified,
+                    Some(_) => MigrationState::Applied,
+                };
+                MigrationStatusRow {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    state,
+                    installed_on: row.map(|row| row.installed_on),
+                }
+            })
+            .collect();
+        status.extend(applied.into_values().map(|row| MigrationStatusRow {
+            version: row.version,
+            description: row.description,
+            state: MigrationState::Unknown,
+            installed_on: Some(row.installed_on),
+        }));
+        status.sort_by_key(|m| m.version);
+        Ok(status)
+    }
+
+    /// Records migrations up to `version` as applied without running them, for databases
+    /// whose schema was created from `db/schema.sql` before migrations existed. Later
+    /// migrations are left for `run_migrations`. Refuses, listing what is missing, unless
+    /// every table and trigger those migrations create already exists. Only names are
+    /// checked: the columns of existing tables are trusted to match the migrations, as they
+    /// do for the released `db/schema.sql`, whose tables are exactly those of migration 1.
+    pub async fn baseline_migrations(&self, version: i64) -> anyhow::Result<Vec<i64>> {
+        if !MIGRATOR.version_exists(version) {
+            bail!("no migration with version {}", version);
+        }
+        if !self.applied_migrations().await?.is_empty() {
+            bail!("database already has migration history");
+        }
+        if !self.has_schema().await? {
+            bail!("database has no tables to baseline; use `migrate run`");
+        }
+        let mut missing = Vec::new();
+        for migration in MIGRATOR.iter().filter(|m| m.version <= version) {
+            for object in created_objects(&migration.sql) {
+                if !self.has_object(&object).await? {
+                    missing.push(format!("{} (migration {})", object, migration.version));
+                }
+            }
+        }
+        if !missing.is_empty() {
+            bail!(
+                "database is missing objects from the migrations being baselined: {}",
+                missing.join(", ")
+            );
+        }
+
+        let mut tx = self.pool().begin().await?;
+        sqlx::query(
+            r#"
+    CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+      version BIGINT PRIMARY KEY,
+      description TEXT NOT NULL,
+      installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
+      success BOOLEAN NOT NULL,
+      checksum BYTEA NOT NULL,
+      execution_time BIGINT NOT NULL
+    );
+                "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let mut baselined = Vec::new();
+        for migration in MIGRATOR.iter().filter(|m| m.version <= version) {
+            sqlx::query(
+                r#"
+    INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+    VALUES ($1, $2, TRUE, $3, 0);
+                    "#,
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut *tx)
+            .await?;
+            baselined.push(migration.version);
+        }
+        tx.commit().await?;
+        Ok(baselined)
+    }
+
+    async fn applied_migrations(&self) -> anyhow::Result<Vec<AppliedRow>> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT to_regclass('public._sqlx_migrations') IS NOT NULL;")
+                .fetch_one(self.pool())
+                .await?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query_as::<_, AppliedRow>(
+            r#"
+    SELECT version, description, installed_on, success, checksum
+    FROM public._sqlx_migrations
+    ORDER BY version;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    async fn has_object(&self, object: &SchemaObject) -> anyhow::Result<bool> {
+        let (exists,): (bool,) = match object {
+            SchemaObject::Table(name) => {
+                sqlx::query_as("SELECT to_regclass('public.' || $1) IS NOT NULL;")
+                    .bind(name)
+                    .fetch_one(self.pool())
+                    .await?
+            }
+            SchemaObject::Trigger { name, table } => {
+                sqlx::query_as(
+                    r#"
+    SELECT EXISTS (
+      SELECT 1 FROM pg_trigger
+      WHERE tgname = $1 AND tgrelid = to_regclass('public.' || $2)
+    );
+                    "#,
+                )
+                .bind(name)
+                .bind(table)
+                .fetch_one(self.pool())
+                .await?
+            }
+        };
+        Ok(exists)
+    }
+
+    /// Whether the tables from the first migration exist.
+    async fn has_schema(&self) -> anyhow::Result<bool> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT to_regclass('public.breweries') IS NOT NULL;")
+                .fetch_one(self.pool())
+                .await?;
+        Ok(exists)
+    }
+}
+
+/// A table or trigger a migration creates.
+#[derive(Debug, PartialEq)]
+enum SchemaObject {
+    Table(String),
+    Trigger { name: String, table: String },
+}
+
+impl fmt::Display for SchemaObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaObject::Table(name) => write!(f, "table {}", name),
+            SchemaObject::Trigger { name, table } => write!(f, "trigger {} on {}", name, table),
+        }
+    }
+}
+
+/// Finds the `CREATE TABLE` and `CREATE TRIGGER` statements in a migration's SQL.
+fn created_objects(sql: &str) -> Vec<SchemaObject> {
+    let words: Vec<String> = sql
+        .lines()
+        .map(|line| line.split("--").next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == '(' || c == ';'))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+    let name = |word: &str| word.trim_start_matches("public.").to_string();
+
+    let mut objects = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        if word != "create" {
+            continue;
+        }
+        let mut rest = words[i + 1..].iter().map(String::as_str).peekable();
+        if rest.next_if_eq(&"or").is_some() {
+            rest.next_if_eq(&"replace");
+        }
+        match rest.next() {
+            Some("table") => {
+                let mut rest = rest.skip_while(|w| ["if", "not", "exists"].contains(w));
+                if let Some(table) = rest.next() {
+                    objects.push(SchemaObject::Table(name(table)));
+                }
+            }
+            Some("trigger") => {
+                let Some(trigger) = rest.next() else {
+                    continue;
+                };
+                if let Some(table) = rest.skip_while(|w| *w != "on").nth(1) {
+                    objects.push(SchemaObject::Trigger {
+                        name: name(trigger),
+                        table: name(table),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_migrations_are_numbered_from_one() {
+        let versions: Vec<i64> = MIGRATOR.iter().map(|m| m.version).collect();
+        let expected: Vec<i64> = (1..=versions.len() as i64).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn created_objects_finds_tables_and_triggers() {
+        let sql = r#"
+-- CREATE TABLE commented_out (id INT);
+CREATE TABLE breweries (
+  id UUID PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS public.tags(id UUID);
+CREATE INDEX tags_idx ON tags(id);
+CREATE OR REPLACE FUNCTION touch() RETURNS TRIGGER AS $$ BEGIN RETURN NEW; END; $$
+  LANGUAGE plpgsql;
+CREATE TRIGGER tags_touch
+  BEFORE UPDATE OF id ON tags
+  FOR EACH ROW EXECUTE FUNCTION touch();
+"#;
+
+        assert_eq!(
+            created_objects(sql),
+            [
+                SchemaObject::Table("breweries".to_string()),
+                SchemaObject::Table("tags".to_string()),
+                SchemaObject::Trigger {
+                    name: "tags_touch".to_string(),
+                    table: "tags".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::db::schedule_repo::insert_schedule;
+use crate::db::{Db, RepoError, ScheduleWrite};
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use uuid::Uuid;
+
+/// Source recorded on entries expanded from `recurring_schedules`.
+pub const RECURRING_SOURCE: &str = "recurring";
+
+#[derive(sqlx::FromRow)]
+pub struct RecurringScheduleRow {
+    pub id: Uuid,
+    pub brewery_id: Uuid,
+    pub food_vendor_id: Uuid,
+    pub rrule: String,
+    pub start_time: NaiveTime,
+    pub end_time: Option<NaiveTime>,
+    pub effective_from: NaiveDate,
+    pub effective_until: Option<NaiveDate>,
+    pub exceptions: Vec<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Column values written when a recurring schedule is created.
+pub struct RecurringScheduleWrite {
+    pub brewery_id: Uuid,
+    pub food_vendor_id: Uuid,
+    pub rrule: String,
+    pub start_time: jiff::civil::Time,
+    pub end_time: Option<jiff::civil::Time>,
+    pub effective_from: jiff::civil::Date,
+    pub effective_until: Option<jiff::civil::Date>,
+    pub exceptions: Vec<jiff::civil::Date>,
+}
+
+/// One occurrence of a recurring schedule.
+pub struct RecurringEntryWrite {
+    pub brewery_id: Uuid,
+    pub food_vendor_id: Uuid,
+    pub start: jiff::Timestamp,
+    pub end: Option<jiff::Timestamp>,
+}
+
+/// What `RecurringScheduleRepo::materialize_recurring` changed.
+#[derive(Debug, Default, PartialEq)]
+pub struct MaterializeCounts {
+    pub inserted: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+    /// Occurrences skipped because another source already lists an overlapping slot for
+    /// the same brewery and vendor.
+    pub covered: usize,
+}
+
+/// Read/write access for standing weekly or daily slots.
+pub trait RecurringScheduleRepo {
+    async fn get_recurring_schedules(&self) -> Result<Vec<RecurringScheduleRow>, RepoError>;
+
+    async fn create_recurring_schedule(
+        &self,
+        schedule: &RecurringScheduleWrite,
+    ) -> Result<RecurringScheduleRow, RepoError>;
+
+    /// Returns `false` when no row matched `id`.
+    async fn delete_recurring_schedule(&self, id: Uuid) -> Result<bool, RepoError>;
+
+    /// Makes the `recurring` entries starting in `[start, end)` match `entries`, leaving
+    /// out occurrences that overlap an entry from another source. Until-close slots
+    /// count as lasting to the end of their local day in `tz`.
+    async fn materialize_recurring(
+        &self,
+        start: jiff::Timestamp,
+        end: jiff::Timestamp,
+        tz: &str,
+        entries: &[RecurringEntryWrite],
+    ) -> Result<MaterializeCounts, RepoError>;
+}
+
+impl RecurringScheduleRepo for Db {
+    async fn get_recurring_schedules(&self) -> Result<Vec<RecurringScheduleRow>, RepoError> {
+        sqlx::query_as::<_, RecurringScheduleRow>(
+            r#"
+    SELECT * FROM public.recurring_schedules
+    ORDER BY brewery_id, start_time, created_at;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_recurring_schedule(
+        &self,
+        schedule: &RecurringScheduleWrite,
+    ) -> Result<RecurringScheduleRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = sqlx::query_as::<_, RecurringScheduleRow>(
+            r#"
+    INSERT INTO public.recurring_schedules
+      (brewery_id, food_vendor_id, rrule, start_time, end_time, effective_from,
+       effective_until, exceptions)
+    VALUES ($1, $2, $3, $4::time, $5::time, $6::date, $7::date, $8::date[])
+    RETURNING *;
+                "#,
+        )
+        .bind(schedule.brewery_id)
+        .bind(schedule.food_vendor_id)
+        .bind(&schedule.rrule)
+        .bind(schedule.start_time.to_string())
+        .bind(schedule.end_time.map(|time| time.to_string()))
+        .bind(schedule.effective_from.to_string())
+        .bind(schedule.effective_until.map(|date| date.to_string()))
+        .bind(
+            schedule
+                .exceptions
+                .iter()
+                .map(|date| date.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn delete_recurring_schedule(&self, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query("DELETE FROM public.recurring_schedules WHERE id = $1;")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn materialize_recurring(
+        &self,
+        start: jiff::Timestamp,
+        end: jiff::Timestamp,
+        tz: &str,
+        entries: &[RecurringEntryWrite],
+    ) -> Result<MaterializeCounts, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let mut counts = MaterializeCounts::default();
+        let mut keep: Vec<Uuid> = Vec::new();
+
+        for entry in entries {
+            let (covered, existing) = sqlx::query_as::<_, (bool, Option<Uuid>)>(
+                r#"
+    WITH slot AS (SELECT TSTZRANGE($3::timestamptz, $4::timestamptz, '[)') AS hours)
+    SELECT
+      EXISTS (
+        SELECT 1 FROM public.schedule_entries se, slot
+        WHERE se.brewery_id = $1 AND se.food_vendor_id = $2
+          AND se.source <> $5
+          AND public.slot_hours(se.open_hours, $6) && public.slot_hours(slot.hours, $6)
+      ),
+      (
+        SELECT se.id FROM public.schedule_entries se, slot
+        WHERE se.brewery_id = $1 AND se.food_vendor_id = $2
+          AND se.source = $5 AND se.open_hours = slot.hours
+        LIMIT 1
+      );
+                    "#,
+            )
+            .bind(entry.brewery_id)
+            .bind(entry.food_vendor_id)
+            .bind(entry.start.to_string())
+            .bind(entry.end.map(|end| end.to_string()))
+            .bind(RECURRING_SOURCE)
+            .bind(tz)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            match (covered, existing) {
+                (true, _) => counts.covered += 1,
+                (false, Some(id)) => {
+                    keep.push(id);
+                    counts.unchanged += 1;
+                }
+                (false, None) => {
+                    let write = ScheduleWrite {
+                        brewery_id: entry.brewery_id,
+                        food_vendor_id: entry.food_vendor_id,
+                        start: entry.start,
+                        end: entry.end,
+                        all_day: false,
+                        status: "confirmed".to_string(),
+                        source: RECURRING_SOURCE.to_string(),
+                        source_kind: RECURRING_SOURCE.to_string(),
+                    };
+                    keep.push(insert_schedule(&mut tx, &write).await?.id);
+                    counts.inserted += 1;
+                }
+            }
+        }
+
+        let deleted = sqlx::query(
+            r#"
+    DELETE FROM public.schedule_entries
+    WHERE source = $1
+      AND lower(open_hours) >= $2::timestamptz AND lower(open_hours) < $3::timestamptz
+      AND NOT (id = ANY($4));
+                "#,
+        )
+        .bind(RECURRING_SOURCE)
+        .bind(start.to_string())
+        .bind(end.to_string())
+        .bind(&keep)
+        .execute(&mut *tx)
+        .await?;
+        counts.deleted = deleted.rows_affected() as usize;
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+}
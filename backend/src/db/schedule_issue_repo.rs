@@ -0,0 +1,199 @@
+use crate::db::{Db, RepoError, ScheduleRow};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// An issue with the entry it was raised on.
+#[derive(sqlx::FromRow)]
+pub struct ScheduleIssueRow {
+    pub id: Uuid,
+    pub kind: String,
+    pub schedule_entry_id: Uuid,
+    pub related_entry_id: Option<Uuid>,
+    pub detail: String,
+    pub status: String,
+    pub detected_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub reviewed_by: Option<String>,
+    pub brewery_id: Uuid,
+    pub brewery_name: String,
+    pub food_vendor_id: Uuid,
+    pub food_vendor_name: String,
+    pub open_hours: PgRange<DateTime<Utc>>,
+}
+
+/// An issue found by a schedule check.
+#[derive(Debug, PartialEq)]
+pub struct ScheduleIssueWrite {
+    pub kind: String,
+    pub schedule_entry_id: Uuid,
+    pub related_entry_id: Option<Uuid>,
+    pub detail: String,
+}
+
+/// What `ScheduleIssueRepo::sync_schedule_issues` changed.
+#[derive(Debug, Default, PartialEq)]
+pub struct IssueSyncCounts {
+    pub opened: usize,
+    /// Issues found again, open or dismissed.
+    pub unchanged: usize,
+    /// Open issues on checked entries that were not found again.
+    pub cleared: usize,
+}
+
+/// Storage for the schedule check and the issues it raises.
+pub trait ScheduleIssueRepo {
+    /// Returns entries that are not cancelled and either end after `since`, have no end
+    /// or were written after `since`.
+    async fn get_entries_to_check(
+        &self,
+        since: jiff::Timestamp,
+    ) -> Result<Vec<ScheduleRow>, RepoError>;
+
+    /// Records `issues`, keeping the review status of ones already stored, and removes
+    /// open issues on `checked` entries that are not in `issues`.
+    async fn sync_schedule_issues(
+        &self,
+        checked: &[Uuid],
+        issues: &[ScheduleIssueWrite],
+    ) -> Result<IssueSyncCounts, RepoError>;
+
+    /// Returns issues newest first, optionally only those with `status`.
+    async fn get_schedule_issues(
+        &self,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ScheduleIssueRow>, RepoError>;
+
+    /// Returns `None` when no issue matched `id`.
+    async fn review_schedule_issue(
+        &self,
+        id: Uuid,
+        status: &str,
+        reviewed_by: &str,
+    ) -> Result<Option<ScheduleIssueRow>, RepoError>;
+}
+
+impl ScheduleIssueRepo for Db {
+    async fn get_entries_to_check(
+        &self,
+        since: jiff::Timestamp,
+    ) -> Result<Vec<ScheduleRow>, RepoError> {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"
+    SELECT * FROM public.schedule_entries
+    WHERE status <> 'cancelled'
+      AND (upper_inf(open_hours) OR upper(open_hours) > $1::timestamptz
+           OR updated_at > $1::timestamptz)
+    ORDER BY food_vendor_id, lower(open_hours), id;
+                "#,
+        )
+        .bind(since.to_string())
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn sync_schedule_issues(
+        &self,
+        checked: &[Uuid],
+        issues: &[ScheduleIssueWrite],
+    ) -> Result<IssueSyncCounts, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let mut counts = IssueSyncCounts::default();
+        let mut found: Vec<Uuid> = Vec::with_capacity(issues.len());
+
+        for issue in issues {
+            // `xmax` is 0 only for freshly inserted rows.
+            let (id, inserted) = sqlx::query_as::<_, (Uuid, bool)>(
+                r#"
+    INSERT INTO public.schedule_issues (kind, schedule_entry_id, related_entry_id, detail)
+    VALUES ($1, $2, $3, $4)
+    ON CONFLICT (kind, schedule_entry_id, related_entry_id)
+      DO UPDATE SET detail = EXCLUDED.detail
+    RETURNING id, xmax = 0;
+                    "#,
+            )
+            .bind(&issue.kind)
+            .bind(issue.schedule_entry_id)
+            .bind(issue.related_entry_id)
+            .bind(&issue.detail)
+            .fetch_one(&mut *tx)
+            .await?;
+            found.push(id);
+            if inserted {
+                counts.opened += 1;
+            } else {
+                counts.unchanged += 1;
+            }
+        }
+
+        let cleared = sqlx::query(
+            r#"
+    DELETE FROM public.schedule_issues
+    WHERE status = 'open' AND schedule_entry_id = ANY($1) AND NOT (id = ANY($2));
+                "#,
+        )
+        .bind(checked)
+        .bind(&found)
+        .execute(&mut *tx)
+        .await?;
+        counts.cleared = cleared.rows_affected() as usize;
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+
+    async fn get_schedule_issues(
+        &self,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ScheduleIssueRow>, RepoError> {
+        sqlx::query_as::<_, ScheduleIssueRow>(
+            r#"
+    SELECT si.*, se.brewery_id, se.brewery_name, se.food_vendor_id, se.food_vendor_name,
+           se.open_hours
+    FROM public.schedule_issues si
+    JOIN public.schedule_entries se ON se.id = si.schedule_entry_id
+    WHERE $1::text IS NULL OR si.status = $1
+    ORDER BY si.detected_at DESC, lower(se.open_hours), si.id
+    LIMIT $2;
+                "#,
+        )
+        .bind(status)
+        .bind(limit)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn review_schedule_issue(
+        &self,
+        id: Uuid,
+        status: &str,
+        reviewed_by: &str,
+    ) -> Result<Option<ScheduleIssueRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = sqlx::query_as::<_, ScheduleIssueRow>(
+            r#"
+    WITH si AS (
+      UPDATE public.schedule_issues
+      SET status = $2, reviewed_at = NOW(), reviewed_by = $3
+      WHERE id = $1
+      RETURNING *
+    )
+    SELECT si.*, se.brewery_id, se.brewery_name, se.food_vendor_id, se.food_vendor_name,
+           se.open_hours
+    FROM si
+    JOIN public.schedule_entries se ON se.id = si.schedule_entry_id;
+                "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(reviewed_by)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+}
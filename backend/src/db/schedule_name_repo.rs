@@ -0,0 +1,57 @@
+use crate::db::{Db, RepoError};
+use uuid::Uuid;
+
+/// A schedule entry whose copied brewery or vendor name differs from its parent's.
+#[derive(sqlx::FromRow)]
+pub struct DriftedNameRow {
+    pub id: Uuid,
+    pub brewery_name: String,
+    pub canonical_brewery_name: String,
+    pub food_vendor_name: String,
+    pub canonical_vendor_name: String,
+}
+
+/// Finds and fixes `schedule_entries` name copies that no longer match their parents,
+/// such as rows written before the name triggers existed.
+pub trait ScheduleNameRepo {
+    async fn get_drifted_names(&self) -> Result<Vec<DriftedNameRow>, RepoError>;
+
+    /// Copies the parents' names onto every drifted row and returns how many changed.
+    async fn repair_drifted_names(&self) -> Result<u64, RepoError>;
+}
+
+impl ScheduleNameRepo for Db {
+    async fn get_drifted_names(&self) -> Result<Vec<DriftedNameRow>, RepoError> {
+        sqlx::query_as::<_, DriftedNameRow>(
+            r#"
+    SELECT se.id, se.brewery_name, b.name AS canonical_brewery_name,
+           se.food_vendor_name, v.name AS canonical_vendor_name
+    FROM public.schedule_entries se
+    JOIN public.breweries b ON b.id = se.brewery_id
+    JOIN public.food_vendors v ON v.id = se.food_vendor_id
+    WHERE se.brewery_name <> b.name OR se.food_vendor_name <> v.name
+    ORDER BY lower(se.open_hours), se.id;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn repair_drifted_names(&self) -> Result<u64, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query(
+            r#"
+    UPDATE public.schedule_entries se
+    SET brewery_name = b.name, food_vendor_name = v.name
+    FROM public.breweries b, public.food_vendors v
+    WHERE b.id = se.brewery_id AND v.id = se.food_vendor_id
+      AND (se.brewery_name <> b.name OR se.food_vendor_name <> v.name);
+                "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::db::{Db, RepoError};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct ScheduleOverrideRow {
+    pub id: Uuid,
+    pub kind: String,
+    pub brewery_id: Uuid,
+    pub target_vendor_id: Option<Uuid>,
+    pub target_hours: Option<PgRange<DateTime<Utc>>>,
+    pub food_vendor_id: Option<Uuid>,
+    pub open_hours: Option<PgRange<DateTime<Utc>>>,
+    pub all_day: bool,
+    pub reason: String,
+    pub author: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A `[start, end)` slot; `end: None` means "until close".
+pub struct SlotWrite {
+    pub food_vendor_id: Uuid,
+    pub start: jiff::Timestamp,
+    pub end: Option<jiff::Timestamp>,
+}
+
+/// Column values written when an override is created.
+pub struct ScheduleOverrideWrite {
+    pub kind: &'static str,
+    pub brewery_id: Uuid,
+    /// Slot being cancelled or edited; `None` for adds.
+    pub target: Option<SlotWrite>,
+    /// Slot being added or the edit's replacement; `None` for cancels.
+    pub replacement: Option<SlotWrite>,
+    pub all_day: bool,
+    pub reason: String,
+    pub author: String,
+    pub expires_at: Option<jiff::Timestamp>,
+}
+
+/// Read/write access for the admin override layer merged by
+/// `ScheduleRepo::get_schedules_within`.
+pub trait ScheduleOverrideRepo {
+    /// Returns overrides that have not expired, newest first.
+    async fn get_active_overrides(&self) -> Result<Vec<ScheduleOverrideRow>, RepoError>;
+
+    async fn create_override(
+        &self,
+        schedule_override: &ScheduleOverrideWrite,
+    ) -> Result<ScheduleOverrideRow, RepoError>;
+
+    /// Returns `false` when no row matched `id`.
+    async fn delete_override(&self, id: Uuid) -> Result<bool, RepoError>;
+}
+
+impl ScheduleOverrideRepo for Db {
+    async fn get_active_overrides(&self) -> Result<Vec<ScheduleOverrideRow>, RepoError> {
+        sqlx::query_as::<_, ScheduleOverrideRow>(
+            r#"
+    SELECT * FROM public.schedule_overrides
+    WHERE expires_at IS NULL OR expires_at > NOW()
+    ORDER BY created_at DESC;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_override(
+        &self,
+        schedule_override: &ScheduleOverrideWrite,
+    ) -> Result<ScheduleOverrideRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let target = schedule_override.target.as_ref();
+        let replacement = schedule_override.replacement.as_ref();
+        let row = sqlx::query_as::<_, ScheduleOverrideRow>(
+            r#"
+    INSERT INTO public.schedule_overrides
+      (kind, brewery_id, target_vendor_id, target_hours, food_vendor_id, open_hours,
+       all_day, reason, author, expires_at)
+    VALUES (
+      $1, $2,
+      $3, CASE WHEN $4::timestamptz IS NULL THEN NULL
+          ELSE TSTZRANGE($4::timestamptz, $5::timestamptz, '[)') END,
+      $6, CASE WHEN $7::timestamptz IS NULL THEN NULL
+          ELSE TSTZRANGE($7::timestamptz, $8::timestamptz, '[)') END,
+      $9, $10, $11, $12::timestamptz
+    )
+    RETURNING *;
+                "#,
+        )
+        .bind(schedule_override.kind)
+        .bind(schedule_override.brewery_id)
+        .bind(target.map(|slot| slot.food_vendor_id))
+        .bind(target.map(|slot| slot.start.to_string()))
+        .bind(target.and_then(|slot| slot.end).map(|end| end.to_string()))
+        .bind(replacement.map(|slot| slot.food_vendor_id))
+        .bind(replacement.map(|slot| slot.start.to_string()))
+        .bind(
+            replacement
+                .and_then(|slot| slot.end)
+                .map(|end| end.to_string()),
+        )
+        .bind(schedule_override.all_day)
+        .bind(&schedule_override.reason)
+        .bind(&schedule_override.author)
+        .bind(schedule_override.expires_at.map(|ts| ts.to_string()))
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn delete_override(&self, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query("DELETE FROM public.schedule_overrides WHERE id = $1;")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,8 +1,7 @@
-use crate::db::Db;
-use anyhow::Context;
+use crate::db::{Db, RepoError, TagFilter};
+use sqlx::PgConnection;
 use sqlx::postgres::types::PgRange;
 use sqlx::types::chrono::{DateTime, Utc};
-use std::time::Duration;
 use uuid::Uuid;
 
 #[derive(sqlx::FromRow)]
@@ -13,37 +12,356 @@ pub struct ScheduleRow {
     pub food_vendor_id: Uuid,
     pub food_vendor_name: String,
     pub open_hours: PgRange<DateTime<Utc>>,
+    pub all_day: bool,
+    pub status: String,
     pub source: String,
+    #[sqlx(flatten)]
+    pub provenance: ProvenanceRow,
     pub updated_at: DateTime<Utc>,
+    /// Set when a schedule override added or edited this row.
+    #[sqlx(default)]
+    pub override_id: Option<Uuid>,
 }
 
-/// Read access for schedule query rows.
+/// Where a schedule entry came from, beyond the writer named by `source`.
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub struct ProvenanceRow {
+    /// `scraper`, `ics`, `manual` or `recurring`.
+    pub source_kind: String,
+    pub source_url: Option<String>,
+    /// The scrape run that last added or changed the entry.
+    pub scrape_run_id: Option<Uuid>,
+    pub parser_version: Option<String>,
+    /// From 0 to 1.
+    pub confidence: f64,
+    /// The content the scrape run parsed; only loaded by `get_schedules_within`.
+    #[sqlx(default)]
+    pub snapshot_id: Option<String>,
+}
+
+impl Default for ProvenanceRow {
+    /// The column defaults: a manual entry nobody doubts.
+    fn default() -> Self {
+        ProvenanceRow {
+            source_kind: "manual".to_string(),
+            source_url: None,
+            scrape_run_id: None,
+            parser_version: None,
+            confidence: 1.0,
+            snapshot_id: None,
+        }
+    }
+}
+
+/// Column values written by schedule entry inserts and updates. Brewery and vendor
+/// names are copied from the parent rows.
+pub struct ScheduleWrite {
+    pub brewery_id: Uuid,
+    pub food_vendor_id: Uuid,
+    pub start: jiff::Timestamp,
+    /// `None` stores an unbounded end ("until close").
+    pub end: Option<jiff::Timestamp>,
+    pub all_day: bool,
+    /// `confirmed`, `tentative` or `cancelled`.
+    pub status: String,
+    pub source: String,
+    /// `manual` or `recurring`. Writes reset the rest of the provenance to its defaults.
+    pub source_kind: String,
+}
+
+/// Narrows the rows returned by `ScheduleRepo::get_schedules_within`.
+#[derive(Default)]
+pub struct ScheduleFilter {
+    /// Also return cancelled entries, including slots cancelled by an override.
+    pub include_cancelled: bool,
+    /// Only entries whose vendor has these tags.
+    pub tags: TagFilter,
+}
+
+/// When each brewery's schedule was last refreshed from its source.
+#[derive(sqlx::FromRow)]
+pub struct BreweryFreshnessRow {
+    pub brewery_id: Uuid,
+    pub brewery_name: String,
+    pub last_scraped_at: Option<DateTime<Utc>>,
+}
+
+/// Read/write access for schedule entry rows.
 pub trait ScheduleRepo {
+    /// Returns entries overlapping `[start, end)` with active schedule overrides applied:
+    /// cancelled slots are marked cancelled, edited slots replaced and added slots
+    /// included. "Until close" entries count as ending with their local day in `tz`.
     async fn get_schedules_within(
         &self,
         start: jiff::Timestamp,
-        duration_hours: u64,
-    ) -> anyhow::Result<Vec<ScheduleRow>>;
+        end: jiff::Timestamp,
+        tz: &str,
+        filter: &ScheduleFilter,
+        limit: i64,
+    ) -> Result<Vec<ScheduleRow>, RepoError>;
+
+    async fn get_brewery_freshness(&self) -> Result<Vec<BreweryFreshnessRow>, RepoError>;
+
+    async fn get_schedule(&self, id: Uuid) -> Result<Option<ScheduleRow>, RepoError>;
+
+    async fn create_schedule(&self, schedule: &ScheduleWrite) -> Result<ScheduleRow, RepoError>;
+
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        schedule: &ScheduleWrite,
+    ) -> Result<Option<ScheduleRow>, RepoError>;
+
+    /// Marks the entry `cancelled` rather than deleting it, so it stays in the history.
+    /// Returns `false` when no row matched `id`.
+    async fn cancel_schedule(&self, id: Uuid) -> Result<bool, RepoError>;
 }
 
 impl ScheduleRepo for Db {
     async fn get_schedules_within(
         &self,
         start: jiff::Timestamp,
-        duration_hours: u64,
-    ) -> anyhow::Result<Vec<ScheduleRow>> {
-        let soon = start + Duration::from_hours(duration_hours);
+        end: jiff::Timestamp,
+        tz: &str,
+        filter: &ScheduleFilter,
+        limit: i64,
+    ) -> Result<Vec<ScheduleRow>, RepoError> {
         sqlx::query_as::<_, ScheduleRow>(
             r#"
-    SELECT * FROM public.schedule_entries WHERE
-    TSTZRANGE($1::timestamptz, $2::timestamptz) && open_hours
-    LIMIT 100;
+    SELECT
+      m.*,
+      (SELECT r.snapshot_id FROM public.scrape_runs r WHERE r.id = m.scrape_run_id)
+        AS snapshot_id
+    FROM public.merged_schedule_entries($1::timestamptz, $2::timestamptz, $7, NOW()) m
+    WHERE ($3 OR m.status <> 'cancelled')
+    AND public.vendor_matches_tags(m.food_vendor_id, $5::text[], $6::text[])
+    ORDER BY lower(m.open_hours), m.id
+    LIMIT $4;
                 "#,
         )
         .bind(start.to_string())
-        .bind(soon.to_string())
+        .bind(end.to_string())
+        .bind(filter.include_cancelled)
+        .bind(limit)
+        .bind(&filter.tags.cuisines)
+        .bind(&filter.tags.dietary)
+        .bind(tz)
         .fetch_all(self.pool())
         .await
-        .context("Failed to fetch schedule entries")
+        .map_err(RepoError::from)
+    }
+
+    async fn get_brewery_freshness(&self) -> Result<Vec<BreweryFreshnessRow>, RepoError> {
+        sqlx::query_as::<_, BreweryFreshnessRow>(
+            r#"
+    SELECT id AS brewery_id, name AS brewery_name, last_scraped_at
+    FROM public.breweries
+    ORDER BY name;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_schedule(&self, id: Uuid) -> Result<Option<ScheduleRow>, RepoError> {
+        sqlx::query_as::<_, ScheduleRow>(
+            r#"
+    SELECT * FROM public.schedule_entries
+    WHERE id = $1;
+                "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_schedule(&self, schedule: &ScheduleWrite) -> Result<ScheduleRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = insert_schedule(&mut tx, schedule).await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn update_schedule(
+        &self,
+        id: Uuid,
+        schedule: &ScheduleWrite,
+    ) -> Result<Option<ScheduleRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = update_schedule_row(&mut tx, id, schedule).await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn cancel_schedule(&self, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query(
+            r#"
+    UPDATE public.schedule_entries SET status = 'cancelled', updated_at = NOW()
+    WHERE id = $1;
+                "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+pub(super) async fn insert_schedule(
+    conn: &mut PgConnection,
+    schedule: &ScheduleWrite,
+) -> Result<ScheduleRow, sqlx::Error> {
+    // The `schedule_entries_set_names` trigger fills in the brewery and vendor names.
+    sqlx::query_as::<_, ScheduleRow>(
+        r#"
+    INSERT INTO public.schedule_entries
+      (brewery_id, food_vendor_id, open_hours, all_day, status, source, source_kind)
+    VALUES ($1, $2, TSTZRANGE($3::timestamptz, $4::timestamptz, '[)'), $5, $6, $7, $8)
+    RETURNING *;
+            "#,
+    )
+    .bind(schedule.brewery_id)
+    .bind(schedule.food_vendor_id)
+    .bind(schedule.start.to_string())
+    .bind(schedule.end.map(|end| end.to_string()))
+    .bind(schedule.all_day)
+    .bind(&schedule.status)
+    .bind(&schedule.source)
+    .bind(&schedule.source_kind)
+    .fetch_one(conn)
+    .await
+}
+
+pub(super) async fn update_schedule_row(
+    conn: &mut PgConnection,
+    id: Uuid,
+    schedule: &ScheduleWrite,
+) -> Result<Option<ScheduleRow>, sqlx::Error> {
+    sqlx::query_as::<_, ScheduleRow>(
+        r#"
+    UPDATE public.schedule_entries SET
+      brewery_id = $2,
+      food_vendor_id = $3,
+      open_hours = TSTZRANGE($4::timestamptz, $5::timestamptz, '[)'),
+      all_day = $6,
+      status = $7,
+      source = $8,
+      source_kind = $9,
+      source_url = NULL,
+      scrape_run_id = NULL,
+      parser_version = NULL,
+      confidence = 1,
+      updated_at = NOW()
+    WHERE id = $1
+    RETURNING *;
+            "#,
+    )
+    .bind(id)
+    .bind(schedule.brewery_id)
+    .bind(schedule.food_vendor_id)
+    .bind(schedule.start.to_string())
+    .bind(schedule.end.map(|end| end.to_string()))
+    .bind(schedule.all_day)
+    .bind(&schedule.status)
+    .bind(&schedule.source)
+    .bind(&schedule.source_kind)
+    .fetch_optional(conn)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixture::Fixture;
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn edit_override_reinstates_a_cancelled_entry() {
+        let fixture = Fixture::new("Off the Rez").await;
+        sqlx::query("UPDATE schedule_entries SET status = 'cancelled' WHERE id = $1")
+            .bind(fixture.schedule_id)
+            .execute(fixture.db.pool())
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+    INSERT INTO schedule_overrides
+      (kind, brewery_id, target_vendor_id, target_hours, food_vendor_id, open_hours, reason, author)
+    SELECT 'edit', brewery_id, food_vendor_id, open_hours, food_vendor_id, open_hours,
+      'Back on', 'test'
+    FROM schedule_entries WHERE id = $1
+            "#,
+        )
+        .bind(fixture.schedule_id)
+        .execute(fixture.db.pool())
+        .await
+        .unwrap();
+
+        let row = fixture
+            .listed_entry(&ScheduleFilter::default())
+            .await
+            .expect("edited entry is listed");
+        assert_eq!(row.status, "confirmed");
+        assert!(row.override_id.is_some());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn cancelled_entries_stay_listed_with_include_cancelled() {
+        let fixture = Fixture::new("Nosh").await;
+        let cancelled = fixture.db.cancel_schedule(fixture.schedule_id).await;
+        assert!(cancelled.unwrap());
+
+        let hidden = fixture.listed_entry(&ScheduleFilter::default()).await;
+        assert!(hidden.is_none());
+        let filter = ScheduleFilter {
+            include_cancelled: true,
+            ..ScheduleFilter::default()
+        };
+        let row = fixture
+            .listed_entry(&filter)
+            .await
+            .expect("cancelled entry is listed");
+        assert_eq!(row.status, "cancelled");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn past_until_close_entries_stay_in_their_day() {
+        let fixture = Fixture::new("Nosh").await;
+        sqlx::query(
+            r#"
+    INSERT INTO schedule_entries (brewery_id, food_vendor_id, open_hours, source)
+    VALUES ($1, $2, TSTZRANGE($3::timestamptz - INTERVAL '3 days', NULL), 'test')
+            "#,
+        )
+        .bind(fixture.brewery_id)
+        .bind(fixture.vendor_id)
+        .bind(fixture.start.to_string())
+        .execute(fixture.db.pool())
+        .await
+        .unwrap();
+
+        let rows = fixture
+            .db
+            .get_schedules_within(
+                fixture.start,
+                fixture.start + jiff::SignedDuration::from_hours(1),
+                "America/Los_Angeles",
+                &ScheduleFilter::default(),
+                1000,
+            )
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = rows
+            .iter()
+            .filter(|row| row.brewery_id == fixture.brewery_id)
+            .map(|row| row.id)
+            .collect();
+        assert_eq!(ids, [fixture.schedule_id]);
     }
 }
@@ -0,0 +1,436 @@
+use crate::db::{Db, RECURRING_SOURCE, RepoError, ScheduleRow, SlotWrite, VendorOrBrewery};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct ScrapeRunRow {
+    pub id: Uuid,
+    pub brewery_id: Uuid,
+    pub source: String,
+    pub scraped_hours: Option<PgRange<DateTime<Utc>>>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// `succeeded` or `failed`.
+    pub outcome: String,
+    pub entries_parsed: i32,
+    pub entries_inserted: i32,
+    pub entries_updated: i32,
+    pub entries_deleted: i32,
+    pub error: Option<String>,
+    pub snapshot_id: Option<String>,
+}
+
+/// An entry added, removed or changed by a scrape run, with the vendor, hours and status
+/// on each side. `before_*` is empty for additions and `after_*` for removals.
+#[derive(sqlx::FromRow)]
+pub struct ScrapeRunChangeRow {
+    pub kind: String,
+    pub schedule_entry_id: Uuid,
+    pub before_vendor_id: Option<Uuid>,
+    pub before_vendor_name: Option<String>,
+    pub before_hours: Option<PgRange<DateTime<Utc>>>,
+    pub before_status: Option<String>,
+    pub after_vendor_id: Option<Uuid>,
+    pub after_vendor_name: Option<String>,
+    pub after_hours: Option<PgRange<DateTime<Utc>>>,
+    pub after_status: Option<String>,
+}
+
+/// A finished scrape of one brewery.
+pub struct ScrapeRunWrite {
+    pub brewery_id: Uuid,
+    pub source: String,
+    pub window_start: jiff::Timestamp,
+    pub window_end: jiff::Timestamp,
+    pub started_at: jiff::Timestamp,
+    /// `scraper` or `ics`, recorded with `source_url` and `parser_version` on every entry
+    /// the run adds or changes.
+    pub source_kind: String,
+    pub source_url: Option<String>,
+    pub parser_version: Option<String>,
+    /// Identifies the content the run parsed, such as `sha256:<hex>` of the fetched page.
+    pub snapshot_id: Option<String>,
+    /// Entries the source listed, including unchanged ones.
+    pub entries_parsed: i32,
+}
+
+/// A scrape that produced nothing usable, such as a fetch error or a parser that no
+/// longer understands the page.
+pub struct FailedScrapeRunWrite {
+    pub brewery_id: Uuid,
+    pub source: String,
+    pub started_at: jiff::Timestamp,
+    /// Set when the page or feed was fetched before the run failed.
+    pub snapshot_id: Option<String>,
+    pub error: String,
+}
+
+/// Latest scrape outcomes for one brewery.
+#[derive(sqlx::FromRow)]
+pub struct ScrapeStatusRow {
+    pub brewery_id: Uuid,
+    pub brewery_name: String,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_outcome: Option<String>,
+    pub last_error: Option<String>,
+    /// Failed runs since the last successful one.
+    pub consecutive_failures: i64,
+}
+
+pub enum ScheduleChangeWrite {
+    Added {
+        slot: SlotWrite,
+        all_day: bool,
+        /// `confirmed`, `tentative` or `cancelled`.
+        status: String,
+        confidence: f64,
+    },
+    Removed {
+        schedule_id: Uuid,
+    },
+    /// `time_shifted`, `vendor_swapped` or `status_changed`; the entry keeps its id.
+    Changed {
+        kind: &'static str,
+        schedule_id: Uuid,
+        slot: SlotWrite,
+        all_day: bool,
+        status: String,
+        confidence: f64,
+    },
+}
+
+/// Scrape run history and the scraped entries each run replaces.
+pub trait ScrapeRunRepo {
+    /// Locks the brewery, passes `diff` its entries from the run's source that start
+    /// within the window, then records the run and applies the returned changes to
+    /// `schedule_entries` in the same transaction and marks the brewery as freshly
+    /// scraped. An error from `diff` rolls everything back and is returned as is.
+    /// Recurring entries overlapping a scraped one are deleted, with until-close slots
+    /// lasting to the end of their local day in `tz`.
+    async fn create_scrape_run<E>(
+        &self,
+        run: &ScrapeRunWrite,
+        tz: &str,
+        diff: impl FnOnce(&[ScheduleRow]) -> Result<Vec<ScheduleChangeWrite>, E>,
+    ) -> Result<Result<ScrapeRunRow, E>, RepoError>;
+
+    async fn create_failed_scrape_run(
+        &self,
+        run: &FailedScrapeRunWrite,
+    ) -> Result<ScrapeRunRow, RepoError>;
+
+    async fn get_scrape_run(&self, id: Uuid) -> Result<Option<ScrapeRunRow>, RepoError>;
+
+    /// Returns the run's changes in the order they were applied.
+    async fn get_scrape_run_changes(
+        &self,
+        run_id: Uuid,
+    ) -> Result<Vec<ScrapeRunChangeRow>, RepoError>;
+
+    /// Returns one row per brewery, including breweries that were never scraped.
+    async fn get_scrape_status(&self) -> Result<Vec<ScrapeStatusRow>, RepoError>;
+}
+
+impl ScrapeRunRepo for Db {
+    async fn create_scrape_run<E>(
+        &self,
+        run: &ScrapeRunWrite,
+        tz: &str,
+        diff: impl FnOnce(&[ScheduleRow]) -> Result<Vec<ScheduleChangeWrite>, E>,
+    ) -> Result<Result<ScrapeRunRow, E>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        // Concurrent runs for the brewery apply one after the other, each diffing
+        // against what the previous one left.
+        VendorOrBrewery::Brewery(run.brewery_id)
+            .lock(&mut tx)
+            .await?;
+        let current = sqlx::query_as::<_, ScheduleRow>(
+            r#"
+    SELECT * FROM public.schedule_entries
+    WHERE brewery_id = $1 AND source = $2
+      AND lower(open_hours) >= $3::timestamptz AND lower(open_hours) < $4::timestamptz
+    ORDER BY lower(open_hours), id;
+                "#,
+        )
+        .bind(run.brewery_id)
+        .bind(&run.source)
+        .bind(run.window_start.to_string())
+        .bind(run.window_end.to_string())
+        .fetch_all(&mut *tx)
+        .await?;
+        let changes = match diff(&current) {
+            Ok(changes) => changes,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let count = |matches: fn(&ScheduleChangeWrite) -> bool| {
+            changes.iter().filter(|change| matches(change)).count() as i32
+        };
+        let row = sqlx::query_as::<_, ScrapeRunRow>(
+            r#"
+    INSERT INTO public.scrape_runs
+      (brewery_id, source, scraped_hours, started_at, outcome,
+       entries_parsed, entries_inserted, entries_updated, entries_deleted, snapshot_id)
+    VALUES (
+      $1, $2, TSTZRANGE($3::timestamptz, $4::timestamptz, '[)'), $5::timestamptz, 'succeeded',
+      $6, $7, $8, $9, $10
+    )
+    RETURNING *;
+                "#,
+        )
+        .bind(run.brewery_id)
+        .bind(&run.source)
+        .bind(run.window_start.to_string())
+        .bind(run.window_end.to_string())
+        .bind(run.started_at.to_string())
+        .bind(run.entries_parsed)
+        .bind(count(|c| matches!(c, ScheduleChangeWrite::Added { .. })))
+        .bind(count(|c| matches!(c, ScheduleChangeWrite::Changed { .. })))
+        .bind(count(|c| matches!(c, ScheduleChangeWrite::Removed { .. })))
+        .bind(&run.snapshot_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for change in &changes {
+            match change {
+                ScheduleChangeWrite::Added {
+                    slot,
+                    all_day,
+                    status,
+                    confidence,
+                } => {
+                    sqlx::query(
+                        r#"
+    WITH added AS (
+      INSERT INTO public.schedule_entries
+        (brewery_id, food_vendor_id, open_hours, all_day, status, source,
+         source_kind, source_url, scrape_run_id, parser_version, confidence)
+      VALUES (
+        $2, $3, TSTZRANGE($4::timestamptz, $5::timestamptz, '[)'), $6, $12, $7,
+        $8, $9, $1, $10, $11
+      )
+      RETURNING *
+    )
+    INSERT INTO public.scrape_run_changes
+      (run_id, kind, schedule_entry_id,
+       after_vendor_id, after_vendor_name, after_hours, after_status)
+    SELECT $1, 'added', id, food_vendor_id, food_vendor_name, open_hours, status FROM added;
+                        "#,
+                    )
+                    .bind(row.id)
+                    .bind(run.brewery_id)
+                    .bind(slot.food_vendor_id)
+                    .bind(slot.start.to_string())
+                    .bind(slot.end.map(|end| end.to_string()))
+                    .bind(all_day)
+                    .bind(&run.source)
+                    .bind(&run.source_kind)
+                    .bind(&run.source_url)
+                    .bind(&run.parser_version)
+                    .bind(confidence)
+                    .bind(status)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                ScheduleChangeWrite::Removed { schedule_id } => {
+                    sqlx::query(
+                        r#"
+    WITH before AS (
+      SELECT * FROM public.schedule_entries WHERE id = $2
+    ),
+    removed AS (
+      UPDATE public.schedule_entries SET
+        status = 'cancelled',
+        scrape_run_id = $1,
+        updated_at = NOW()
+      WHERE id = $2
+      RETURNING id
+    )
+    INSERT INTO public.scrape_run_changes
+      (run_id, kind, schedule_entry_id,
+       before_vendor_id, before_vendor_name, before_hours, before_status)
+    SELECT $1, 'removed', b.id, b.food_vendor_id, b.food_vendor_name, b.open_hours, b.status
+    FROM removed r JOIN before b ON b.id = r.id;
+                        "#,
+                    )
+                    .bind(row.id)
+                    .bind(schedule_id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                ScheduleChangeWrite::Changed {
+                    kind,
+                    schedule_id,
+                    slot,
+                    all_day,
+                    status,
+                    confidence,
+                } => {
+                    // Every part of the statement sees the same snapshot, so `before`
+                    // still holds the pre-update row.
+                    sqlx::query(
+                        r#"
+    WITH before AS (
+      SELECT * FROM public.schedule_entries WHERE id = $3
+    ),
+    updated AS (
+      UPDATE public.schedule_entries SET
+        food_vendor_id = $4,
+        open_hours = TSTZRANGE($5::timestamptz, $6::timestamptz, '[)'),
+        all_day = $7,
+        status = $12,
+        source_kind = $8,
+        source_url = $9,
+        scrape_run_id = $1,
+        parser_version = $10,
+        confidence = $11,
+        updated_at = NOW()
+      WHERE id = $3
+      RETURNING *
+    )
+    INSERT INTO public.scrape_run_changes
+      (run_id, kind, schedule_entry_id,
+       before_vendor_id, before_vendor_name, before_hours, before_status,
+       after_vendor_id, after_vendor_name, after_hours, after_status)
+    SELECT
+      $1, $2, u.id,
+      b.food_vendor_id, b.food_vendor_name, b.open_hours, b.status,
+      u.food_vendor_id, u.food_vendor_name, u.open_hours, u.status
+    FROM updated u JOIN before b ON b.id = u.id;
+                        "#,
+                    )
+                    .bind(row.id)
+                    .bind(kind)
+                    .bind(schedule_id)
+                    .bind(slot.food_vendor_id)
+                    .bind(slot.start.to_string())
+                    .bind(slot.end.map(|end| end.to_string()))
+                    .bind(all_day)
+                    .bind(&run.source_kind)
+                    .bind(&run.source_url)
+                    .bind(&run.parser_version)
+                    .bind(confidence)
+                    .bind(status)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        // A scraped slot supersedes the recurring entry expanded for the same vendor.
+        sqlx::query(
+            r#"
+    DELETE FROM public.schedule_entries r
+    WHERE r.brewery_id = $1 AND r.source = $2
+      AND EXISTS (
+        SELECT 1 FROM public.schedule_entries se
+        WHERE se.brewery_id = r.brewery_id AND se.food_vendor_id = r.food_vendor_id
+          AND se.source <> $2
+          AND public.slot_hours(se.open_hours, $3) && public.slot_hours(r.open_hours, $3)
+      );
+                "#,
+        )
+        .bind(run.brewery_id)
+        .bind(RECURRING_SOURCE)
+        .bind(tz)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE public.breweries SET last_scraped_at = $2 WHERE id = $1;")
+            .bind(run.brewery_id)
+            .bind(row.finished_at)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Ok(row))
+    }
+
+    async fn create_failed_scrape_run(
+        &self,
+        run: &FailedScrapeRunWrite,
+    ) -> Result<ScrapeRunRow, RepoError> {
+        sqlx::query_as::<_, ScrapeRunRow>(
+            r#"
+    INSERT INTO public.scrape_runs (brewery_id, source, started_at, outcome, error, snapshot_id)
+    VALUES ($1, $2, $3::timestamptz, 'failed', $4, $5)
+    RETURNING *;
+                "#,
+        )
+        .bind(run.brewery_id)
+        .bind(&run.source)
+        .bind(run.started_at.to_string())
+        .bind(&run.error)
+        .bind(&run.snapshot_id)
+        .fetch_one(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_scrape_run(&self, id: Uuid) -> Result<Option<ScrapeRunRow>, RepoError> {
+        sqlx::query_as::<_, ScrapeRunRow>(
+            r#"
+    SELECT * FROM public.scrape_runs
+    WHERE id = $1;
+                "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_scrape_run_changes(
+        &self,
+        run_id: Uuid,
+    ) -> Result<Vec<ScrapeRunChangeRow>, RepoError> {
+        sqlx::query_as::<_, ScrapeRunChangeRow>(
+            r#"
+    SELECT * FROM public.scrape_run_changes
+    WHERE run_id = $1
+    ORDER BY id;
+                "#,
+        )
+        .bind(run_id)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_scrape_status(&self) -> Result<Vec<ScrapeStatusRow>, RepoError> {
+        sqlx::query_as::<_, ScrapeStatusRow>(
+            r#"
+    SELECT
+      b.id AS brewery_id,
+      b.name AS brewery_name,
+      last_success.finished_at AS last_success_at,
+      last_run.started_at AS last_run_at,
+      last_run.outcome AS last_outcome,
+      last_run.error AS last_error,
+      (
+        SELECT count(*) FROM public.scrape_runs r
+        WHERE r.brewery_id = b.id AND r.outcome = 'failed'
+          AND r.started_at > COALESCE(last_success.started_at, '-infinity')
+      ) AS consecutive_failures
+    FROM public.breweries b
+    LEFT JOIN LATERAL (
+      SELECT * FROM public.scrape_runs r
+      WHERE r.brewery_id = b.id
+      ORDER BY r.started_at DESC
+      LIMIT 1
+    ) last_run ON true
+    LEFT JOIN LATERAL (
+      SELECT * FROM public.scrape_runs r
+      WHERE r.brewery_id = b.id AND r.outcome = 'succeeded'
+      ORDER BY r.started_at DESC
+      LIMIT 1
+    ) last_success ON true
+    ORDER BY b.name;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+}
@@ -0,0 +1,275 @@
+use crate::db::{Db, RepoError, TagFilter};
+use serde::Deserialize;
+use sqlx::types::Json;
+use sqlx::types::chrono::{DateTime, NaiveTime, Utc};
+use uuid::Uuid;
+
+/// Which side of the schedule statistics are grouped by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsSubject {
+    /// One row per vendor; partners are the breweries it visited.
+    Vendors,
+    /// One row per brewery; partners are the vendors it hosted.
+    Breweries,
+}
+
+/// Appearance aggregates of one vendor or brewery.
+#[derive(sqlx::FromRow)]
+pub struct AppearanceStatsRow {
+    pub id: Uuid,
+    pub name: String,
+    pub appearances: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Most common local start and end times, as `HH:MM:SS`, of entries with hours.
+    pub usual_start: Option<String>,
+    pub usual_end: Option<String>,
+    /// Most frequent first.
+    pub weekdays: Json<Vec<WeekdayCountRow>>,
+    /// Most frequent first.
+    pub partners: Json<Vec<PartnerCountRow>>,
+}
+
+#[derive(Deserialize)]
+pub struct WeekdayCountRow {
+    /// ISO weekday in the local time zone, 1 for Monday.
+    pub weekday: i32,
+    pub appearances: i64,
+}
+
+#[derive(Deserialize)]
+pub struct PartnerCountRow {
+    pub id: Uuid,
+    pub name: String,
+    pub appearances: i64,
+}
+
+/// How often a vendor appeared at a brewery on one local weekday.
+#[derive(sqlx::FromRow)]
+pub struct WeekdayPatternRow {
+    pub brewery_id: Uuid,
+    pub brewery_name: String,
+    pub food_vendor_id: Uuid,
+    pub food_vendor_name: String,
+    /// ISO weekday in the local time zone, 1 for Monday.
+    pub weekday: i32,
+    /// Local days on this weekday the vendor appeared at the brewery.
+    pub appearances: i64,
+    /// Local days on this weekday the brewery hosted any vendor.
+    pub hosted_days: i64,
+    /// Most common local start and end times of the vendor's entries with hours there.
+    pub usual_start: Option<NaiveTime>,
+    pub usual_end: Option<NaiveTime>,
+}
+
+/// Read access to aggregates over schedule history.
+pub trait StatsRepo {
+    /// Aggregates entries starting in `[since, until)` that weren't cancelled, with
+    /// schedule overrides applied as `ScheduleRepo::get_schedules_within` applies them,
+    /// and local times in the IANA zone `tz`. Most frequent first.
+    async fn get_appearance_stats(
+        &self,
+        subject: StatsSubject,
+        since: jiff::Timestamp,
+        until: jiff::Timestamp,
+        tz: &str,
+    ) -> Result<Vec<AppearanceStatsRow>, RepoError>;
+
+    /// Counts, per brewery, vendor and local weekday in the IANA zone `tz`, the days
+    /// entries starting in `[since, until)` weren't cancelled, with schedule overrides
+    /// applied. Only vendors matching `tags` are returned, but every vendor counts towards
+    /// `hosted_days`.
+    async fn get_weekday_patterns(
+        &self,
+        since: jiff::Timestamp,
+        until: jiff::Timestamp,
+        tz: &str,
+        tags: &TagFilter,
+    ) -> Result<Vec<WeekdayPatternRow>, RepoError>;
+}
+
+impl StatsRepo for Db {
+    async fn get_appearance_stats(
+        &self,
+        subject: StatsSubject,
+        since: jiff::Timestamp,
+        until: jiff::Timestamp,
+        tz: &str,
+    ) -> Result<Vec<AppearanceStatsRow>, RepoError> {
+        sqlx::query_as::<_, AppearanceStatsRow>(
+            r#"
+    WITH appearances AS (
+      SELECT
+        CASE WHEN $4 THEN e.food_vendor_id ELSE e.brewery_id END AS subject_id,
+        CASE WHEN $4 THEN e.food_vendor_name ELSE e.brewery_name END AS subject_name,
+        CASE WHEN $4 THEN e.brewery_id ELSE e.food_vendor_id END AS partner_id,
+        CASE WHEN $4 THEN e.brewery_name ELSE e.food_vendor_name END AS partner_name,
+        lower(e.open_hours) AS start_at,
+        lower(e.open_hours) AT TIME ZONE $3 AS local_start,
+        upper(e.open_hours) AT TIME ZONE $3 AS local_end,
+        e.all_day
+      FROM public.merged_schedule_entries($1::timestamptz, $2::timestamptz, $3, '-infinity') e
+      WHERE lower(e.open_hours) >= $1::timestamptz AND e.status <> 'cancelled'
+    ),
+    weekdays AS (
+      SELECT subject_id, extract(isodow FROM local_start)::integer AS weekday,
+        count(*) AS appearances
+      FROM appearances
+      GROUP BY subject_id, weekday
+    ),
+    partners AS (
+      SELECT subject_id, partner_id, partner_name, count(*) AS appearances
+      FROM appearances
+      GROUP BY subject_id, partner_id, partner_name
+    )
+    SELECT
+      a.subject_id AS id,
+      a.subject_name AS name,
+      count(*) AS appearances,
+      min(a.start_at) AS first_seen,
+      max(a.start_at) AS last_seen,
+      to_char(
+        mode() WITHIN GROUP (ORDER BY a.local_start::time) FILTER (WHERE NOT a.all_day),
+        'HH24:MI:SS'
+      ) AS usual_start,
+      to_char(
+        mode() WITHIN GROUP (ORDER BY a.local_end::time)
+          FILTER (WHERE NOT a.all_day AND a.local_end IS NOT NULL),
+        'HH24:MI:SS'
+      ) AS usual_end,
+      (
+        SELECT json_agg(
+          json_build_object('weekday', w.weekday, 'appearances', w.appearances)
+          ORDER BY w.appearances DESC, w.weekday
+        )
+        FROM weekdays w WHERE w.subject_id = a.subject_id
+      ) AS weekdays,
+      (
+        SELECT json_agg(
+          json_build_object('id', p.partner_id, 'name', p.partner_name,
+            'appearances', p.appearances)
+          ORDER BY p.appearances DESC, p.partner_name
+        )
+        FROM partners p WHERE p.subject_id = a.subject_id
+      ) AS partners
+    FROM appearances a
+    GROUP BY a.subject_id, a.subject_name
+    ORDER BY appearances DESC, name;
+                "#,
+        )
+        .bind(since.to_string())
+        .bind(until.to_string())
+        .bind(tz)
+        .bind(subject == StatsSubject::Vendors)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn get_weekday_patterns(
+        &self,
+        since: jiff::Timestamp,
+        until: jiff::Timestamp,
+        tz: &str,
+        tags: &TagFilter,
+    ) -> Result<Vec<WeekdayPatternRow>, RepoError> {
+        sqlx::query_as::<_, WeekdayPatternRow>(
+            r#"
+    WITH appearances AS (
+      SELECT
+        e.brewery_id,
+        e.food_vendor_id,
+        extract(isodow FROM lower(e.open_hours) AT TIME ZONE $3)::integer AS weekday,
+        (lower(e.open_hours) AT TIME ZONE $3)::date AS local_day,
+        lower(e.open_hours) AT TIME ZONE $3 AS local_start,
+        upper(e.open_hours) AT TIME ZONE $3 AS local_end,
+        e.all_day
+      FROM public.merged_schedule_entries($1::timestamptz, $2::timestamptz, $3, '-infinity') e
+      WHERE lower(e.open_hours) >= $1::timestamptz AND e.status <> 'cancelled'
+    ),
+    hosted AS (
+      SELECT brewery_id, weekday, count(DISTINCT local_day) AS hosted_days
+      FROM appearances
+      GROUP BY brewery_id, weekday
+    )
+    SELECT
+      a.brewery_id,
+      b.name AS brewery_name,
+      a.food_vendor_id,
+      v.name AS food_vendor_name,
+      a.weekday,
+      count(DISTINCT a.local_day) AS appearances,
+      h.hosted_days,
+      mode() WITHIN GROUP (ORDER BY a.local_start::time) FILTER (WHERE NOT a.all_day)
+        AS usual_start,
+      mode() WITHIN GROUP (ORDER BY a.local_end::time)
+        FILTER (WHERE NOT a.all_day AND a.local_end IS NOT NULL) AS usual_end
+    FROM appearances a
+    JOIN hosted h ON h.brewery_id = a.brewery_id AND h.weekday = a.weekday
+    JOIN public.breweries b ON b.id = a.brewery_id
+    JOIN public.food_vendors v ON v.id = a.food_vendor_id
+    WHERE public.vendor_matches_tags(a.food_vendor_id, $4::text[], $5::text[])
+    GROUP BY a.brewery_id, b.name, a.food_vendor_id, v.name, a.weekday, h.hosted_days
+    ORDER BY a.brewery_id, a.weekday, a.food_vendor_id;
+                "#,
+        )
+        .bind(since.to_string())
+        .bind(until.to_string())
+        .bind(tz)
+        .bind(&tags.cuisines)
+        .bind(&tags.dietary)
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixture::Fixture;
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn stats_apply_edit_and_add_overrides() {
+        let mut fixture = Fixture::new("Off the Rez").await;
+        let other_id = fixture.add_vendor("Marination").await;
+        sqlx::query(
+            r#"
+    INSERT INTO schedule_overrides
+      (kind, brewery_id, target_vendor_id, target_hours, food_vendor_id, open_hours, reason, author)
+    SELECT 'edit', brewery_id, food_vendor_id, open_hours, $2, open_hours, 'Swapped', 'test'
+    FROM schedule_entries WHERE id = $1
+    UNION ALL
+    SELECT 'add', brewery_id, NULL, NULL, $2,
+      TSTZRANGE(lower(open_hours) + INTERVAL '1 day', upper(open_hours) + INTERVAL '1 day'),
+      'Extra day', 'test'
+    FROM schedule_entries WHERE id = $1
+            "#,
+        )
+        .bind(fixture.schedule_id)
+        .bind(other_id)
+        .execute(fixture.db.pool())
+        .await
+        .unwrap();
+
+        let stats = fixture
+            .db
+            .get_appearance_stats(
+                StatsSubject::Vendors,
+                fixture.start,
+                fixture.start + jiff::SignedDuration::from_hours(72),
+                "America/Los_Angeles",
+            )
+            .await
+            .unwrap();
+        let appearances = |id: Uuid| {
+            stats
+                .iter()
+                .find(|row| row.id == id)
+                .map_or(0, |row| row.appearances)
+        };
+        assert_eq!(appearances(fixture.vendor_id), 0);
+        assert_eq!(appearances(other_id), 2);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::db::{Db, RepoError};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow)]
+pub struct TagRow {
+    pub id: Uuid,
+    /// `cuisine`, `dietary` or `price`.
+    pub kind: String,
+    pub slug: String,
+    pub name: String,
+}
+
+/// A tag of one vendor.
+#[derive(sqlx::FromRow)]
+pub struct VendorTagRow {
+    pub food_vendor_id: Uuid,
+    pub kind: String,
+    pub slug: String,
+    pub name: String,
+}
+
+/// Column values written by tag inserts.
+pub struct TagWrite {
+    pub kind: String,
+    pub slug: String,
+    pub name: String,
+}
+
+/// Narrows vendors by tag slug. Empty lists match every vendor.
+#[derive(Debug, Default)]
+pub struct TagFilter {
+    /// The vendor has at least one of these cuisines.
+    pub cuisines: Vec<String>,
+    /// The vendor has every one of these dietary options.
+    pub dietary: Vec<String>,
+}
+
+/// Read/write access for tags and their assignment to vendors.
+pub trait TagRepo {
+    /// Returns every tag ordered by kind and name.
+    async fn get_tags(&self) -> Result<Vec<TagRow>, RepoError>;
+
+    /// Returns `None` when a tag with the same slug exists.
+    async fn create_tag(&self, tag: &TagWrite) -> Result<Option<TagRow>, RepoError>;
+
+    /// Returns the tags of every vendor.
+    async fn get_vendor_tags(&self) -> Result<Vec<VendorTagRow>, RepoError>;
+
+    /// Replaces all tags of a vendor.
+    async fn set_vendor_tags(
+        &self,
+        vendor_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<Vec<VendorTagRow>, RepoError>;
+}
+
+impl TagRepo for Db {
+    async fn get_tags(&self) -> Result<Vec<TagRow>, RepoError> {
+        sqlx::query_as::<_, TagRow>(
+            r#"
+    SELECT id, kind, slug, name FROM public.tags
+    ORDER BY kind, name;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_tag(&self, tag: &TagWrite) -> Result<Option<TagRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = sqlx::query_as::<_, TagRow>(
+            r#"
+    INSERT INTO public.tags (kind, slug, name)
+    VALUES ($1, $2, $3)
+    ON CONFLICT (slug) DO NOTHING
+    RETURNING id, kind, slug, name;
+                "#,
+        )
+        .bind(&tag.kind)
+        .bind(&tag.slug)
+        .bind(&tag.name)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn get_vendor_tags(&self) -> Result<Vec<VendorTagRow>, RepoError> {
+        sqlx::query_as::<_, VendorTagRow>(
+            r#"
+    SELECT vt.food_vendor_id, t.kind, t.slug, t.name
+    FROM public.food_vendor_tags vt
+    JOIN public.tags t ON t.id = vt.tag_id
+    ORDER BY vt.food_vendor_id, t.kind, t.name;
+                "#,
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn set_vendor_tags(
+        &self,
+        vendor_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<Vec<VendorTagRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        // Locks the vendor so concurrent replacements apply one after the other, and
+        // reports an unknown vendor even when `tag_ids` is empty.
+        sqlx::query("SELECT id FROM public.food_vendors WHERE id = $1 FOR UPDATE;")
+            .bind(vendor_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query(
+            "DELETE FROM public.food_vendor_tags WHERE food_vendor_id = $1 AND tag_id <> ALL($2);",
+        )
+        .bind(vendor_id)
+        .bind(tag_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+    INSERT INTO public.food_vendor_tags (food_vendor_id, tag_id)
+    SELECT $1, tag_id FROM UNNEST($2::uuid[]) AS tag_id
+    ON CONFLICT (food_vendor_id, tag_id) DO NOTHING;
+                "#,
+        )
+        .bind(vendor_id)
+        .bind(tag_ids)
+        .execute(&mut *tx)
+        .await?;
+        let rows = sqlx::query_as::<_, VendorTagRow>(
+            r#"
+    SELECT vt.food_vendor_id, t.kind, t.slug, t.name
+    FROM public.food_vendor_tags vt
+    JOIN public.tags t ON t.id = vt.tag_id
+    WHERE vt.food_vendor_id = $1
+    ORDER BY t.kind, t.name;
+                "#,
+        )
+        .bind(vendor_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(rows)
+    }
+}
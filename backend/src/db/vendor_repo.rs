@@ -1,5 +1,5 @@
-use crate::db::Db;
-use anyhow::Context;
+use crate::db::{Db, RepoError};
+use sqlx::PgConnection;
 use uuid::Uuid;
 
 #[derive(sqlx::FromRow)]
@@ -11,13 +11,34 @@ pub struct VendorRow {
     pub menu: Option<String>,
 }
 
-/// Read access for vendor rows.
+/// Column values written by vendor inserts and updates.
+pub struct VendorWrite {
+    pub name: String,
+    pub notes: Option<String>,
+    pub website: Option<String>,
+    pub menu: Option<String>,
+}
+
+/// Read/write access for vendor rows.
 pub trait VendorRepo {
-    async fn get_vendors(&self) -> anyhow::Result<Vec<VendorRow>>;
+    async fn get_vendors(&self) -> Result<Vec<VendorRow>, RepoError>;
+
+    async fn get_vendor(&self, id: Uuid) -> Result<Option<VendorRow>, RepoError>;
+
+    async fn create_vendor(&self, vendor: &VendorWrite) -> Result<VendorRow, RepoError>;
+
+    async fn update_vendor(
+        &self,
+        id: Uuid,
+        vendor: &VendorWrite,
+    ) -> Result<Option<VendorRow>, RepoError>;
+
+    /// Returns `false` when no row matched `id`.
+    async fn delete_vendor(&self, id: Uuid) -> Result<bool, RepoError>;
 }
 
 impl VendorRepo for Db {
-    async fn get_vendors(&self) -> anyhow::Result<Vec<VendorRow>> {
+    async fn get_vendors(&self) -> Result<Vec<VendorRow>, RepoError> {
         sqlx::query_as::<_, VendorRow>(
             r#"
     SELECT * FROM public.food_vendors
@@ -26,6 +47,88 @@ impl VendorRepo for Db {
         )
         .fetch_all(self.pool())
         .await
-        .context("Failed to fetch vendors")
+        .map_err(RepoError::from)
+    }
+
+    async fn get_vendor(&self, id: Uuid) -> Result<Option<VendorRow>, RepoError> {
+        sqlx::query_as::<_, VendorRow>(
+            r#"
+    SELECT * FROM public.food_vendors
+    WHERE id = $1;
+                "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool())
+        .await
+        .map_err(RepoError::from)
+    }
+
+    async fn create_vendor(&self, vendor: &VendorWrite) -> Result<VendorRow, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = insert_vendor(&mut tx, vendor).await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn update_vendor(
+        &self,
+        id: Uuid,
+        vendor: &VendorWrite,
+    ) -> Result<Option<VendorRow>, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let row = update_vendor_row(&mut tx, id, vendor).await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    async fn delete_vendor(&self, id: Uuid) -> Result<bool, RepoError> {
+        let mut tx = self.begin_write().await?;
+        let result = sqlx::query("DELETE FROM public.food_vendors WHERE id = $1;")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
     }
 }
+
+pub(super) async fn insert_vendor(
+    conn: &mut PgConnection,
+    vendor: &VendorWrite,
+) -> Result<VendorRow, sqlx::Error> {
+    sqlx::query_as::<_, VendorRow>(
+        r#"
+    INSERT INTO public.food_vendors (name, notes, website, menu)
+    VALUES ($1, $2, $3, $4)
+    RETURNING *;
+            "#,
+    )
+    .bind(&vendor.name)
+    .bind(&vendor.notes)
+    .bind(&vendor.website)
+    .bind(&vendor.menu)
+    .fetch_one(conn)
+    .await
+}
+
+pub(super) async fn update_vendor_row(
+    conn: &mut PgConnection,
+    id: Uuid,
+    vendor: &VendorWrite,
+) -> Result<Option<VendorRow>, sqlx::Error> {
+    sqlx::query_as::<_, VendorRow>(
+        r#"
+    UPDATE public.food_vendors SET
+      name = $2, notes = $3, website = $4, menu = $5
+    WHERE id = $1
+    RETURNING *;
+            "#,
+    )
+    .bind(id)
+    .bind(&vendor.name)
+    .bind(&vendor.notes)
+    .bind(&vendor.website)
+    .bind(&vendor.menu)
+    .fetch_optional(conn)
+    .await
+}
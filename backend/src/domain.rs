@@ -1,7 +1,67 @@
+mod api_keys;
+mod audit;
 mod breweries;
+mod brewery_hours;
+mod error;
+mod import;
+mod links;
+mod menus;
+mod predictions;
+mod recurring;
+mod schedule_issues;
+mod schedule_names;
+mod schedule_overrides;
 mod schedules;
+mod scrape_runs;
+mod stats;
+mod tags;
+mod validation;
 mod vendors;
 
-pub use breweries::{Breweries, get_breweries};
-pub use schedules::{VendorSchedules, get_schedules};
-pub use vendors::{Vendors, get_vendors};
+pub use api_keys::{ApiKey, Scope, authenticate, mint_api_key, revoke_api_key};
+pub use audit::{AuditEvents, get_audit_events};
+pub use breweries::{
+    Breweries, Brewery, BreweryDetail, CreateBrewery, UpdateBrewery, create_brewery,
+    delete_brewery, get_breweries, get_brewery, update_brewery,
+};
+pub use brewery_hours::{
+    BreweryClosure, BreweryHours, CreateBreweryClosure, SetWeeklyHours, create_brewery_closure,
+    delete_brewery_closure, get_brewery_hours, set_weekly_hours,
+};
+pub use error::DomainError;
+pub use import::{ImportFormat, ImportKind, ImportReport, import_records};
+pub use links::{Images, Links, SetImages, SetLinks, set_images, set_links};
+pub use menus::{Menu, MenuItem, get_menu, record_menu};
+pub use recurring::{
+    CreateRecurringSchedule, RecurringHorizon, RecurringSchedule, RecurringSchedules,
+    create_recurring_schedule, delete_recurring_schedule, get_recurring_schedules,
+    materialize_recurring,
+};
+pub use schedule_issues::{
+    IssueStatus, ReviewScheduleIssue, ScheduleCheck, ScheduleIssue, ScheduleIssues,
+    check_schedules, get_schedule_issues, review_schedule_issue,
+};
+pub use schedule_names::repair_schedule_names;
+pub use schedule_overrides::{
+    CreateScheduleOverride, ScheduleOverride, ScheduleOverrides, create_schedule_override,
+    delete_schedule_override, get_schedule_overrides,
+};
+pub use schedules::{
+    CreateSchedule, Schedule, ScheduleStatus, SchedulesEnvelope, SourceKind, UpdateSchedule,
+    cancel_schedule, create_schedule, get_schedules, update_schedule,
+};
+pub use scrape_runs::{
+    ParsedEntry, ScrapeOrigin, ScrapeResult, ScrapeRunDiff, ScrapeStatus, build_scrape_result,
+    get_scrape_run_diff, get_scrape_status, record_failed_scrape_run, record_scrape_run,
+};
+pub use stats::{
+    BreweryStatsReport, MAX_LOOKBACK_DAYS, VendorStatsReport, get_brewery_stats, get_vendor_stats,
+};
+pub use tags::{
+    CreateTag, SetVendorTags, Tag, Tags, VendorTags, create_tag, get_tags, set_vendor_tags,
+};
+pub use validation::FieldError;
+pub use vendors::{
+    CreateVendor, UpdateVendor, Vendor, VendorDetail, Vendors, create_vendor, delete_vendor,
+    get_vendor, get_vendors, update_vendor,
+};
@@ -0,0 +1,314 @@
+use crate::db::{ApiKeyRepo, ApiKeyRow, ApiKeyWrite};
+use crate::domain::DomainError;
+use crate::domain::validation::Validator;
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use sqlx::types::chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Marks tokens as ours so leaked keys are easy to grep for.
+const TOKEN_PREFIX: &str = "bt_";
+/// Hex characters of the public lookup prefix.
+const LOOKUP_PREFIX_LEN: usize = 12;
+
+/// Permission granted to an API key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Create, edit and delete schedule entries.
+    SchedulesWrite,
+    /// Create, edit and delete breweries and vendors.
+    CatalogWrite,
+    /// Start scraper runs.
+    ScrapeTrigger,
+}
+
+impl Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::SchedulesWrite => "schedules:write",
+            Scope::CatalogWrite => "catalog:write",
+            Scope::ScrapeTrigger => "scrape:trigger",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "schedules:write" => Ok(Scope::SchedulesWrite),
+            "catalog:write" => Ok(Scope::CatalogWrite),
+            "scrape:trigger" => Ok(Scope::ScrapeTrigger),
+            _ => Err(format!(
+                "unknown scope '{}' (expected schedules:write, catalog:write or scrape:trigger)",
+                s
+            )),
+        }
+    }
+}
+
+/// An authenticated key, stored in request extensions by the admin auth middleware.
+#[derive(Clone, Debug)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+}
+
+impl ApiKey {
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+impl From<ApiKeyRow> for ApiKey {
+    fn from(value: ApiKeyRow) -> Self {
+        ApiKey {
+            id: value.id,
+            name: value.name,
+            // The table's CHECK constraint only admits known scopes.
+            scopes: value.scopes.iter().filter_map(|s| s.parse().ok()).collect(),
+        }
+    }
+}
+
+/// A freshly minted key. `token` is only available here; the database keeps a hash.
+pub struct MintedKey {
+    pub key: ApiKey,
+    pub token: String,
+    pub expires_at: Option<jiff::Timestamp>,
+}
+
+/// Creates a key of the form `bt_<prefix>_<secret>`, storing only the argon2 hash of the
+/// secret.
+pub async fn mint_api_key<R: ApiKeyRepo>(
+    db: &R,
+    name: &str,
+    scopes: &[Scope],
+    expires_at: Option<jiff::Timestamp>,
+) -> Result<MintedKey, DomainError> {
+    let name = name.trim();
+    let mut v = Validator::default();
+    v.non_empty("name", name);
+    v.check(!scopes.is_empty(), "scopes", "must not be empty");
+    if let Some(expires_at) = expires_at {
+        v.check(
+            expires_at > jiff::Timestamp::now(),
+            "expires_at",
+            "must be in the future",
+        );
+    }
+    v.finish()?;
+
+    let prefix = Uuid::new_v4().simple().to_string()[..LOOKUP_PREFIX_LEN].to_string();
+    let secret = Uuid::new_v4().simple().to_string();
+    let key_hash = Argon2::default()
+        .hash_password(secret.as_bytes(), &SaltString::generate(&mut OsRng))
+        .expect("default argon2 parameters accept any secret")
+        .to_string();
+
+    let mut unique_scopes: Vec<Scope> = Vec::new();
+    for scope in scopes {
+        if !unique_scopes.contains(scope) {
+            unique_scopes.push(*scope);
+        }
+    }
+    let row = db
+        .create_api_key(&ApiKeyWrite {
+            name: name.to_string(),
+            prefix: prefix.clone(),
+            key_hash,
+            scopes: unique_scopes
+                .iter()
+                .map(|s| s.as_str().to_string())
+                .collect(),
+            expires_at,
+        })
+        .await?;
+
+    Ok(MintedKey {
+        key: row.into(),
+        token: format!("{}{}_{}", TOKEN_PREFIX, prefix, secret),
+        expires_at,
+    })
+}
+
+/// Resolves a bearer token to its key. Unknown, revoked, expired and mismatched keys are
+/// all reported as `Unauthorized`.
+pub async fn authenticate<R: ApiKeyRepo>(
+    db: &R,
+    token: &str,
+    now: DateTime<Utc>,
+) -> Result<ApiKey, DomainError> {
+    let (prefix, secret) = token
+        .strip_prefix(TOKEN_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+        .ok_or(DomainError::Unauthorized)?;
+
+    let row = db
+        .get_active_api_key(prefix)
+        .await?
+        .ok_or(DomainError::Unauthorized)?;
+    if row.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(DomainError::Unauthorized);
+    }
+
+    let hash = PasswordHash::new(&row.key_hash).map_err(|_| DomainError::InvalidRow {
+        id: row.id,
+        reason: "api key hash is not a PHC string",
+    })?;
+    Argon2::default()
+        .verify_password(secret.as_bytes(), &hash)
+        .map_err(|_| DomainError::Unauthorized)?;
+
+    Ok(row.into())
+}
+
+pub async fn revoke_api_key<R: ApiKeyRepo>(db: &R, id: Uuid) -> Result<(), DomainError> {
+    if db.revoke_api_key(id).await? {
+        Ok(())
+    } else {
+        Err(DomainError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct MockApiKeyRepo {
+        /// Rows keyed by lookup prefix.
+        rows: Mutex<Vec<(String, ApiKeyRow)>>,
+    }
+
+    impl ApiKeyRepo for MockApiKeyRepo {
+        async fn get_active_api_key(&self, prefix: &str) -> Result<Option<ApiKeyRow>, RepoError> {
+            let rows = self.rows.lock().expect("lock poisoned");
+            Ok(rows
+                .iter()
+                .find(|(row_prefix, _)| row_prefix == prefix)
+                .map(|(_, row)| row.clone()))
+        }
+
+        async fn create_api_key(&self, key: &ApiKeyWrite) -> Result<ApiKeyRow, RepoError> {
+            let row = ApiKeyRow {
+                id: Uuid::new_v4(),
+                name: key.name.clone(),
+                key_hash: key.key_hash.clone(),
+                scopes: key.scopes.clone(),
+                expires_at: key.expires_at.map(|ts| {
+                    DateTime::from_timestamp_micros(ts.as_microsecond()).expect("in range")
+                }),
+            };
+            self.rows
+                .lock()
+                .expect("lock poisoned")
+                .push((key.prefix.clone(), row.clone()));
+            Ok(row)
+        }
+
+        async fn revoke_api_key(&self, id: Uuid) -> Result<bool, RepoError> {
+            let mut rows = self.rows.lock().expect("lock poisoned");
+            let before = rows.len();
+            rows.retain(|(_, row)| row.id != id);
+            Ok(rows.len() < before)
+        }
+    }
+
+    #[tokio::test]
+    async fn minted_key_authenticates_with_its_scopes() {
+        let repo = MockApiKeyRepo::default();
+        let minted = mint_api_key(&repo, " ops ", &[Scope::CatalogWrite], None)
+            .await
+            .expect("minted");
+
+        let key = authenticate(&repo, &minted.token, Utc::now())
+            .await
+            .expect("authenticated");
+
+        assert_eq!(key.id, minted.key.id);
+        assert_eq!(key.name, "ops");
+        assert!(key.has_scope(Scope::CatalogWrite));
+        assert!(!key.has_scope(Scope::SchedulesWrite));
+        assert!(
+            !repo.rows.lock().expect("lock poisoned")[0]
+                .1
+                .key_hash
+                .contains(minted.token.rsplit('_').next().expect("secret"))
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticate_rejects_wrong_secret_and_malformed_tokens() {
+        let repo = MockApiKeyRepo::default();
+        let minted = mint_api_key(&repo, "ops", &[Scope::CatalogWrite], None)
+            .await
+            .expect("minted");
+        let (public, _) = minted.token.rsplit_once('_').expect("token has a secret");
+
+        for token in [format!("{}_wrong", public), "not-a-key".to_string()] {
+            let result = authenticate(&repo, &token, Utc::now()).await;
+            assert!(
+                matches!(result, Err(DomainError::Unauthorized)),
+                "{}",
+                token
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn authenticate_rejects_expired_and_revoked_keys() {
+        let repo = MockApiKeyRepo::default();
+        let expires_at = jiff::Timestamp::now() + jiff::SignedDuration::from_hours(1);
+        let minted = mint_api_key(&repo, "ops", &[Scope::SchedulesWrite], Some(expires_at))
+            .await
+            .expect("minted");
+
+        let later = Utc::now() + Duration::from_secs(2 * 60 * 60);
+        let result = authenticate(&repo, &minted.token, later).await;
+        assert!(matches!(result, Err(DomainError::Unauthorized)));
+
+        revoke_api_key(&repo, minted.key.id).await.expect("revoked");
+        let result = authenticate(&repo, &minted.token, Utc::now()).await;
+        assert!(matches!(result, Err(DomainError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn mint_api_key_requires_name_and_scopes() {
+        let repo = MockApiKeyRepo::default();
+
+        let result = mint_api_key(&repo, " ", &[], None).await;
+
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["name", "scopes"]);
+    }
+
+    #[test]
+    fn scope_round_trips_through_strings() {
+        for scope in [
+            Scope::SchedulesWrite,
+            Scope::CatalogWrite,
+            Scope::ScrapeTrigger,
+        ] {
+            assert_eq!(scope.as_str().parse::<Scope>(), Ok(scope));
+        }
+        assert!("admin".parse::<Scope>().is_err());
+    }
+}
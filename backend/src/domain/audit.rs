@@ -0,0 +1,121 @@
+use crate::db::{AuditEventRow, AuditRepo};
+use crate::domain::DomainError;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Most events returned by one audit query.
+const MAX_AUDIT_EVENTS: i64 = 200;
+
+/// Returns recent audit events, newest first, optionally only those for one brewery,
+/// vendor, schedule entry or override.
+pub async fn get_audit_events<R: AuditRepo>(
+    db: &R,
+    entity_id: Option<Uuid>,
+) -> Result<AuditEvents, DomainError> {
+    let rows = db.get_audit_events(entity_id, MAX_AUDIT_EVENTS).await?;
+    Ok(AuditEvents {
+        events: rows.into_iter().map(AuditEvent::from).collect(),
+    })
+}
+
+/// One recorded change. `before` is absent for inserts and `after` for deletes.
+#[derive(Serialize)]
+pub struct AuditEvent {
+    id: i64,
+    occurred_at: String,
+    actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    entity_type: String,
+    entity_id: Uuid,
+    action: String,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+}
+
+impl From<AuditEventRow> for AuditEvent {
+    fn from(value: AuditEventRow) -> Self {
+        AuditEvent {
+            id: value.id,
+            occurred_at: value.occurred_at.to_rfc3339(),
+            actor: value.actor,
+            request_id: value.request_id,
+            entity_type: value.entity_type,
+            entity_id: value.entity_id,
+            action: value.action,
+            before: value.before,
+            after: value.after,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AuditEvents {
+    events: Vec<AuditEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use sqlx::types::chrono::Utc;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockAuditRepo {
+        rows: Vec<(Uuid, &'static str)>,
+        requested: Mutex<Option<(Option<Uuid>, i64)>>,
+    }
+
+    impl AuditRepo for MockAuditRepo {
+        async fn get_audit_events(
+            &self,
+            entity_id: Option<Uuid>,
+            limit: i64,
+        ) -> Result<Vec<AuditEventRow>, RepoError> {
+            *self.requested.lock().expect("lock poisoned") = Some((entity_id, limit));
+            Ok(self
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, (id, _))| entity_id.is_none_or(|entity_id| entity_id == *id))
+                .map(|(i, (id, action))| AuditEventRow {
+                    id: i as i64 + 1,
+                    occurred_at: Utc::now(),
+                    actor: "api_key:ops".to_string(),
+                    request_id: None,
+                    entity_type: "breweries".to_string(),
+                    entity_id: *id,
+                    action: action.to_string(),
+                    before: None,
+                    after: Some(serde_json::json!({ "id": id })),
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn get_audit_events_filters_by_entity_and_caps_rows() {
+        let brewery_id = Uuid::new_v4();
+        let repo = MockAuditRepo {
+            rows: vec![(brewery_id, "insert"), (Uuid::new_v4(), "delete")],
+            ..Default::default()
+        };
+
+        let events = get_audit_events(&repo, Some(brewery_id))
+            .await
+            .expect("events");
+
+        let json = serde_json::to_value(&events).expect("serializes");
+        let events = json["events"].as_array().expect("events array");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["entity_id"], brewery_id.to_string());
+        assert_eq!(events[0]["action"], "insert");
+        assert!(events[0]["before"].is_null());
+        assert!(events[0].get("request_id").is_none());
+        assert_eq!(
+            *repo.requested.lock().expect("lock poisoned"),
+            Some((Some(brewery_id), MAX_AUDIT_EVENTS))
+        );
+    }
+}
@@ -1,13 +1,163 @@
-use crate::db::{BreweryRepo, BreweryRow};
-use anyhow::anyhow;
-use serde::Serialize;
+use crate::db::{
+    BreweryHoursRepo, BreweryRepo, BreweryRow, BreweryWrite, LinkRepo, RepoError, VendorOrBrewery,
+};
+use crate::domain::DomainError;
+use crate::domain::brewery_hours::{BusinessHours, DayHours, OpenAt, load_business_hours};
+use crate::domain::links::{Profile, load_profile};
+use crate::domain::validation::{Validator, double_option, patch};
+use jiff::tz::TimeZone;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-pub async fn get_breweries<R: BreweryRepo>(db: &R) -> anyhow::Result<Breweries> {
-    let breweries = db.get_breweries().await?;
-    let breweries = Breweries::try_from(breweries).map_err(|e| anyhow!(e))?;
+/// Lists breweries with whether each is open at `now` and its hours for the local day.
+pub async fn get_breweries<R: BreweryRepo + BreweryHoursRepo>(
+    db: &R,
+    now: jiff::Timestamp,
+    tz: &TimeZone,
+) -> Result<Breweries, DomainError> {
+    let rows = db.get_breweries().await?;
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let today = now.to_zoned(tz.clone()).date();
+    let hours = load_business_hours(db, today, today).await?;
+    let mut breweries = Breweries::try_from(rows)?;
+    for (brewery, id) in breweries.breweries.iter_mut().zip(ids) {
+        brewery.set_hours(hours.get(&id), now, tz);
+    }
     Ok(breweries)
 }
 
+/// Returns one brewery with its hours for the local day, links and images.
+pub async fn get_brewery<R: BreweryRepo + BreweryHoursRepo + LinkRepo>(
+    db: &R,
+    id: Uuid,
+    now: jiff::Timestamp,
+    tz: &TimeZone,
+) -> Result<BreweryDetail, DomainError> {
+    let row = db.get_brewery(id).await?.ok_or(DomainError::NotFound)?;
+    let today = now.to_zoned(tz.clone()).date();
+    let hours = load_business_hours(db, today, today).await?;
+    let mut brewery = Brewery::try_from(row)?;
+    brewery.set_hours(hours.get(&id), now, tz);
+    let profile = load_profile(db, VendorOrBrewery::Brewery(id)).await?;
+    Ok(BreweryDetail { brewery, profile })
+}
+
+pub async fn create_brewery<R: BreweryRepo>(
+    db: &R,
+    input: CreateBrewery,
+) -> Result<Brewery, DomainError> {
+    let write = input.into_write();
+    validate_brewery(&write)?;
+    let row = db.create_brewery(&write).await?;
+    Brewery::try_from(row)
+}
+
+pub async fn update_brewery<R: BreweryRepo + BreweryHoursRepo>(
+    db: &R,
+    id: Uuid,
+    input: UpdateBrewery,
+    now: jiff::Timestamp,
+    tz: &TimeZone,
+) -> Result<Brewery, DomainError> {
+    let current = db.get_brewery(id).await?.ok_or(DomainError::NotFound)?;
+    let write = input.apply(current);
+    validate_brewery(&write)?;
+    let row = db
+        .update_brewery(id, &write)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let today = now.to_zoned(tz.clone()).date();
+    let hours = load_business_hours(db, today, today).await?;
+    let mut brewery = Brewery::try_from(row)?;
+    brewery.set_hours(hours.get(&id), now, tz);
+    Ok(brewery)
+}
+
+pub async fn delete_brewery<R: BreweryRepo>(db: &R, id: Uuid) -> Result<(), DomainError> {
+    match db.delete_brewery(id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(DomainError::NotFound),
+        Err(RepoError::ForeignKeyViolation { .. }) => {
+            Err(DomainError::Conflict("brewery still has schedule entries"))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(super) fn validate_brewery(write: &BreweryWrite) -> Result<(), DomainError> {
+    let mut v = Validator::default();
+    v.non_empty("name", &write.name);
+    v.coordinates(write.lat, write.lng);
+    v.url("website", write.website.as_deref());
+    v.url("drink_menu", write.drink_menu.as_deref());
+    v.url("food_schedule", write.food_schedule.as_deref());
+    v.finish()
+}
+
+/// Admin input for a new brewery.
+#[derive(Deserialize)]
+pub struct CreateBrewery {
+    name: String,
+    notes: Option<String>,
+    website: Option<String>,
+    address: Option<String>,
+    lat: Option<f64>,
+    lng: Option<f64>,
+    drink_menu: Option<String>,
+    food_schedule: Option<String>,
+}
+
+impl CreateBrewery {
+    pub(super) fn into_write(self) -> BreweryWrite {
+        BreweryWrite {
+            name: self.name.trim().to_string(),
+            notes: self.notes,
+            website: self.website,
+            address: self.address,
+            lat: self.lat,
+            lng: self.lng,
+            drink_menu: self.drink_menu,
+            food_schedule: self.food_schedule,
+        }
+    }
+}
+
+/// Admin input for a partial brewery update. Absent fields are kept; `null`
+/// clears optional fields.
+#[derive(Deserialize)]
+pub struct UpdateBrewery {
+    name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    notes: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    website: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    address: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    lat: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    lng: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    drink_menu: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    food_schedule: Option<Option<String>>,
+}
+
+impl UpdateBrewery {
+    fn apply(self, current: BreweryRow) -> BreweryWrite {
+        BreweryWrite {
+            name: patch(current.name, self.name.map(|name| name.trim().to_string())),
+            notes: patch(current.notes, self.notes),
+            website: patch(current.website, self.website),
+            address: patch(current.address, self.address),
+            lat: patch(current.lat, self.lat),
+            lng: patch(current.lng, self.lng),
+            drink_menu: patch(current.drink_menu, self.drink_menu),
+            food_schedule: patch(current.food_schedule, self.food_schedule),
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct Brewery {
     id: String,
@@ -16,14 +166,34 @@ pub struct Brewery {
     lat: Option<f64>,
     lng: Option<f64>,
     website: Option<String>,
+    /// `None` when the brewery's hours are unknown.
+    open_now: Option<bool>,
+    today_hours: Option<DayHours>,
+}
+
+impl Brewery {
+    fn set_hours(&mut self, hours: Option<&BusinessHours>, now: jiff::Timestamp, tz: &TimeZone) {
+        let Some(hours) = hours else {
+            return;
+        };
+        self.open_now = match hours.open_at(now, tz) {
+            OpenAt::Unknown => None,
+            OpenAt::Closed => Some(false),
+            OpenAt::Open { .. } => Some(true),
+        };
+        self.today_hours = hours.day_hours(now.to_zoned(tz.clone()).date(), tz);
+    }
 }
 
 impl TryFrom<BreweryRow> for Brewery {
-    type Error = &'static str;
+    type Error = DomainError;
 
     fn try_from(value: BreweryRow) -> Result<Self, Self::Error> {
         if value.name.is_empty() {
-            return Err("brewery name is empty");
+            return Err(DomainError::InvalidRow {
+                id: value.id,
+                reason: "brewery name is empty",
+            });
         }
 
         Ok(Brewery {
@@ -33,17 +203,28 @@ impl TryFrom<BreweryRow> for Brewery {
             lat: value.lat,
             lng: value.lng,
             website: value.website,
+            open_now: None,
+            today_hours: None,
         })
     }
 }
 
+/// A brewery as listed, plus its links and images.
+#[derive(Serialize)]
+pub struct BreweryDetail {
+    #[serde(flatten)]
+    brewery: Brewery,
+    #[serde(flatten)]
+    profile: Profile,
+}
+
 #[derive(Serialize)]
 pub struct Breweries {
     breweries: Vec<Brewery>,
 }
 
 impl TryFrom<Vec<BreweryRow>> for Breweries {
-    type Error = &'static str;
+    type Error = DomainError;
 
     fn try_from(value: Vec<BreweryRow>) -> Result<Self, Self::Error> {
         let breweries = value
@@ -57,16 +238,97 @@ impl TryFrom<Vec<BreweryRow>> for Breweries {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::{BreweryClosureRow, BreweryClosureWrite, WeeklyHoursRow, WeeklyHoursWrite};
     use std::sync::Mutex;
-    use uuid::Uuid;
 
     struct MockBreweryRepo {
         rows: Mutex<Option<Vec<BreweryRow>>>,
     }
 
     impl BreweryRepo for MockBreweryRepo {
-        async fn get_breweries(&self) -> anyhow::Result<Vec<BreweryRow>> {
-            Ok(self.rows.lock().expect("lock poisoned").take().unwrap_or_default())
+        async fn get_breweries(&self) -> Result<Vec<BreweryRow>, RepoError> {
+            Ok(self
+                .rows
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or_default())
+        }
+
+        async fn get_brewery(&self, id: Uuid) -> Result<Option<BreweryRow>, RepoError> {
+            let mut rows = self.rows.lock().expect("lock poisoned");
+            let rows = rows.get_or_insert_with(Vec::new);
+            Ok(rows
+                .iter()
+                .position(|row| row.id == id)
+                .map(|i| rows.remove(i)))
+        }
+
+        async fn create_brewery(&self, brewery: &BreweryWrite) -> Result<BreweryRow, RepoError> {
+            Ok(row_from_write(Uuid::from_u128(1), brewery))
+        }
+
+        async fn update_brewery(
+            &self,
+            id: Uuid,
+            brewery: &BreweryWrite,
+        ) -> Result<Option<BreweryRow>, RepoError> {
+            Ok(Some(row_from_write(id, brewery)))
+        }
+
+        async fn delete_brewery(&self, id: Uuid) -> Result<bool, RepoError> {
+            Ok(self.get_brewery(id).await?.is_some())
+        }
+    }
+
+    impl BreweryHoursRepo for MockBreweryRepo {
+        async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_brewery_closures(
+            &self,
+            _from: jiff::civil::Date,
+            _until: jiff::civil::Date,
+        ) -> Result<Vec<BreweryClosureRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn replace_weekly_hours(
+            &self,
+            _brewery_id: Uuid,
+            _hours: &[WeeklyHoursWrite],
+        ) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn upsert_brewery_closure(
+            &self,
+            _closure: &BreweryClosureWrite,
+        ) -> Result<BreweryClosureRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn delete_brewery_closure(
+            &self,
+            _brewery_id: Uuid,
+            _id: Uuid,
+        ) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    fn row_from_write(id: Uuid, brewery: &BreweryWrite) -> BreweryRow {
+        BreweryRow {
+            id,
+            name: brewery.name.clone(),
+            notes: brewery.notes.clone(),
+            website: brewery.website.clone(),
+            address: brewery.address.clone(),
+            lat: brewery.lat,
+            lng: brewery.lng,
+            drink_menu: brewery.drink_menu.clone(),
+            food_schedule: brewery.food_schedule.clone(),
         }
     }
 
@@ -90,7 +352,9 @@ mod tests {
             rows: Mutex::new(Some(vec![sample_brewery_row("Stoup Brewing")])),
         };
 
-        let result = get_breweries(&repo).await.expect("valid result");
+        let result = get_breweries(&repo, jiff::Timestamp::now(), &TimeZone::UTC)
+            .await
+            .expect("valid result");
         assert_eq!(result.breweries.len(), 1);
         let first = &result.breweries[0];
         assert_eq!(first.id, Uuid::nil().to_string());
@@ -103,4 +367,113 @@ mod tests {
         let result = Brewery::try_from(row);
         assert!(result.is_err());
     }
+
+    fn create_input(name: &str, lat: Option<f64>, lng: Option<f64>) -> CreateBrewery {
+        CreateBrewery {
+            name: name.to_string(),
+            notes: None,
+            website: None,
+            address: None,
+            lat,
+            lng,
+            drink_menu: None,
+            food_schedule: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_brewery_trims_name() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(None),
+        };
+
+        let brewery = create_brewery(&repo, create_input("  Stoup Brewing ", None, None))
+            .await
+            .expect("valid brewery");
+        assert_eq!(brewery.name, "Stoup Brewing");
+    }
+
+    #[tokio::test]
+    async fn create_brewery_reports_each_invalid_field() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(None),
+        };
+
+        let result = create_brewery(&repo, create_input("", Some(47.6), Some(-222.0))).await;
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["name", "lng"]);
+    }
+
+    #[tokio::test]
+    async fn update_brewery_keeps_absent_fields_and_clears_nulls() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(Some(vec![sample_brewery_row("Stoup Brewing")])),
+        };
+        let input: UpdateBrewery =
+            serde_json::from_str(r#"{"name": "Stoup Ballard", "website": null}"#)
+                .expect("valid json");
+
+        let brewery = update_brewery(
+            &repo,
+            Uuid::nil(),
+            input,
+            jiff::Timestamp::now(),
+            &TimeZone::UTC,
+        )
+        .await
+        .expect("valid brewery");
+        assert_eq!(brewery.name, "Stoup Ballard");
+        assert_eq!(brewery.website, None);
+        assert_eq!(brewery.address.as_deref(), Some("123 Ballard Ave"));
+        assert_eq!(brewery.lat, Some(47.6665));
+    }
+
+    #[tokio::test]
+    async fn update_brewery_rejects_half_cleared_coordinates() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(Some(vec![sample_brewery_row("Stoup Brewing")])),
+        };
+        let input: UpdateBrewery = serde_json::from_str(r#"{"lat": null}"#).expect("valid json");
+
+        let result = update_brewery(
+            &repo,
+            Uuid::nil(),
+            input,
+            jiff::Timestamp::now(),
+            &TimeZone::UTC,
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn update_brewery_unknown_id_is_not_found() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(None),
+        };
+        let input: UpdateBrewery = serde_json::from_str("{}").expect("valid json");
+
+        let result = update_brewery(
+            &repo,
+            Uuid::from_u128(9),
+            input,
+            jiff::Timestamp::now(),
+            &TimeZone::UTC,
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn delete_brewery_unknown_id_is_not_found() {
+        let repo = MockBreweryRepo {
+            rows: Mutex::new(None),
+        };
+
+        let result = delete_brewery(&repo, Uuid::from_u128(9)).await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
 }
@@ -0,0 +1,685 @@
+use crate::db::{
+    BreweryClosureRow, BreweryClosureWrite, BreweryHoursRepo, BreweryRepo, RepoError,
+    WeeklyHoursRow, WeeklyHoursWrite,
+};
+use crate::domain::DomainError;
+use crate::domain::recurring::{to_jiff_date, to_jiff_time};
+use crate::domain::validation::Validator;
+use jiff::ToSpan;
+use jiff::civil::{Date, Time, Weekday};
+use jiff::tz::TimeZone;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Days of upcoming closures listed with a brewery's hours.
+const UPCOMING_CLOSURE_DAYS: i64 = 366;
+
+/// Returns a brewery's weekly hours and its closures from `today` on.
+pub async fn get_brewery_hours<R: BreweryRepo + BreweryHoursRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    today: Date,
+) -> Result<BreweryHours, DomainError> {
+    db.get_brewery(brewery_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let weekly = db
+        .get_weekly_hours()
+        .await?
+        .into_iter()
+        .filter(|row| row.brewery_id == brewery_id)
+        .map(|row| WeeklyPeriod::try_from(&row).map(WeeklyHours::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    let closures = db
+        .get_brewery_closures(today, today + UPCOMING_CLOSURE_DAYS.days())
+        .await?
+        .into_iter()
+        .filter(|row| row.brewery_id == brewery_id)
+        .map(BreweryClosure::from)
+        .collect();
+    Ok(BreweryHours {
+        brewery_id,
+        weekly,
+        closures,
+    })
+}
+
+/// Replaces a brewery's weekly hours. An empty list makes its hours unknown.
+pub async fn set_weekly_hours<R: BreweryRepo + BreweryHoursRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    input: SetWeeklyHours,
+    today: Date,
+) -> Result<BreweryHours, DomainError> {
+    let writes = input.into_writes()?;
+    db.replace_weekly_hours(brewery_id, &writes).await?;
+    get_brewery_hours(db, brewery_id, today).await
+}
+
+/// Closes a brewery for a day or gives it special hours, replacing what was set for that
+/// date before.
+pub async fn create_brewery_closure<R: BreweryHoursRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    input: CreateBreweryClosure,
+) -> Result<BreweryClosure, DomainError> {
+    let write = input.into_write(brewery_id)?;
+    match db.upsert_brewery_closure(&write).await {
+        Ok(row) => Ok(BreweryClosure::from(row)),
+        Err(RepoError::ForeignKeyViolation { .. }) => Err(DomainError::NotFound),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn delete_brewery_closure<R: BreweryHoursRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    id: Uuid,
+) -> Result<(), DomainError> {
+    if db.delete_brewery_closure(brewery_id, id).await? {
+        Ok(())
+    } else {
+        Err(DomainError::NotFound)
+    }
+}
+
+/// Loads every brewery's hours with its closures dated `from` through `until`. Closures
+/// on the day before `from` are included because their periods can run past midnight.
+pub(super) async fn load_business_hours<R: BreweryHoursRepo>(
+    db: &R,
+    from: Date,
+    until: Date,
+) -> Result<HashMap<Uuid, BusinessHours>, DomainError> {
+    let mut hours: HashMap<Uuid, BusinessHours> = HashMap::new();
+    for row in db.get_weekly_hours().await? {
+        let period = WeeklyPeriod::try_from(&row)?;
+        hours.entry(row.brewery_id).or_default().weekly.push(period);
+    }
+    let from = from.yesterday().expect("dates are within jiff's range");
+    for row in db.get_brewery_closures(from, until).await? {
+        let closure = Closure::from(&row);
+        hours
+            .entry(row.brewery_id)
+            .or_default()
+            .closures
+            .insert(closure.date, closure);
+    }
+    Ok(hours)
+}
+
+/// Local opening and closing time; a close before the open is on the next day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct OpenPeriod {
+    opens_at: Time,
+    closes_at: Time,
+}
+
+impl OpenPeriod {
+    /// The period as instants when it opens on the local `date`.
+    fn on(self, date: Date, tz: &TimeZone) -> (jiff::Timestamp, jiff::Timestamp) {
+        let close_date = if self.closes_at < self.opens_at {
+            date.tomorrow().expect("dates are within jiff's range")
+        } else {
+            date
+        };
+        (
+            local_instant(date, self.opens_at, tz),
+            local_instant(close_date, self.closes_at, tz),
+        )
+    }
+}
+
+pub(super) fn local_instant(date: Date, time: Time, tz: &TimeZone) -> jiff::Timestamp {
+    date.to_datetime(time)
+        .to_zoned(tz.clone())
+        .expect("local times resolve in the configured zone")
+        .timestamp()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct WeeklyPeriod {
+    weekday: Weekday,
+    period: OpenPeriod,
+}
+
+impl TryFrom<&WeeklyHoursRow> for WeeklyPeriod {
+    type Error = DomainError;
+
+    fn try_from(value: &WeeklyHoursRow) -> Result<Self, Self::Error> {
+        let weekday = i8::try_from(value.weekday)
+            .ok()
+            .and_then(|n| Weekday::from_monday_one_offset(n).ok())
+            .ok_or(DomainError::InvalidRow {
+                id: value.id,
+                reason: "brewery hours weekday is out of range",
+            })?;
+        Ok(WeeklyPeriod {
+            weekday,
+            period: OpenPeriod {
+                opens_at: to_jiff_time(value.opens_at),
+                closes_at: to_jiff_time(value.closes_at),
+            },
+        })
+    }
+}
+
+struct Closure {
+    date: Date,
+    /// Special hours for the day; closed all day when `None`.
+    hours: Option<OpenPeriod>,
+    reason: String,
+}
+
+impl From<&BreweryClosureRow> for Closure {
+    fn from(value: &BreweryClosureRow) -> Self {
+        let hours = match (value.opens_at, value.closes_at) {
+            (Some(opens_at), Some(closes_at)) => Some(OpenPeriod {
+                opens_at: to_jiff_time(opens_at),
+                closes_at: to_jiff_time(closes_at),
+            }),
+            _ => None,
+        };
+        Closure {
+            date: to_jiff_date(value.on_date),
+            hours,
+            reason: value.reason.clone(),
+        }
+    }
+}
+
+/// One brewery's weekly hours and the closures loaded with them.
+#[derive(Default)]
+pub(super) struct BusinessHours {
+    weekly: Vec<WeeklyPeriod>,
+    closures: HashMap<Date, Closure>,
+}
+
+#[cfg(test)]
+impl BusinessHours {
+    /// Open every day from `opens_at` to `closes_at`.
+    pub(super) fn daily(opens_at: &str, closes_at: &str) -> Self {
+        let period = OpenPeriod {
+            opens_at: opens_at.parse().unwrap(),
+            closes_at: closes_at.parse().unwrap(),
+        };
+        let weekly = (1..=7)
+            .map(|n| WeeklyPeriod {
+                weekday: Weekday::from_monday_one_offset(n).unwrap(),
+                period,
+            })
+            .collect();
+        BusinessHours {
+            weekly,
+            closures: HashMap::new(),
+        }
+    }
+
+    pub(super) fn close_on(&mut self, date: Date, reason: &str) {
+        let closure = Closure {
+            date,
+            hours: None,
+            reason: reason.to_string(),
+        };
+        self.closures.insert(date, closure);
+    }
+}
+
+/// Whether a brewery is open at an instant.
+#[derive(Debug, PartialEq)]
+pub(super) enum OpenAt {
+    /// The brewery has no weekly hours and no closure on that day.
+    Unknown,
+    Closed,
+    Open {
+        until: jiff::Timestamp,
+    },
+}
+
+impl BusinessHours {
+    /// Periods opening on the local `date` and the closure reason, or `None` when the
+    /// hours for that day are unknown.
+    fn day(&self, date: Date) -> Option<(Vec<OpenPeriod>, Option<&str>)> {
+        if let Some(closure) = self.closures.get(&date) {
+            return Some((closure.hours.into_iter().collect(), Some(&closure.reason)));
+        }
+        if self.weekly.is_empty() {
+            return None;
+        }
+        let periods = self
+            .weekly
+            .iter()
+            .filter(|weekly| weekly.weekday == date.weekday())
+            .map(|weekly| weekly.period)
+            .collect();
+        Some((periods, None))
+    }
+
+    pub(super) fn open_at(&self, at: jiff::Timestamp, tz: &TimeZone) -> OpenAt {
+        let date = at.to_zoned(tz.clone()).date();
+        let yesterday = date.yesterday().expect("dates are within jiff's range");
+        for day in [yesterday, date] {
+            let Some((periods, _)) = self.day(day) else {
+                continue;
+            };
+            for period in periods {
+                let (opens, closes) = period.on(day, tz);
+                if opens <= at && at < closes {
+                    return OpenAt::Open { until: closes };
+                }
+            }
+        }
+        if self.day(date).is_some() {
+            OpenAt::Closed
+        } else {
+            OpenAt::Unknown
+        }
+    }
+
+    /// The local `date`'s opening hours, or `None` when they are unknown.
+    pub(super) fn day_hours(&self, date: Date, tz: &TimeZone) -> Option<DayHours> {
+        let (periods, reason) = self.day(date)?;
+        Some(DayHours {
+            date: date.to_string(),
+            periods: periods
+                .into_iter()
+                .map(|period| {
+                    let (opens, closes) = period.on(date, tz);
+                    OpenInterval {
+                        opens_at: opens.to_string(),
+                        closes_at: closes.to_string(),
+                    }
+                })
+                .collect(),
+            reason: reason.map(str::to_owned),
+        })
+    }
+
+    /// Set when the brewery is known to be closed all day on the local `date`.
+    pub(super) fn closed_day(&self, date: Date) -> Option<ClosedDay> {
+        match self.day(date)? {
+            (periods, reason) if periods.is_empty() => Some(ClosedDay {
+                date: date.to_string(),
+                reason: reason.map(str::to_owned),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A brewery's opening hours on one local day. No periods means closed.
+#[derive(Debug, Serialize)]
+pub struct DayHours {
+    date: String,
+    periods: Vec<OpenInterval>,
+    /// Why the day differs from the weekly hours.
+    reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenInterval {
+    opens_at: String,
+    closes_at: String,
+}
+
+/// A local day a brewery is closed, shown with schedules.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ClosedDay {
+    date: String,
+    reason: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<DayOfWeek> for Weekday {
+    fn from(value: DayOfWeek) -> Self {
+        match value {
+            DayOfWeek::Monday => Weekday::Monday,
+            DayOfWeek::Tuesday => Weekday::Tuesday,
+            DayOfWeek::Wednesday => Weekday::Wednesday,
+            DayOfWeek::Thursday => Weekday::Thursday,
+            DayOfWeek::Friday => Weekday::Friday,
+            DayOfWeek::Saturday => Weekday::Saturday,
+            DayOfWeek::Sunday => Weekday::Sunday,
+        }
+    }
+}
+
+impl From<Weekday> for DayOfWeek {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Monday => DayOfWeek::Monday,
+            Weekday::Tuesday => DayOfWeek::Tuesday,
+            Weekday::Wednesday => DayOfWeek::Wednesday,
+            Weekday::Thursday => DayOfWeek::Thursday,
+            Weekday::Friday => DayOfWeek::Friday,
+            Weekday::Saturday => DayOfWeek::Saturday,
+            Weekday::Sunday => DayOfWeek::Sunday,
+        }
+    }
+}
+
+/// One weekly open period, local to `LOCAL_TZ`. A `closes_at` before `opens_at` is on
+/// the next day.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeeklyHours {
+    weekday: DayOfWeek,
+    opens_at: Time,
+    closes_at: Time,
+}
+
+impl From<WeeklyPeriod> for WeeklyHours {
+    fn from(value: WeeklyPeriod) -> Self {
+        WeeklyHours {
+            weekday: value.weekday.into(),
+            opens_at: value.period.opens_at,
+            closes_at: value.period.closes_at,
+        }
+    }
+}
+
+/// Admin input replacing a brewery's weekly hours.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetWeeklyHours {
+    weekly: Vec<WeeklyHours>,
+}
+
+impl SetWeeklyHours {
+    fn into_writes(self) -> Result<Vec<WeeklyHoursWrite>, DomainError> {
+        let mut v = Validator::default();
+        v.check(
+            self.weekly
+                .iter()
+                .all(|hours| hours.opens_at != hours.closes_at),
+            "weekly",
+            "closes_at must differ from opens_at",
+        );
+
+        // Minutes after the start of the weekday, with overnight closes past 24:00.
+        let minutes = |time: Time| i32::from(time.hour()) * 60 + i32::from(time.minute());
+        let mut spans: Vec<(i8, i32, i32)> = self
+            .weekly
+            .iter()
+            .map(|hours| {
+                let opens = minutes(hours.opens_at);
+                let mut closes = minutes(hours.closes_at);
+                if hours.closes_at < hours.opens_at {
+                    closes += 24 * 60;
+                }
+                let weekday = Weekday::from(hours.weekday).to_monday_one_offset();
+                (weekday, opens, closes)
+            })
+            .collect();
+        spans.sort();
+        v.check(
+            spans
+                .windows(2)
+                .all(|pair| pair[0].0 != pair[1].0 || pair[1].1 >= pair[0].2),
+            "weekly",
+            "periods on the same weekday must not overlap",
+        );
+        v.finish()?;
+
+        Ok(self
+            .weekly
+            .into_iter()
+            .map(|hours| WeeklyHoursWrite {
+                weekday: i16::from(Weekday::from(hours.weekday).to_monday_one_offset()),
+                opens_at: hours.opens_at,
+                closes_at: hours.closes_at,
+            })
+            .collect())
+    }
+}
+
+/// Admin input closing a brewery for a local date, or with `opens_at` and `closes_at`
+/// giving it special hours that day.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateBreweryClosure {
+    date: Date,
+    opens_at: Option<Time>,
+    closes_at: Option<Time>,
+    reason: String,
+}
+
+impl CreateBreweryClosure {
+    fn into_write(self, brewery_id: Uuid) -> Result<BreweryClosureWrite, DomainError> {
+        let mut v = Validator::default();
+        v.non_empty("reason", &self.reason);
+        v.check(
+            self.opens_at.is_some() == self.closes_at.is_some(),
+            if self.opens_at.is_some() {
+                "closes_at"
+            } else {
+                "opens_at"
+            },
+            "opens_at and closes_at must be set together",
+        );
+        v.check(
+            self.opens_at.is_none() || self.opens_at != self.closes_at,
+            "closes_at",
+            "must differ from opens_at",
+        );
+        v.finish()?;
+
+        Ok(BreweryClosureWrite {
+            brewery_id,
+            on_date: self.date,
+            hours: self.opens_at.zip(self.closes_at),
+            reason: self.reason.trim().to_string(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct BreweryClosure {
+    id: Uuid,
+    brewery_id: Uuid,
+    date: String,
+    opens_at: Option<String>,
+    closes_at: Option<String>,
+    reason: String,
+    created_at: String,
+}
+
+impl From<BreweryClosureRow> for BreweryClosure {
+    fn from(value: BreweryClosureRow) -> Self {
+        BreweryClosure {
+            id: value.id,
+            brewery_id: value.brewery_id,
+            date: value.on_date.to_string(),
+            opens_at: value.opens_at.map(|time| to_jiff_time(time).to_string()),
+            closes_at: value.closes_at.map(|time| to_jiff_time(time).to_string()),
+            reason: value.reason,
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BreweryHours {
+    brewery_id: Uuid,
+    weekly: Vec<WeeklyHours>,
+    /// Closures and special hours from today on.
+    closures: Vec<BreweryClosure>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(opens_at: &str, closes_at: &str) -> OpenPeriod {
+        OpenPeriod {
+            opens_at: opens_at.parse().unwrap(),
+            closes_at: closes_at.parse().unwrap(),
+        }
+    }
+
+    /// Open 15:00-22:00 Monday to Thursday and 12:00-01:00 Friday.
+    fn stoup_hours() -> BusinessHours {
+        let mut weekly: Vec<WeeklyPeriod> = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+        ]
+        .into_iter()
+        .map(|weekday| WeeklyPeriod {
+            weekday,
+            period: period("15:00", "22:00"),
+        })
+        .collect();
+        weekly.push(WeeklyPeriod {
+            weekday: Weekday::Friday,
+            period: period("12:00", "01:00"),
+        });
+        BusinessHours {
+            weekly,
+            closures: HashMap::new(),
+        }
+    }
+
+    fn tz() -> TimeZone {
+        TimeZone::get("America/Los_Angeles").unwrap()
+    }
+
+    fn local(datetime: &str) -> jiff::Timestamp {
+        datetime
+            .parse::<jiff::civil::DateTime>()
+            .unwrap()
+            .to_zoned(tz())
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn open_at_follows_weekly_hours_past_midnight() {
+        let hours = stoup_hours();
+        // 2026-03-06 is a Friday.
+        assert_eq!(
+            hours.open_at(local("2026-03-06T23:30"), &tz()),
+            OpenAt::Open {
+                until: local("2026-03-07T01:00")
+            }
+        );
+        assert_eq!(
+            hours.open_at(local("2026-03-07T00:30"), &tz()),
+            OpenAt::Open {
+                until: local("2026-03-07T01:00")
+            }
+        );
+        assert_eq!(
+            hours.open_at(local("2026-03-07T01:00"), &tz()),
+            OpenAt::Closed
+        );
+        assert_eq!(
+            hours.open_at(local("2026-03-05T10:00"), &tz()),
+            OpenAt::Closed
+        );
+    }
+
+    #[test]
+    fn closures_replace_the_weekly_hours() {
+        let mut hours = stoup_hours();
+        let closed: Date = "2026-03-05".parse().unwrap();
+        let short: Date = "2026-03-04".parse().unwrap();
+        hours.closures.insert(
+            closed,
+            Closure {
+                date: closed,
+                hours: None,
+                reason: "Staff party".to_string(),
+            },
+        );
+        hours.closures.insert(
+            short,
+            Closure {
+                date: short,
+                hours: Some(period("12:00", "17:00")),
+                reason: "Holiday".to_string(),
+            },
+        );
+
+        assert_eq!(
+            hours.open_at(local("2026-03-05T18:00"), &tz()),
+            OpenAt::Closed
+        );
+        assert_eq!(
+            hours.closed_day(closed),
+            Some(ClosedDay {
+                date: "2026-03-05".to_string(),
+                reason: Some("Staff party".to_string()),
+            })
+        );
+        assert_eq!(
+            hours.open_at(local("2026-03-04T12:30"), &tz()),
+            OpenAt::Open {
+                until: local("2026-03-04T17:00")
+            }
+        );
+        assert_eq!(
+            hours.open_at(local("2026-03-04T18:00"), &tz()),
+            OpenAt::Closed
+        );
+        // Saturday has no weekly hours.
+        assert!(hours.closed_day("2026-03-07".parse().unwrap()).is_some());
+    }
+
+    #[test]
+    fn hours_without_weekly_periods_are_unknown() {
+        let hours = BusinessHours::default();
+        assert_eq!(
+            hours.open_at(local("2026-03-05T18:00"), &tz()),
+            OpenAt::Unknown
+        );
+        assert!(
+            hours
+                .day_hours("2026-03-05".parse().unwrap(), &tz())
+                .is_none()
+        );
+        assert!(hours.closed_day("2026-03-05".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn set_weekly_hours_rejects_overlapping_periods() {
+        let input: SetWeeklyHours = serde_json::from_str(
+            r#"{"weekly": [
+                {"weekday": "friday", "opens_at": "12:00", "closes_at": "01:00"},
+                {"weekday": "friday", "opens_at": "23:00", "closes_at": "23:30"}
+            ]}"#,
+        )
+        .unwrap();
+        let Err(DomainError::Validation(errors)) = input.into_writes() else {
+            panic!("expected validation error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "periods on the same weekday must not overlap"
+        );
+    }
+
+    #[test]
+    fn closure_hours_must_be_set_together() {
+        let input: CreateBreweryClosure = serde_json::from_str(
+            r#"{"date": "2026-12-24", "opens_at": "12:00", "reason": "Xmas"}"#,
+        )
+        .unwrap();
+        let Err(DomainError::Validation(errors)) = input.into_write(Uuid::nil()) else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "closes_at");
+    }
+}
@@ -0,0 +1,47 @@
+use crate::db::RepoError;
+use crate::domain::api_keys::Scope;
+use crate::domain::validation::FieldError;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Failure surfaced by domain functions; the API layer maps each variant to a status.
+#[derive(Debug, Error)]
+pub enum DomainError {
+    #[error("repository unavailable: {0}")]
+    RepoUnavailable(#[source] RepoError),
+    #[error("invalid row {id}: {reason}")]
+    InvalidRow { id: Uuid, reason: &'static str },
+    #[error("not found")]
+    NotFound,
+    #[error("repository timed out")]
+    Timeout,
+    #[error("validation failed")]
+    Validation(Vec<FieldError>),
+    #[error("missing or invalid api key")]
+    Unauthorized,
+    #[error("api key lacks the {0} scope")]
+    Forbidden(Scope),
+    #[error("conflict: {0}")]
+    Conflict(&'static str),
+    #[error("requested window starts after available data ends at {data_available_until}")]
+    OutsideHorizon { data_available_until: String },
+    #[error(transparent)]
+    Repo(RepoError),
+}
+
+impl From<RepoError> for DomainError {
+    fn from(value: RepoError) -> Self {
+        match value {
+            RepoError::Unavailable(_) => DomainError::RepoUnavailable(value),
+            RepoError::Timeout => DomainError::Timeout,
+            RepoError::NotFound => DomainError::NotFound,
+            RepoError::ForeignKeyViolation { .. } => {
+                DomainError::Conflict("row is referenced by or references missing rows")
+            }
+            RepoError::ExclusionViolation { .. } => {
+                DomainError::Conflict("vendor is already scheduled for overlapping hours")
+            }
+            RepoError::Query(_) => DomainError::Repo(value),
+        }
+    }
+}
@@ -0,0 +1,816 @@
+use crate::db::{
+    BreweryRepo, BreweryRow, BreweryWrite, ImportRepo, ImportScheduleWrite, ImportWrite, ParentRef,
+    ScheduleRow, ScheduleWrite, VendorRepo, VendorRow, VendorWrite,
+};
+use crate::domain::DomainError;
+use crate::domain::breweries::{CreateBrewery, validate_brewery};
+use crate::domain::schedules::{
+    IMPORT_SOURCE, ScheduleStatus, SourceKind, row_hours, schedule_write_error, validate_schedule,
+};
+use crate::domain::validation::FieldError;
+use crate::domain::vendors::{CreateVendor, validate_vendor};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Validates and dedupes an import file, then applies it in one transaction unless
+/// `dry_run` is set or any record was rejected.
+///
+/// Breweries match stored rows by name and address, vendors by name, and schedule
+/// entries by brewery, vendor and start, all ignoring case and extra whitespace.
+/// Matched rows are updated in place; empty fields keep the stored value.
+pub async fn import_records<R: BreweryRepo + VendorRepo + ImportRepo>(
+    db: &R,
+    format: ImportFormat,
+    body: &str,
+    dry_run: bool,
+) -> Result<ImportReport, DomainError> {
+    let file = ImportFile::parse(format, body)?;
+    let mut plan = ImportPlan {
+        breweries: db.get_breweries().await?,
+        vendors: db.get_vendors().await?,
+        ..ImportPlan::default()
+    };
+    plan.report.errors = file.errors;
+    plan.add_breweries(file.breweries);
+    plan.add_vendors(file.vendors);
+    let pending = plan.add_schedules(file.schedules);
+    let keys: Vec<_> = pending
+        .iter()
+        .filter_map(|schedule| schedule.stored_key())
+        .collect();
+    let stored = if keys.is_empty() {
+        Vec::new()
+    } else {
+        db.get_schedules_by_start(&keys).await?
+    };
+    plan.match_schedules(pending, stored)?;
+
+    let mut report = plan.report;
+    report.dry_run = dry_run;
+    report.errors.sort_by_key(|error| (error.kind, error.line));
+    if dry_run || !report.errors.is_empty() {
+        return Ok(report);
+    }
+    db.apply_import(&plan.write)
+        .await
+        .map_err(schedule_write_error)?;
+    report.applied = true;
+    Ok(report)
+}
+
+/// Which records a CSV file holds. JSON files name their sections instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    Breweries,
+    Vendors,
+    Schedules,
+}
+
+impl fmt::Display for ImportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImportKind::Breweries => "breweries",
+            ImportKind::Vendors => "vendors",
+            ImportKind::Schedules => "schedules",
+        })
+    }
+}
+
+impl FromStr for ImportKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "breweries" => Ok(ImportKind::Breweries),
+            "vendors" => Ok(ImportKind::Vendors),
+            "schedules" => Ok(ImportKind::Schedules),
+            _ => Err(format!(
+                "unknown import kind '{}' (expected breweries, vendors or schedules)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// An object with optional `breweries`, `vendors` and `schedules` arrays.
+    Json,
+    /// One record per row with a header line.
+    Csv(ImportKind),
+}
+
+/// An imported schedule entry. Breweries and vendors are named rather than referenced
+/// by id so one file can create them and their schedule together.
+#[derive(Deserialize)]
+struct ImportSchedule {
+    brewery: String,
+    /// Picks between breweries that share a name.
+    brewery_address: Option<String>,
+    vendor: String,
+    start_at: jiff::Timestamp,
+    end_at: Option<jiff::Timestamp>,
+    all_day: Option<bool>,
+    status: Option<ScheduleStatus>,
+}
+
+/// Outcome of an import. Nothing is written unless `applied` is set.
+#[derive(Debug, Default, Serialize)]
+pub struct ImportReport {
+    dry_run: bool,
+    applied: bool,
+    breweries: ImportCounts,
+    vendors: ImportCounts,
+    schedules: ImportCounts,
+    errors: Vec<RecordError>,
+}
+
+impl ImportReport {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ImportCounts {
+    created: usize,
+    updated: usize,
+    unchanged: usize,
+}
+
+/// A rejected record. `line` is the CSV line, or the record's 1-based position in its
+/// JSON array.
+#[derive(Debug, Serialize)]
+pub struct RecordError {
+    kind: ImportKind,
+    line: usize,
+    fields: Vec<FieldError>,
+}
+
+/// A record and where it came from.
+struct Record<T> {
+    line: usize,
+    value: T,
+}
+
+#[derive(Default)]
+struct ImportFile {
+    breweries: Vec<Record<CreateBrewery>>,
+    vendors: Vec<Record<CreateVendor>>,
+    schedules: Vec<Record<ImportSchedule>>,
+    errors: Vec<RecordError>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct JsonImport {
+    breweries: Vec<serde_json::Value>,
+    vendors: Vec<serde_json::Value>,
+    schedules: Vec<serde_json::Value>,
+}
+
+impl ImportFile {
+    /// Splits `body` into records. A record that doesn't deserialize is reported
+    /// against its line; a file that can't be read at all fails the import.
+    fn parse(format: ImportFormat, body: &str) -> Result<Self, DomainError> {
+        let mut file = ImportFile::default();
+        match format {
+            ImportFormat::Json => {
+                let json: JsonImport = serde_json::from_str(body)
+                    .map_err(|e| body_error(format!("is not a valid import: {}", e)))?;
+                file.breweries =
+                    json_records(ImportKind::Breweries, json.breweries, &mut file.errors);
+                file.vendors = json_records(ImportKind::Vendors, json.vendors, &mut file.errors);
+                file.schedules =
+                    json_records(ImportKind::Schedules, json.schedules, &mut file.errors);
+            }
+            ImportFormat::Csv(kind) => {
+                let mut reader = csv::ReaderBuilder::new()
+                    .trim(csv::Trim::All)
+                    .from_reader(body.as_bytes());
+                reader
+                    .headers()
+                    .map_err(|e| body_error(format!("has an unreadable header: {}", e)))?;
+                match kind {
+                    ImportKind::Breweries => {
+                        file.breweries = csv_records(kind, &mut reader, &mut file.errors)
+                    }
+                    ImportKind::Vendors => {
+                        file.vendors = csv_records(kind, &mut reader, &mut file.errors)
+                    }
+                    ImportKind::Schedules => {
+                        file.schedules = csv_records(kind, &mut reader, &mut file.errors)
+                    }
+                }
+            }
+        }
+        Ok(file)
+    }
+}
+
+fn json_records<T: DeserializeOwned>(
+    kind: ImportKind,
+    values: Vec<serde_json::Value>,
+    errors: &mut Vec<RecordError>,
+) -> Vec<Record<T>> {
+    let mut records = Vec::new();
+    for (i, value) in values.into_iter().enumerate() {
+        match serde_json::from_value(value) {
+            Ok(value) => records.push(Record { line: i + 1, value }),
+            Err(e) => errors.push(unreadable(kind, i + 1, e)),
+        }
+    }
+    records
+}
+
+fn csv_records<T: DeserializeOwned>(
+    kind: ImportKind,
+    reader: &mut csv::Reader<&[u8]>,
+    errors: &mut Vec<RecordError>,
+) -> Vec<Record<T>> {
+    let headers = reader.headers().cloned().unwrap_or_default();
+    let mut records = Vec::new();
+    for row in reader.records() {
+        let row = match row {
+            Ok(row) => row,
+            Err(e) => {
+                let line = e.position().map_or(0, |p| p.line() as usize);
+                errors.push(unreadable(kind, line, e));
+                continue;
+            }
+        };
+        let line = row.position().map_or(0, |p| p.line() as usize);
+        match row.deserialize(Some(&headers)) {
+            Ok(value) => records.push(Record { line, value }),
+            Err(e) => errors.push(unreadable(kind, line, e)),
+        }
+    }
+    records
+}
+
+fn unreadable(kind: ImportKind, line: usize, e: impl fmt::Display) -> RecordError {
+    RecordError {
+        kind,
+        line,
+        fields: vec![FieldError {
+            field: "record",
+            message: e.to_string(),
+        }],
+    }
+}
+
+fn body_error(message: String) -> DomainError {
+    DomainError::Validation(vec![FieldError {
+        field: "body",
+        message,
+    }])
+}
+
+/// Lowercases and collapses whitespace so natural keys ignore formatting.
+fn normalize(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Field errors from a validator, which only ever fails with `Validation`.
+fn field_errors(result: Result<(), DomainError>) -> Vec<FieldError> {
+    match result {
+        Err(DomainError::Validation(fields)) => fields,
+        _ => Vec::new(),
+    }
+}
+
+/// A schedule entry whose parents are resolved but which may still match a stored row.
+struct PendingSchedule {
+    brewery: ParentRef,
+    vendor: ParentRef,
+    start: jiff::Timestamp,
+    end: Option<jiff::Timestamp>,
+    all_day: Option<bool>,
+    status: Option<ScheduleStatus>,
+}
+
+impl PendingSchedule {
+    /// Only entries whose brewery and vendor already exist can match a stored row.
+    fn stored_key(&self) -> Option<(Uuid, Uuid, jiff::Timestamp)> {
+        match (self.brewery, self.vendor) {
+            (ParentRef::Existing(brewery), ParentRef::Existing(vendor)) => {
+                Some((brewery, vendor, self.start))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ImportPlan {
+    breweries: Vec<BreweryRow>,
+    vendors: Vec<VendorRow>,
+    /// Brewery name → (address, brewery) for every stored or imported brewery.
+    brewery_names: HashMap<String, Vec<(String, ParentRef)>>,
+    vendor_names: HashMap<String, ParentRef>,
+    write: ImportWrite,
+    report: ImportReport,
+}
+
+impl ImportPlan {
+    fn reject(&mut self, kind: ImportKind, line: usize, fields: Vec<FieldError>) {
+        self.report.errors.push(RecordError { kind, line, fields });
+    }
+
+    fn duplicate(&mut self, kind: ImportKind, line: usize, first: usize) {
+        self.reject(
+            kind,
+            line,
+            vec![FieldError {
+                field: "record",
+                message: format!("duplicates line {}", first),
+            }],
+        );
+    }
+
+    fn add_breweries(&mut self, records: Vec<Record<CreateBrewery>>) {
+        for row in &self.breweries {
+            let address = normalize(row.address.as_deref().unwrap_or_default());
+            let names = self.brewery_names.entry(normalize(&row.name)).or_default();
+            if !names.iter().any(|(stored, _)| *stored == address) {
+                names.push((address, ParentRef::Existing(row.id)));
+            }
+        }
+
+        let mut seen: HashMap<(String, String), usize> = HashMap::new();
+        for record in records {
+            let write = record.value.into_write();
+            let errors = field_errors(validate_brewery(&write));
+            if !errors.is_empty() {
+                self.reject(ImportKind::Breweries, record.line, errors);
+                continue;
+            }
+            let name = normalize(&write.name);
+            let address = normalize(write.address.as_deref().unwrap_or_default());
+            if let Some(&first) = seen.get(&(name.clone(), address.clone())) {
+                self.duplicate(ImportKind::Breweries, record.line, first);
+                continue;
+            }
+            seen.insert((name.clone(), address.clone()), record.line);
+
+            let stored = self.breweries.iter().find(|row| {
+                normalize(&row.name) == name
+                    && normalize(row.address.as_deref().unwrap_or_default()) == address
+            });
+            let Some(stored) = stored else {
+                let index = self.write.new_breweries.len();
+                self.write.new_breweries.push(write);
+                self.brewery_names
+                    .entry(name)
+                    .or_default()
+                    .push((address, ParentRef::New(index)));
+                self.report.breweries.created += 1;
+                continue;
+            };
+            // Name and address matched the stored row, so its spelling is kept.
+            let merged = BreweryWrite {
+                name: stored.name.clone(),
+                notes: write.notes.or_else(|| stored.notes.clone()),
+                website: write.website.or_else(|| stored.website.clone()),
+                address: stored.address.clone(),
+                lat: write.lat.or(stored.lat),
+                lng: write.lng.or(stored.lng),
+                drink_menu: write.drink_menu.or_else(|| stored.drink_menu.clone()),
+                food_schedule: write.food_schedule.or_else(|| stored.food_schedule.clone()),
+            };
+            let unchanged = merged.name == stored.name
+                && merged.notes == stored.notes
+                && merged.website == stored.website
+                && merged.address == stored.address
+                && merged.lat == stored.lat
+                && merged.lng == stored.lng
+                && merged.drink_menu == stored.drink_menu
+                && merged.food_schedule == stored.food_schedule;
+            if unchanged {
+                self.report.breweries.unchanged += 1;
+            } else {
+                self.write.updated_breweries.push((stored.id, merged));
+                self.report.breweries.updated += 1;
+            }
+        }
+    }
+
+    fn add_vendors(&mut self, records: Vec<Record<CreateVendor>>) {
+        for row in &self.vendors {
+            self.vendor_names
+                .entry(normalize(&row.name))
+                .or_insert(ParentRef::Existing(row.id));
+        }
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for record in records {
+            let write = record.value.into_write();
+            let errors = field_errors(validate_vendor(&write));
+            if !errors.is_empty() {
+                self.reject(ImportKind::Vendors, record.line, errors);
+                continue;
+            }
+            let name = normalize(&write.name);
+            if let Some(&first) = seen.get(&name) {
+                self.duplicate(ImportKind::Vendors, record.line, first);
+                continue;
+            }
+            seen.insert(name.clone(), record.line);
+
+            let Some(stored) = self.vendors.iter().find(|row| normalize(&row.name) == name) else {
+                let index = self.write.new_vendors.len();
+                self.write.new_vendors.push(write);
+                self.vendor_names.insert(name, ParentRef::New(index));
+                self.report.vendors.created += 1;
+                continue;
+            };
+            let merged = VendorWrite {
+                name: stored.name.clone(),
+                notes: write.notes.or_else(|| stored.notes.clone()),
+                website: write.website.or_else(|| stored.website.clone()),
+                menu: write.menu.or_else(|| stored.menu.clone()),
+            };
+            let unchanged = merged.name == stored.name
+                && merged.notes == stored.notes
+                && merged.website == stored.website
+                && merged.menu == stored.menu;
+            if unchanged {
+                self.report.vendors.unchanged += 1;
+            } else {
+                self.write.updated_vendors.push((stored.id, merged));
+                self.report.vendors.updated += 1;
+            }
+        }
+    }
+
+    /// Resolves each entry's brewery and vendor and rejects invalid or repeated entries.
+    fn add_schedules(&mut self, records: Vec<Record<ImportSchedule>>) -> Vec<PendingSchedule> {
+        let mut seen: HashMap<(ParentRef, ParentRef, jiff::Timestamp), usize> = HashMap::new();
+        let mut pending = Vec::new();
+        for record in records {
+            let line = record.line;
+            let schedule = record.value;
+            let mut errors = field_errors(validate_schedule(schedule.start_at, schedule.end_at));
+            let brewery = self.resolve_brewery(&schedule, &mut errors);
+            let vendor = self.vendor_names.get(&normalize(&schedule.vendor)).copied();
+            if vendor.is_none() {
+                errors.push(FieldError {
+                    field: "vendor",
+                    message: "does not match a vendor".to_string(),
+                });
+            }
+            let (Some(brewery), Some(vendor), true) = (brewery, vendor, errors.is_empty()) else {
+                self.reject(ImportKind::Schedules, line, errors);
+                continue;
+            };
+            if let Some(&first) = seen.get(&(brewery, vendor, schedule.start_at)) {
+                self.duplicate(ImportKind::Schedules, line, first);
+                continue;
+            }
+            seen.insert((brewery, vendor, schedule.start_at), line);
+
+            pending.push(PendingSchedule {
+                brewery,
+                vendor,
+                start: schedule.start_at,
+                end: schedule.end_at,
+                all_day: schedule.all_day,
+                status: schedule.status,
+            });
+        }
+        pending
+    }
+
+    fn resolve_brewery(
+        &self,
+        schedule: &ImportSchedule,
+        errors: &mut Vec<FieldError>,
+    ) -> Option<ParentRef> {
+        let candidates = self
+            .brewery_names
+            .get(&normalize(&schedule.brewery))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let matches: Vec<ParentRef> = match &schedule.brewery_address {
+            Some(address) => {
+                let address = normalize(address);
+                candidates
+                    .iter()
+                    .filter(|(stored, _)| *stored == address)
+                    .map(|(_, brewery)| *brewery)
+                    .collect()
+            }
+            None => candidates.iter().map(|(_, brewery)| *brewery).collect(),
+        };
+        match matches.as_slice() {
+            [brewery] => Some(*brewery),
+            [] => {
+                errors.push(FieldError {
+                    field: "brewery",
+                    message: "does not match a brewery".to_string(),
+                });
+                None
+            }
+            _ => {
+                errors.push(FieldError {
+                    field: "brewery_address",
+                    message: "is required when breweries share a name".to_string(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Updates stored entries that match by brewery, vendor and start, keeping their
+    /// source, and queues the rest as new entries.
+    fn match_schedules(
+        &mut self,
+        pending: Vec<PendingSchedule>,
+        stored: Vec<ScheduleRow>,
+    ) -> Result<(), DomainError> {
+        let mut stored_by_key = HashMap::new();
+        for row in stored {
+            let (start, end) = row_hours(&row)?;
+            stored_by_key
+                .entry((row.brewery_id, row.food_vendor_id, start))
+                .or_insert((row, end));
+        }
+
+        for schedule in pending {
+            let Some((row, stored_end)) = schedule
+                .stored_key()
+                .and_then(|key| stored_by_key.get(&key))
+            else {
+                self.write.new_schedules.push(ImportScheduleWrite {
+                    brewery: schedule.brewery,
+                    vendor: schedule.vendor,
+                    start: schedule.start,
+                    end: schedule.end,
+                    all_day: schedule.all_day.unwrap_or_default(),
+                    status: schedule.status.unwrap_or_default().as_str().to_string(),
+                    source: IMPORT_SOURCE.to_string(),
+                });
+                self.report.schedules.created += 1;
+                continue;
+            };
+            let write = ScheduleWrite {
+                brewery_id: row.brewery_id,
+                food_vendor_id: row.food_vendor_id,
+                start: schedule.start,
+                end: schedule.end.or(*stored_end),
+                all_day: schedule.all_day.unwrap_or(row.all_day),
+                status: schedule
+                    .status
+                    .map_or_else(|| row.status.clone(), |s| s.as_str().to_string()),
+                source: row.source.clone(),
+                source_kind: SourceKind::Manual.as_str().to_string(),
+            };
+            if write.end == *stored_end
+                && write.all_day == row.all_day
+                && write.status == row.status
+            {
+                self.report.schedules.unchanged += 1;
+            } else {
+                self.write.updated_schedules.push((row.id, write));
+                self.report.schedules.updated += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use std::sync::Mutex;
+
+    /// A new schedule entry from an applied import: brewery, vendor, status and source.
+    type AppliedSchedule = (ParentRef, ParentRef, String, String);
+
+    #[derive(Default)]
+    struct MockImportRepo {
+        breweries: Vec<BreweryRow>,
+        vendors: Vec<VendorRow>,
+        applied: Mutex<Option<Vec<AppliedSchedule>>>,
+    }
+
+    impl BreweryRepo for MockImportRepo {
+        async fn get_breweries(&self) -> Result<Vec<BreweryRow>, RepoError> {
+            Ok(self
+                .breweries
+                .iter()
+                .map(|row| brewery_row(row.id, &row.name, row.address.as_deref()))
+                .collect())
+        }
+
+        async fn get_brewery(&self, _id: Uuid) -> Result<Option<BreweryRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn create_brewery(&self, _brewery: &BreweryWrite) -> Result<BreweryRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn update_brewery(
+            &self,
+            _id: Uuid,
+            _brewery: &BreweryWrite,
+        ) -> Result<Option<BreweryRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn delete_brewery(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    impl VendorRepo for MockImportRepo {
+        async fn get_vendors(&self) -> Result<Vec<VendorRow>, RepoError> {
+            Ok(self
+                .vendors
+                .iter()
+                .map(|row| vendor_row(row.id, &row.name))
+                .collect())
+        }
+
+        async fn get_vendor(&self, _id: Uuid) -> Result<Option<VendorRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn create_vendor(&self, _vendor: &VendorWrite) -> Result<VendorRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn update_vendor(
+            &self,
+            _id: Uuid,
+            _vendor: &VendorWrite,
+        ) -> Result<Option<VendorRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn delete_vendor(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    impl ImportRepo for MockImportRepo {
+        async fn get_schedules_by_start(
+            &self,
+            _keys: &[(Uuid, Uuid, jiff::Timestamp)],
+        ) -> Result<Vec<ScheduleRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn apply_import(&self, import: &ImportWrite) -> Result<(), RepoError> {
+            let schedules = import
+                .new_schedules
+                .iter()
+                .map(|s| (s.brewery, s.vendor, s.status.clone(), s.source.clone()))
+                .collect();
+            *self.applied.lock().expect("lock poisoned") = Some(schedules);
+            Ok(())
+        }
+    }
+
+    fn brewery_row(id: Uuid, name: &str, address: Option<&str>) -> BreweryRow {
+        BreweryRow {
+            id,
+            name: name.to_string(),
+            notes: None,
+            website: None,
+            address: address.map(str::to_string),
+            lat: None,
+            lng: None,
+            drink_menu: None,
+            food_schedule: None,
+        }
+    }
+
+    fn vendor_row(id: Uuid, name: &str) -> VendorRow {
+        VendorRow {
+            id,
+            name: name.to_string(),
+            notes: None,
+            website: None,
+            menu: None,
+        }
+    }
+
+    fn repo() -> MockImportRepo {
+        MockImportRepo {
+            breweries: vec![brewery_row(
+                Uuid::from_u128(1),
+                "Stoup Brewing",
+                Some("1108 NW 52nd St"),
+            )],
+            vendors: vec![vendor_row(Uuid::from_u128(2), "Where Ya At Matt")],
+            ..MockImportRepo::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn import_dedupes_and_rejects_the_whole_file_on_any_error() {
+        let db = repo();
+        let csv = "name,website\n\
+                   where ya  at MATT,https://example.com\n\
+                   Nosh,\n\
+                   NOSH,\n\
+                   ,\n";
+
+        let report = import_records(&db, ImportFormat::Csv(ImportKind::Vendors), csv, false)
+            .await
+            .expect("import report");
+
+        assert_eq!(
+            report.vendors,
+            ImportCounts {
+                created: 1,
+                updated: 1,
+                unchanged: 0
+            }
+        );
+        let errors: Vec<_> = report
+            .errors
+            .iter()
+            .map(|e| (e.line, e.fields[0].message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![(4, "duplicates line 3"), (5, "must not be empty")]
+        );
+        assert!(!report.applied);
+        assert!(db.applied.lock().expect("lock poisoned").is_none());
+    }
+
+    #[tokio::test]
+    async fn import_links_schedules_to_breweries_created_in_the_same_file() {
+        let db = repo();
+        let json = r#"{
+            "breweries": [{ "name": "Lucky Envelope", "address": "907 NW 50th St" }],
+            "schedules": [
+                { "brewery": "lucky envelope", "vendor": "Where Ya At Matt",
+                  "start_at": "2026-02-04T17:00:00Z", "status": "tentative" },
+                { "brewery": "Stoup Brewing", "brewery_address": "1108 nw 52nd st",
+                  "vendor": "where ya at matt", "start_at": "2026-02-05T17:00:00Z" }
+            ]
+        }"#;
+
+        let report = import_records(&db, ImportFormat::Json, json, false)
+            .await
+            .expect("import report");
+
+        assert!(report.applied, "{:?}", report.errors);
+        let applied = db.applied.lock().expect("lock poisoned");
+        let refs: Vec<_> = applied
+            .as_ref()
+            .expect("import applied")
+            .iter()
+            .map(|(brewery, vendor, status, source)| {
+                (*brewery, *vendor, status.as_str(), source.as_str())
+            })
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                (
+                    ParentRef::New(0),
+                    ParentRef::Existing(Uuid::from_u128(2)),
+                    "tentative",
+                    "import"
+                ),
+                (
+                    ParentRef::Existing(Uuid::from_u128(1)),
+                    ParentRef::Existing(Uuid::from_u128(2)),
+                    "confirmed",
+                    "import"
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_without_writing() {
+        let db = repo();
+        let json = r#"{ "vendors": [{ "name": "Nosh" }] }"#;
+
+        let report = import_records(&db, ImportFormat::Json, json, true)
+            .await
+            .expect("import report");
+
+        assert!(report.dry_run);
+        assert!(!report.applied);
+        assert_eq!(report.vendors.created, 1);
+        assert!(db.applied.lock().expect("lock poisoned").is_none());
+    }
+}
@@ -0,0 +1,415 @@
+use crate::db::{ImageRow, ImageWrite, LinkRepo, LinkRow, LinkWrite, VendorOrBrewery};
+use crate::domain::DomainError;
+use crate::domain::validation::{Validator, is_web_url, web_url_host};
+use serde::{Deserialize, Serialize};
+
+/// Replaces the links of a vendor or brewery.
+pub async fn set_links<R: LinkRepo>(
+    db: &R,
+    owner: VendorOrBrewery,
+    input: SetLinks,
+) -> Result<Links, DomainError> {
+    let writes = input.into_writes()?;
+    let links = db
+        .set_links(owner, &writes)
+        .await?
+        .into_iter()
+        .map(Link::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Links { links })
+}
+
+/// Replaces the images of a vendor or brewery.
+pub async fn set_images<R: LinkRepo>(
+    db: &R,
+    owner: VendorOrBrewery,
+    input: SetImages,
+) -> Result<Images, DomainError> {
+    let writes = input.into_writes()?;
+    let images = db
+        .set_images(owner, &writes)
+        .await?
+        .into_iter()
+        .map(Image::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Images { images })
+}
+
+/// Loads the links and images shown on a vendor's or brewery's detail.
+pub(super) async fn load_profile<R: LinkRepo>(
+    db: &R,
+    owner: VendorOrBrewery,
+) -> Result<Profile, DomainError> {
+    let links = db
+        .get_links(owner)
+        .await?
+        .into_iter()
+        .map(Link::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    let images = db
+        .get_images(owner)
+        .await?
+        .into_iter()
+        .map(Image::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Profile { links, images })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Instagram,
+    Facebook,
+    /// A `tel:` URL.
+    Phone,
+    /// A `mailto:` URL.
+    Email,
+    /// Where to order ahead.
+    Ordering,
+}
+
+impl LinkKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LinkKind::Instagram => "instagram",
+            LinkKind::Facebook => "facebook",
+            LinkKind::Phone => "phone",
+            LinkKind::Email => "email",
+            LinkKind::Ordering => "ordering",
+        }
+    }
+
+    fn parse(value: &str) -> Option<LinkKind> {
+        match value {
+            "instagram" => Some(LinkKind::Instagram),
+            "facebook" => Some(LinkKind::Facebook),
+            "phone" => Some(LinkKind::Phone),
+            "email" => Some(LinkKind::Email),
+            "ordering" => Some(LinkKind::Ordering),
+            _ => None,
+        }
+    }
+
+    /// Describes what `url` should have been, or `None` when it is fine.
+    fn check(self, url: &str) -> Option<&'static str> {
+        let ok = match self {
+            LinkKind::Instagram => is_site(url, "instagram.com"),
+            LinkKind::Facebook => is_site(url, "facebook.com"),
+            LinkKind::Phone => url.strip_prefix("tel:").is_some_and(is_phone_number),
+            LinkKind::Email => url.strip_prefix("mailto:").is_some_and(is_email_address),
+            LinkKind::Ordering => is_web_url(url),
+        };
+        (!ok).then_some(match self {
+            LinkKind::Instagram => "instagram must be an instagram.com URL",
+            LinkKind::Facebook => "facebook must be a facebook.com URL",
+            LinkKind::Phone => "phone must be a tel: URL such as tel:+1-206-555-0100",
+            LinkKind::Email => "email must be a mailto: URL such as mailto:hello@example.com",
+            LinkKind::Ordering => "ordering must be an http or https URL",
+        })
+    }
+}
+
+fn is_site(url: &str, domain: &str) -> bool {
+    web_url_host(url).is_some_and(|host| {
+        host == domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|sub| sub.ends_with('.'))
+    })
+}
+
+/// An optional `+` and 7 to 15 digits, which may be separated by `-` or `.`.
+fn is_phone_number(value: &str) -> bool {
+    let digits = value.strip_prefix('+').unwrap_or(value);
+    let count = digits.chars().filter(char::is_ascii_digit).count();
+    (7..=15).contains(&count)
+        && digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == '.')
+}
+
+fn is_email_address(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !value.contains(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '?'))
+        && !domain.contains('@')
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageKind {
+    Logo,
+    /// A wide photo shown at the top of a detail page.
+    Hero,
+}
+
+impl ImageKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ImageKind::Logo => "logo",
+            ImageKind::Hero => "hero",
+        }
+    }
+
+    fn parse(value: &str) -> Option<ImageKind> {
+        match value {
+            "logo" => Some(ImageKind::Logo),
+            "hero" => Some(ImageKind::Hero),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Link {
+    kind: LinkKind,
+    url: String,
+}
+
+impl TryFrom<LinkRow> for Link {
+    type Error = DomainError;
+
+    fn try_from(value: LinkRow) -> Result<Self, Self::Error> {
+        let kind = LinkKind::parse(&value.kind).ok_or(DomainError::InvalidRow {
+            id: value.id,
+            reason: "unknown link kind",
+        })?;
+        Ok(Link {
+            kind,
+            url: value.url,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Image {
+    kind: ImageKind,
+    url: String,
+    /// Credit to show with the image, such as the photographer.
+    attribution: Option<String>,
+}
+
+impl TryFrom<ImageRow> for Image {
+    type Error = DomainError;
+
+    fn try_from(value: ImageRow) -> Result<Self, Self::Error> {
+        let kind = ImageKind::parse(&value.kind).ok_or(DomainError::InvalidRow {
+            id: value.id,
+            reason: "unknown image kind",
+        })?;
+        Ok(Image {
+            kind,
+            url: value.url,
+            attribution: value.attribution,
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct Links {
+    links: Vec<Link>,
+}
+
+#[derive(Serialize)]
+pub struct Images {
+    images: Vec<Image>,
+}
+
+/// Links and images, flattened into vendor and brewery details.
+#[derive(Serialize)]
+pub struct Profile {
+    links: Vec<Link>,
+    images: Vec<Image>,
+}
+
+/// Admin input replacing the links of a vendor or brewery.
+#[derive(Deserialize)]
+pub struct SetLinks {
+    links: Vec<LinkInput>,
+}
+
+#[derive(Deserialize)]
+struct LinkInput {
+    kind: LinkKind,
+    url: String,
+}
+
+impl SetLinks {
+    fn into_writes(self) -> Result<Vec<LinkWrite>, DomainError> {
+        let mut v = Validator::default();
+        let mut writes: Vec<LinkWrite> = Vec::new();
+        for link in self.links {
+            let kind = link.kind.as_str();
+            let url = link.url.trim().to_string();
+            if let Some(message) = link.kind.check(&url) {
+                v.check(false, "links", message);
+            }
+            if writes.iter().any(|write| write.kind == kind) {
+                v.check(
+                    false,
+                    "links",
+                    &format!("only one {} link is allowed", kind),
+                );
+            }
+            writes.push(LinkWrite {
+                kind: kind.to_string(),
+                url,
+            });
+        }
+        v.finish()?;
+        Ok(writes)
+    }
+}
+
+/// Admin input replacing the images of a vendor or brewery.
+#[derive(Deserialize)]
+pub struct SetImages {
+    images: Vec<ImageInput>,
+}
+
+#[derive(Deserialize)]
+struct ImageInput {
+    kind: ImageKind,
+    url: String,
+    attribution: Option<String>,
+}
+
+impl SetImages {
+    fn into_writes(self) -> Result<Vec<ImageWrite>, DomainError> {
+        let mut v = Validator::default();
+        let mut writes: Vec<ImageWrite> = Vec::new();
+        for image in self.images {
+            let kind = image.kind.as_str();
+            let url = image.url.trim().to_string();
+            v.check(
+                is_web_url(&url),
+                "images",
+                &format!("{} must be an http or https URL", kind),
+            );
+            if writes.iter().any(|write| write.kind == kind) {
+                v.check(
+                    false,
+                    "images",
+                    &format!("only one {} image is allowed", kind),
+                );
+            }
+            writes.push(ImageWrite {
+                kind: kind.to_string(),
+                url,
+                attribution: image
+                    .attribution
+                    .map(|text| text.trim().to_string())
+                    .filter(|text| !text.is_empty()),
+            });
+        }
+        v.finish()?;
+        Ok(writes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(result: Result<Vec<LinkWrite>, DomainError>) -> Vec<String> {
+        match result {
+            Ok(_) => Vec::new(),
+            Err(DomainError::Validation(errors)) => errors.into_iter().map(|e| e.message).collect(),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    fn set_links(links: &[(LinkKind, &str)]) -> SetLinks {
+        SetLinks {
+            links: links
+                .iter()
+                .map(|&(kind, url)| LinkInput {
+                    kind,
+                    url: url.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn links_must_be_urls_of_their_kind() {
+        let valid = set_links(&[
+            (
+                LinkKind::Instagram,
+                "https://www.instagram.com/stoupbrewing/",
+            ),
+            (LinkKind::Facebook, "https://facebook.com/stoupbrewing"),
+            (LinkKind::Phone, " tel:+1-206-555-0100 "),
+            (LinkKind::Email, "mailto:hello@example.com"),
+            (LinkKind::Ordering, "https://order.example.com/stoup"),
+        ]);
+        let writes = valid.into_writes().expect("valid links");
+        assert_eq!(writes[2].url, "tel:+1-206-555-0100");
+
+        let invalid = set_links(&[
+            (LinkKind::Instagram, "https://notinstagram.com/stoup"),
+            (LinkKind::Phone, "206-555-0100"),
+            (LinkKind::Email, "mailto:hello"),
+            (LinkKind::Ordering, "order.example.com"),
+        ]);
+        assert_eq!(
+            messages(invalid.into_writes()),
+            [
+                "instagram must be an instagram.com URL",
+                "phone must be a tel: URL such as tel:+1-206-555-0100",
+                "email must be a mailto: URL such as mailto:hello@example.com",
+                "ordering must be an http or https URL",
+            ]
+        );
+    }
+
+    #[test]
+    fn links_allow_one_of_each_kind() {
+        let input = set_links(&[
+            (LinkKind::Phone, "tel:2065550100"),
+            (LinkKind::Phone, "tel:2065550101"),
+        ]);
+        assert_eq!(
+            messages(input.into_writes()),
+            ["only one phone link is allowed"]
+        );
+    }
+
+    #[test]
+    fn images_need_web_urls_and_drop_blank_attributions() {
+        let input = SetImages {
+            images: vec![
+                ImageInput {
+                    kind: ImageKind::Logo,
+                    url: "https://example.com/logo.png".to_string(),
+                    attribution: Some("  ".to_string()),
+                },
+                ImageInput {
+                    kind: ImageKind::Hero,
+                    url: "/hero.jpg".to_string(),
+                    attribution: Some("Photo: Jane Doe".to_string()),
+                },
+            ],
+        };
+        let Err(DomainError::Validation(errors)) = input.into_writes() else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].message, "hero must be an http or https URL");
+
+        let input = SetImages {
+            images: vec![ImageInput {
+                kind: ImageKind::Logo,
+                url: "https://example.com/logo.png".to_string(),
+                attribution: Some("  ".to_string()),
+            }],
+        };
+        let writes = input.into_writes().expect("valid image");
+        assert_eq!(writes[0].attribution, None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::db::{MenuItemRow, MenuItemWrite, MenuRepo, MenuVersionRow, VendorOrBrewery};
+use crate::domain::DomainError;
+use crate::domain::validation::Validator;
+use serde::Serialize;
+
+/// Returns one version of a vendor's menu or brewery's taplist, the latest by default.
+pub async fn get_menu<R: MenuRepo>(
+    db: &R,
+    owner: VendorOrBrewery,
+    version: Option<i32>,
+) -> Result<Menu, DomainError> {
+    let (menu, items) = db
+        .get_menu(owner, version)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    Ok(Menu::new(menu, items))
+}
+
+/// Stores parsed items as a new version unless they match the latest one.
+pub async fn record_menu<R: MenuRepo>(
+    db: &R,
+    owner: VendorOrBrewery,
+    source_url: Option<&str>,
+    items: Vec<MenuItem>,
+) -> Result<MenuIngest, DomainError> {
+    let mut v = Validator::default();
+    v.check(!items.is_empty(), "items", "none were parsed");
+    v.check(
+        items.iter().all(|item| !item.name.trim().is_empty()),
+        "items",
+        "every item needs a name",
+    );
+    v.finish()?;
+
+    if let Some((latest, latest_items)) = db.get_menu(owner, None).await?
+        && latest_items
+            .into_iter()
+            .map(MenuItem::from)
+            .eq(items.iter().cloned())
+    {
+        return Ok(MenuIngest {
+            version: latest.version,
+            items: items.len(),
+            changed: false,
+        });
+    }
+
+    let writes: Vec<MenuItemWrite> = items.into_iter().map(MenuItem::into_write).collect();
+    let menu = db.insert_menu_version(owner, source_url, &writes).await?;
+    Ok(MenuIngest {
+        version: menu.version,
+        items: writes.len(),
+        changed: true,
+    })
+}
+
+/// One dish or drink, as parsed from a menu and as served.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MenuItem {
+    pub section: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price_cents: Option<i32>,
+    /// Dietary tag slugs such as `vegan`.
+    pub dietary: Vec<String>,
+}
+
+impl MenuItem {
+    fn into_write(self) -> MenuItemWrite {
+        MenuItemWrite {
+            section: self.section,
+            name: self.name,
+            description: self.description,
+            price_cents: self.price_cents,
+            dietary: self.dietary,
+        }
+    }
+}
+
+impl From<MenuItemRow> for MenuItem {
+    fn from(value: MenuItemRow) -> Self {
+        MenuItem {
+            section: value.section,
+            name: value.name,
+            description: value.description,
+            price_cents: value.price_cents,
+            dietary: value.dietary,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Menu {
+    version: i32,
+    source_url: Option<String>,
+    fetched_at: String,
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    fn new(menu: MenuVersionRow, items: Vec<MenuItemRow>) -> Self {
+        Menu {
+            version: menu.version,
+            source_url: menu.source_url,
+            fetched_at: menu.fetched_at.to_rfc3339(),
+            items: items.into_iter().map(MenuItem::from).collect(),
+        }
+    }
+}
+
+/// What one menu ingest stored.
+#[derive(Debug, Serialize)]
+pub struct MenuIngest {
+    /// The new version, or the latest one when nothing changed.
+    version: i32,
+    items: usize,
+    changed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use sqlx::types::chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Keeps every stored version in memory.
+    #[derive(Default)]
+    struct MockMenuRepo {
+        versions: Mutex<Vec<Vec<MenuItem>>>,
+    }
+
+    impl MenuRepo for MockMenuRepo {
+        async fn get_menu(
+            &self,
+            _owner: VendorOrBrewery,
+            version: Option<i32>,
+        ) -> Result<Option<(MenuVersionRow, Vec<MenuItemRow>)>, RepoError> {
+            let versions = self.versions.lock().expect("lock poisoned");
+            let number = version.unwrap_or(versions.len() as i32);
+            let Some(items) = number
+                .checked_sub(1)
+                .and_then(|i| versions.get(usize::try_from(i).ok()?))
+            else {
+                return Ok(None);
+            };
+            Ok(Some((
+                version_row(number),
+                items
+                    .iter()
+                    .map(|item| MenuItemRow {
+                        section: item.section.clone(),
+                        name: item.name.clone(),
+                        description: item.description.clone(),
+                        price_cents: item.price_cents,
+                        dietary: item.dietary.clone(),
+                    })
+                    .collect(),
+            )))
+        }
+
+        async fn insert_menu_version(
+            &self,
+            _owner: VendorOrBrewery,
+            _source_url: Option<&str>,
+            items: &[MenuItemWrite],
+        ) -> Result<MenuVersionRow, RepoError> {
+            let mut versions = self.versions.lock().expect("lock poisoned");
+            versions.push(
+                items
+                    .iter()
+                    .map(|item| MenuItem {
+                        section: item.section.clone(),
+                        name: item.name.clone(),
+                        description: item.description.clone(),
+                        price_cents: item.price_cents,
+                        dietary: item.dietary.clone(),
+                    })
+                    .collect(),
+            );
+            Ok(version_row(versions.len() as i32))
+        }
+    }
+
+    fn version_row(version: i32) -> MenuVersionRow {
+        MenuVersionRow {
+            id: Uuid::new_v4(),
+            version,
+            source_url: None,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    fn item(name: &str, price_cents: Option<i32>) -> MenuItem {
+        MenuItem {
+            section: Some("Tacos".to_string()),
+            name: name.to_string(),
+            description: None,
+            price_cents,
+            dietary: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn record_menu_adds_a_version_only_when_items_change() {
+        let repo = MockMenuRepo::default();
+        let owner = VendorOrBrewery::Vendor(Uuid::nil());
+        let menu = vec![item("Al pastor", Some(450)), item("Nopales", Some(400))];
+
+        let first = record_menu(&repo, owner, None, menu.clone()).await.unwrap();
+        assert_eq!((first.version, first.changed), (1, true));
+
+        let same = record_menu(&repo, owner, None, menu.clone()).await.unwrap();
+        assert_eq!((same.version, same.changed), (1, false));
+
+        let mut repriced = menu;
+        repriced[1].price_cents = Some(425);
+        let second = record_menu(&repo, owner, None, repriced).await.unwrap();
+        assert_eq!((second.version, second.changed), (2, true));
+
+        let old = get_menu(&repo, owner, Some(1)).await.unwrap();
+        assert_eq!(old.items[1].price_cents, Some(400));
+        assert!(matches!(
+            get_menu(&repo, owner, Some(3)).await,
+            Err(DomainError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn record_menu_rejects_empty_menus() {
+        let repo = MockMenuRepo::default();
+        let result = record_menu(
+            &repo,
+            VendorOrBrewery::Vendor(Uuid::nil()),
+            None,
+            Vec::new(),
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+}
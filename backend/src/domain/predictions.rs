@@ -0,0 +1,361 @@
+use crate::db::{StatsRepo, TagFilter, WeekdayPatternRow};
+use crate::domain::DomainError;
+use crate::domain::brewery_hours::{BusinessHours, OpenAt, local_instant};
+use crate::domain::recurring::to_jiff_time;
+use crate::domain::schedules::{Provenance, local_days, to_chrono};
+use jiff::civil::{Date, Time, Weekday};
+use jiff::tz::TimeZone;
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Weeks of history that predictions are based on.
+const LOOKBACK_WEEKS: i64 = 12;
+
+/// Predictions less likely than this are left out.
+const MIN_CONFIDENCE: f64 = 0.25;
+
+/// Guesses which vendors appear at each brewery on the local days in `[start, end)` past
+/// the brewery's horizon, from the weekdays they appeared there in the
+/// `LOOKBACK_WEEKS` before `start`, or before now for windows starting later, since
+/// nothing after now has happened yet. Breweries without a horizon are never predicted.
+pub(super) async fn predict_entries<R: StatsRepo>(
+    db: &R,
+    start: jiff::Timestamp,
+    end: jiff::Timestamp,
+    horizons: &HashMap<Uuid, jiff::Timestamp>,
+    hours: &HashMap<Uuid, BusinessHours>,
+    tags: &TagFilter,
+    tz: &TimeZone,
+) -> Result<Vec<PredictedEntry>, DomainError> {
+    if horizons.values().all(|horizon| *horizon >= end) {
+        return Ok(Vec::new());
+    }
+    let until = start.min(jiff::Timestamp::now());
+    let since = until - jiff::SignedDuration::from_hours(LOOKBACK_WEEKS * 7 * 24);
+    let rows = db
+        .get_weekday_patterns(since, until, tz.iana_name().unwrap_or("UTC"), tags)
+        .await?;
+    let days = local_days(start, end, tz);
+    let mut predicted = Vec::new();
+    for row in rows {
+        let Some(&horizon) = horizons.get(&row.brewery_id) else {
+            continue;
+        };
+        let pattern = Pattern::try_from(row)?;
+        if pattern.confidence < MIN_CONFIDENCE {
+            continue;
+        }
+        let hours = hours.get(&pattern.row.brewery_id);
+        for &day in days.iter().filter(|day| day.weekday() == pattern.weekday) {
+            if let Some(entry) = pattern.on(day, hours, tz)
+                && entry.start >= horizon.max(start)
+                && entry.start < end
+            {
+                predicted.push(entry);
+            }
+        }
+    }
+    predicted.sort_by(|a, b| {
+        a.start
+            .cmp(&b.start)
+            .then(b.source.confidence.total_cmp(&a.source.confidence))
+            .then_with(|| a.vendor_name.cmp(&b.vendor_name))
+    });
+    Ok(predicted)
+}
+
+/// A vendor's habit of appearing at a brewery on one weekday.
+struct Pattern {
+    row: WeekdayPatternRow,
+    weekday: Weekday,
+    confidence: f64,
+}
+
+impl TryFrom<WeekdayPatternRow> for Pattern {
+    type Error = DomainError;
+
+    fn try_from(row: WeekdayPatternRow) -> Result<Self, Self::Error> {
+        let weekday = i8::try_from(row.weekday)
+            .ok()
+            .and_then(|n| Weekday::from_monday_one_offset(n).ok())
+            .ok_or(DomainError::InvalidRow {
+                id: row.brewery_id,
+                reason: "pattern weekday is out of range",
+            })?;
+        // The extra day keeps a single sighting from reading as a certainty.
+        let confidence = row.appearances as f64 / (row.hosted_days + 1) as f64;
+        Ok(Pattern {
+            row,
+            weekday,
+            confidence: (confidence * 100.0).round() / 100.0,
+        })
+    }
+}
+
+impl Pattern {
+    /// The predicted entry on the local `day`, or `None` when the brewery is closed then.
+    fn on(
+        &self,
+        day: Date,
+        hours: Option<&BusinessHours>,
+        tz: &TimeZone,
+    ) -> Option<PredictedEntry> {
+        let next_day = day.tomorrow().expect("dates are within jiff's range");
+        if hours.is_some_and(|hours| hours.closed_day(day).is_some()) {
+            return None;
+        }
+        let (start, end, all_day) = match self.row.usual_start.map(to_jiff_time) {
+            Some(start_time) => {
+                let start = local_instant(day, start_time, tz);
+                if hours.is_some_and(|hours| hours.open_at(start, tz) == OpenAt::Closed) {
+                    return None;
+                }
+                let end = self.row.usual_end.map(to_jiff_time).map(|end_time| {
+                    let end = local_instant(day, end_time, tz);
+                    if end > start {
+                        end
+                    } else {
+                        local_instant(next_day, end_time, tz)
+                    }
+                });
+                (start, end, false)
+            }
+            None => (
+                local_instant(day, Time::midnight(), tz),
+                Some(local_instant(next_day, Time::midnight(), tz)),
+                true,
+            ),
+        };
+        // Like scraped "until close" entries, end when the brewery closes.
+        let closes = match hours.map(|hours| hours.open_at(start, tz)) {
+            Some(OpenAt::Open { until }) if end.is_none() => Some(until),
+            _ => end,
+        };
+        Some(PredictedEntry {
+            brewery_name: self.row.brewery_name.clone(),
+            brewery_id: self.row.brewery_id,
+            vendor_name: self.row.food_vendor_name.clone(),
+            vendor_id: self.row.food_vendor_id,
+            start_at: to_chrono(start).to_rfc3339(),
+            end_at: closes.map(|ts| to_chrono(ts).to_rfc3339()),
+            all_day,
+            until_close: end.is_none(),
+            source: Provenance::prediction(self.confidence),
+            appearances: self.row.appearances,
+            hosted_days: self.row.hosted_days,
+            start,
+        })
+    }
+}
+
+/// A guess that a vendor appears at a brewery, past the brewery's scraped horizon.
+#[derive(Serialize)]
+pub struct PredictedEntry {
+    brewery_name: String,
+    brewery_id: Uuid,
+    vendor_name: String,
+    vendor_id: Uuid,
+    start_at: String,
+    end_at: Option<String>,
+    all_day: bool,
+    until_close: bool,
+    /// Kind `prediction`, with the estimated chance the vendor appears as confidence.
+    source: Provenance,
+    /// Days on this weekday the vendor appeared at the brewery over the lookback.
+    appearances: i64,
+    /// Days on this weekday the brewery hosted any vendor over the lookback.
+    hosted_days: i64,
+    #[serde(skip)]
+    start: jiff::Timestamp,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{AppearanceStatsRow, RepoError, StatsSubject};
+    use sqlx::types::chrono::NaiveTime;
+    use std::sync::Mutex;
+
+    /// Wednesday patterns at one brewery as vendor, appearances, hosted days, usual start
+    /// and usual end.
+    type Patterns = Vec<(u32, i64, i64, Option<&'static str>, Option<&'static str>)>;
+
+    #[derive(Default)]
+    struct MockPatternRepo {
+        rows: Patterns,
+        /// `since` and `until` of the last pattern lookup.
+        looked_back: Mutex<Option<(jiff::Timestamp, jiff::Timestamp)>>,
+    }
+
+    impl StatsRepo for MockPatternRepo {
+        async fn get_appearance_stats(
+            &self,
+            _subject: StatsSubject,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+        ) -> Result<Vec<AppearanceStatsRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_weekday_patterns(
+            &self,
+            since: jiff::Timestamp,
+            until: jiff::Timestamp,
+            _tz: &str,
+            _tags: &TagFilter,
+        ) -> Result<Vec<WeekdayPatternRow>, RepoError> {
+            *self.looked_back.lock().expect("lock poisoned") = Some((since, until));
+            Ok(self
+                .rows
+                .iter()
+                .map(
+                    |&(vendor, appearances, hosted_days, usual_start, usual_end)| {
+                        WeekdayPatternRow {
+                            brewery_id: Uuid::nil(),
+                            brewery_name: "Stoup Brewing".to_string(),
+                            food_vendor_id: Uuid::from_u128(vendor as u128),
+                            food_vendor_name: format!("Vendor {}", vendor),
+                            // Wednesday.
+                            weekday: 3,
+                            appearances,
+                            hosted_days,
+                            usual_start: usual_start.map(|time| time.parse::<NaiveTime>().unwrap()),
+                            usual_end: usual_end.map(|time| time.parse::<NaiveTime>().unwrap()),
+                        }
+                    },
+                )
+                .collect())
+        }
+    }
+
+    fn ts(value: &str) -> jiff::Timestamp {
+        value.parse().unwrap()
+    }
+
+    /// 2026-02-02 (Monday) through 2026-02-15, in UTC.
+    fn two_weeks() -> (jiff::Timestamp, jiff::Timestamp) {
+        (ts("2026-02-02T00:00:00Z"), ts("2026-02-16T00:00:00Z"))
+    }
+
+    #[tokio::test]
+    async fn predictions_follow_weekdays_past_the_horizon() {
+        let repo = MockPatternRepo {
+            rows: vec![
+                (1, 11, 11, Some("17:00:00"), Some("01:00:00")),
+                (2, 2, 11, Some("16:00:00"), None),
+            ],
+            ..Default::default()
+        };
+        let (start, end) = two_weeks();
+        let horizons = HashMap::from([(Uuid::nil(), ts("2026-02-05T00:00:00Z"))]);
+
+        let predicted = predict_entries(
+            &repo,
+            start,
+            end,
+            &horizons,
+            &HashMap::new(),
+            &TagFilter::default(),
+            &TimeZone::UTC,
+        )
+        .await
+        .unwrap();
+
+        // The first Wednesday is before the horizon and vendor 2 is below the cutoff.
+        assert_eq!(predicted.len(), 1);
+        let entry = &predicted[0];
+        assert_eq!(entry.vendor_name, "Vendor 1");
+        assert_eq!(entry.start_at, "2026-02-11T17:00:00+00:00");
+        assert_eq!(entry.end_at.as_deref(), Some("2026-02-12T01:00:00+00:00"));
+        assert_eq!(entry.source.confidence, 0.92);
+    }
+
+    #[tokio::test]
+    async fn predictions_skip_closed_days_and_end_at_close() {
+        let repo = MockPatternRepo {
+            rows: vec![(1, 6, 8, None, None), (2, 8, 8, Some("17:00:00"), None)],
+            ..Default::default()
+        };
+        let (start, end) = two_weeks();
+        let horizons = HashMap::from([(Uuid::nil(), start)]);
+        let mut stoup = BusinessHours::daily("12:00:00", "22:00:00");
+        stoup.close_on("2026-02-04".parse().unwrap(), "Private event");
+        let hours = HashMap::from([(Uuid::nil(), stoup)]);
+
+        let predicted = predict_entries(
+            &repo,
+            start,
+            end,
+            &horizons,
+            &hours,
+            &TagFilter::default(),
+            &TimeZone::UTC,
+        )
+        .await
+        .unwrap();
+
+        let summary: Vec<_> = predicted
+            .iter()
+            .map(|entry| {
+                (
+                    entry.vendor_name.as_str(),
+                    entry.start_at.as_str(),
+                    entry.end_at.as_deref(),
+                    entry.all_day,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "Vendor 1",
+                    "2026-02-11T00:00:00+00:00",
+                    Some("2026-02-12T00:00:00+00:00"),
+                    true
+                ),
+                (
+                    "Vendor 2",
+                    "2026-02-11T17:00:00+00:00",
+                    Some("2026-02-11T22:00:00+00:00"),
+                    false
+                ),
+            ]
+        );
+        assert!(predicted[1].until_close);
+    }
+
+    #[tokio::test]
+    async fn predictions_look_back_from_now_for_future_windows() {
+        let repo = MockPatternRepo::default();
+        let (start, end) = (ts("2099-02-02T00:00:00Z"), ts("2099-02-16T00:00:00Z"));
+        let horizons = HashMap::from([(Uuid::nil(), start)]);
+        let before = jiff::Timestamp::now();
+
+        predict_entries(
+            &repo,
+            start,
+            end,
+            &horizons,
+            &HashMap::new(),
+            &TagFilter::default(),
+            &TimeZone::UTC,
+        )
+        .await
+        .unwrap();
+
+        let (since, until) = repo
+            .looked_back
+            .lock()
+            .expect("lock poisoned")
+            .take()
+            .expect("patterns looked up");
+        assert!(before <= until && until <= jiff::Timestamp::now());
+        assert_eq!(
+            until.duration_since(since),
+            jiff::SignedDuration::from_hours(12 * 7 * 24)
+        );
+    }
+}
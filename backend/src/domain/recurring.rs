@@ -0,0 +1,676 @@
+use crate::db::{
+    MaterializeCounts, RecurringEntryWrite, RecurringScheduleRepo, RecurringScheduleRow,
+    RecurringScheduleWrite,
+};
+use crate::domain::DomainError;
+use crate::domain::schedules::schedule_write_error;
+use crate::domain::validation::Validator;
+use jiff::ToSpan;
+use jiff::civil::{Date, Time, Weekday};
+use jiff::tz::TimeZone;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{NaiveDate, NaiveTime};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub async fn get_recurring_schedules<R: RecurringScheduleRepo>(
+    db: &R,
+) -> Result<RecurringSchedules, DomainError> {
+    let rows = db.get_recurring_schedules().await?;
+    RecurringSchedules::try_from(rows)
+}
+
+/// Stores a recurring schedule and re-materializes the horizon so its slots show up
+/// right away.
+pub async fn create_recurring_schedule<R: RecurringScheduleRepo>(
+    db: &R,
+    input: CreateRecurringSchedule,
+    horizon: &RecurringHorizon,
+) -> Result<RecurringSchedule, DomainError> {
+    let write = input.into_write()?;
+    let row = db
+        .create_recurring_schedule(&write)
+        .await
+        .map_err(schedule_write_error)?;
+    materialize_recurring(db, horizon).await?;
+    RecurringSchedule::try_from(row)
+}
+
+/// Deletes a recurring schedule and the entries it materialized in the horizon.
+pub async fn delete_recurring_schedule<R: RecurringScheduleRepo>(
+    db: &R,
+    id: Uuid,
+    horizon: &RecurringHorizon,
+) -> Result<(), DomainError> {
+    if !db.delete_recurring_schedule(id).await? {
+        return Err(DomainError::NotFound);
+    }
+    materialize_recurring(db, horizon).await?;
+    Ok(())
+}
+
+/// Expands every recurring schedule over the horizon and reconciles the `recurring`
+/// entries with the result. Occurrences already listed by another source for the same
+/// brewery and vendor are left out, so a scraped slot is never shown twice.
+pub async fn materialize_recurring<R: RecurringScheduleRepo>(
+    db: &R,
+    horizon: &RecurringHorizon,
+) -> Result<Materialized, DomainError> {
+    let mut entries = Vec::new();
+    for row in db.get_recurring_schedules().await? {
+        match RecurringRule::try_from(&row) {
+            Ok(rule) => entries.extend(rule.occurrences(horizon)),
+            Err(e) => warn!("Skipping recurring schedule {}: {}", row.id, e),
+        }
+    }
+
+    let (start, end) = horizon.bounds();
+    let tz = horizon.tz.iana_name().unwrap_or("UTC");
+    let counts = db.materialize_recurring(start, end, tz, &entries).await?;
+    Ok(Materialized::new(start, end, counts))
+}
+
+/// Local days whose occurrences are materialized: `days` days from the one containing
+/// `now` in `tz`.
+pub struct RecurringHorizon {
+    first_day: Date,
+    days: i64,
+    tz: TimeZone,
+}
+
+impl RecurringHorizon {
+    pub fn new(now: jiff::Timestamp, tz: &TimeZone, days: u64) -> Self {
+        RecurringHorizon {
+            first_day: now.to_zoned(tz.clone()).date(),
+            days: days as i64,
+            tz: tz.clone(),
+        }
+    }
+
+    fn day(&self, offset: i64) -> Date {
+        self.first_day
+            .checked_add(offset.days())
+            .expect("horizon stays within jiff's date range")
+    }
+
+    /// Start of the first day and of the day after the last.
+    fn bounds(&self) -> (jiff::Timestamp, jiff::Timestamp) {
+        (
+            self.at(self.first_day, Time::midnight()),
+            self.at(self.day(self.days), Time::midnight()),
+        )
+    }
+
+    fn at(&self, date: Date, time: Time) -> jiff::Timestamp {
+        date.to_datetime(time)
+            .to_zoned(self.tz.clone())
+            .expect("local times resolve in the configured zone")
+            .timestamp()
+    }
+}
+
+/// The supported subset of an RFC 5545 RRULE: `FREQ=DAILY` or `FREQ=WEEKLY`, with an
+/// optional `INTERVAL` and a plain `BYDAY` list.
+#[derive(Debug, PartialEq)]
+struct Recurrence {
+    frequency: Frequency,
+    interval: i64,
+    by_day: Vec<Weekday>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let rule = s.trim();
+        let rule = match rule.split_once(':') {
+            Some((prefix, rest)) if prefix.eq_ignore_ascii_case("RRULE") => rest,
+            _ => rule,
+        };
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' is not NAME=VALUE", part))?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        _ => return Err(format!("FREQ={} is not supported", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| format!("INTERVAL={} is not a positive number", value))?
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_weekday(day)?);
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "{} is not supported; use effective_until and exceptions instead",
+                        name
+                    ));
+                }
+            }
+        }
+        let frequency = frequency.ok_or("FREQ is required")?;
+        Ok(Recurrence {
+            frequency,
+            interval,
+            by_day,
+        })
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.frequency {
+            Frequency::Daily => f.write_str("FREQ=DAILY")?,
+            Frequency::Weekly => f.write_str("FREQ=WEEKLY")?,
+        }
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<_> = self.by_day.iter().map(|day| weekday_code(*day)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl Recurrence {
+    /// Whether the rule, counted from `anchor`, lands on `date`. Weeks start on Monday.
+    fn occurs_on(&self, anchor: Date, date: Date) -> bool {
+        if date < anchor {
+            return false;
+        }
+        match self.frequency {
+            Frequency::Daily => {
+                (self.by_day.is_empty() || self.by_day.contains(&date.weekday()))
+                    && days_between(anchor, date) % self.interval == 0
+            }
+            Frequency::Weekly => {
+                let matches_day = if self.by_day.is_empty() {
+                    date.weekday() == anchor.weekday()
+                } else {
+                    self.by_day.contains(&date.weekday())
+                };
+                let weeks = days_between(week_start(anchor), week_start(date)) / 7;
+                matches_day && weeks % self.interval == 0
+            }
+        }
+    }
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, String> {
+    match code.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Monday),
+        "TU" => Ok(Weekday::Tuesday),
+        "WE" => Ok(Weekday::Wednesday),
+        "TH" => Ok(Weekday::Thursday),
+        "FR" => Ok(Weekday::Friday),
+        "SA" => Ok(Weekday::Saturday),
+        "SU" => Ok(Weekday::Sunday),
+        _ => Err(format!("BYDAY value '{}' is not supported", code)),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+fn days_between(from: Date, to: Date) -> i64 {
+    from.until(to)
+        .expect("dates are within jiff's range")
+        .get_days()
+        .into()
+}
+
+fn week_start(date: Date) -> Date {
+    date.checked_sub(i64::from(date.weekday().to_monday_zero_offset()).days())
+        .expect("dates are within jiff's range")
+}
+
+/// A stored recurring schedule with its rule parsed.
+struct RecurringRule {
+    brewery_id: Uuid,
+    food_vendor_id: Uuid,
+    recurrence: Recurrence,
+    start_time: Time,
+    end_time: Option<Time>,
+    effective_from: Date,
+    effective_until: Option<Date>,
+    exceptions: Vec<Date>,
+}
+
+impl TryFrom<&RecurringScheduleRow> for RecurringRule {
+    type Error = String;
+
+    fn try_from(row: &RecurringScheduleRow) -> Result<Self, Self::Error> {
+        Ok(RecurringRule {
+            brewery_id: row.brewery_id,
+            food_vendor_id: row.food_vendor_id,
+            recurrence: row.rrule.parse()?,
+            start_time: to_jiff_time(row.start_time),
+            end_time: row.end_time.map(to_jiff_time),
+            effective_from: to_jiff_date(row.effective_from),
+            effective_until: row.effective_until.map(to_jiff_date),
+            exceptions: row.exceptions.iter().copied().map(to_jiff_date).collect(),
+        })
+    }
+}
+
+impl RecurringRule {
+    fn occurrences(&self, horizon: &RecurringHorizon) -> Vec<RecurringEntryWrite> {
+        (0..horizon.days)
+            .map(|offset| horizon.day(offset))
+            .filter(|date| {
+                self.effective_until.is_none_or(|until| *date <= until)
+                    && !self.exceptions.contains(date)
+                    && self.recurrence.occurs_on(self.effective_from, *date)
+            })
+            .map(|date| {
+                let end = self.end_time.map(|end_time| {
+                    // Slots running past midnight end on the next day.
+                    let end_date = if end_time < self.start_time {
+                        date.tomorrow().expect("dates are within jiff's range")
+                    } else {
+                        date
+                    };
+                    horizon.at(end_date, end_time)
+                });
+                RecurringEntryWrite {
+                    brewery_id: self.brewery_id,
+                    food_vendor_id: self.food_vendor_id,
+                    start: horizon.at(date, self.start_time),
+                    end,
+                }
+            })
+            .collect()
+    }
+}
+
+pub(super) fn to_jiff_date(date: NaiveDate) -> Date {
+    date.to_string()
+        .parse()
+        .expect("chrono dates print as ISO 8601")
+}
+
+pub(super) fn to_jiff_time(time: NaiveTime) -> Time {
+    time.to_string()
+        .parse()
+        .expect("chrono times print as ISO 8601")
+}
+
+/// Admin input for a new recurring schedule. Dates and times are local to `LOCAL_TZ`.
+#[derive(Deserialize)]
+pub struct CreateRecurringSchedule {
+    brewery_id: Uuid,
+    vendor_id: Uuid,
+    rrule: String,
+    start_time: Time,
+    /// Omit for "until close".
+    end_time: Option<Time>,
+    effective_from: Date,
+    effective_until: Option<Date>,
+    #[serde(default)]
+    exceptions: Vec<Date>,
+}
+
+impl CreateRecurringSchedule {
+    fn into_write(self) -> Result<RecurringScheduleWrite, DomainError> {
+        let mut v = Validator::default();
+        let rrule = match self.rrule.parse::<Recurrence>() {
+            Ok(recurrence) => recurrence.to_string(),
+            Err(message) => {
+                v.check(false, "rrule", &message);
+                String::new()
+            }
+        };
+        if let Some(until) = self.effective_until {
+            v.check(
+                until >= self.effective_from,
+                "effective_until",
+                "must not be before effective_from",
+            );
+        }
+        v.check(
+            self.end_time != Some(self.start_time),
+            "end_time",
+            "must differ from start_time",
+        );
+        v.finish()?;
+
+        Ok(RecurringScheduleWrite {
+            brewery_id: self.brewery_id,
+            food_vendor_id: self.vendor_id,
+            rrule,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            effective_from: self.effective_from,
+            effective_until: self.effective_until,
+            exceptions: self.exceptions,
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct RecurringSchedule {
+    id: Uuid,
+    brewery_id: Uuid,
+    vendor_id: Uuid,
+    rrule: String,
+    start_time: String,
+    end_time: Option<String>,
+    effective_from: String,
+    effective_until: Option<String>,
+    exceptions: Vec<String>,
+    created_at: String,
+}
+
+impl TryFrom<RecurringScheduleRow> for RecurringSchedule {
+    type Error = DomainError;
+
+    fn try_from(value: RecurringScheduleRow) -> Result<Self, Self::Error> {
+        if value.rrule.parse::<Recurrence>().is_err() {
+            return Err(DomainError::InvalidRow {
+                id: value.id,
+                reason: "recurring schedule rrule is not supported",
+            });
+        }
+
+        Ok(RecurringSchedule {
+            id: value.id,
+            brewery_id: value.brewery_id,
+            vendor_id: value.food_vendor_id,
+            rrule: value.rrule,
+            start_time: to_jiff_time(value.start_time).to_string(),
+            end_time: value.end_time.map(|time| to_jiff_time(time).to_string()),
+            effective_from: value.effective_from.to_string(),
+            effective_until: value.effective_until.map(|date| date.to_string()),
+            exceptions: value
+                .exceptions
+                .iter()
+                .map(|date| date.to_string())
+                .collect(),
+            created_at: value.created_at.to_rfc3339(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct RecurringSchedules {
+    recurring_schedules: Vec<RecurringSchedule>,
+}
+
+impl TryFrom<Vec<RecurringScheduleRow>> for RecurringSchedules {
+    type Error = DomainError;
+
+    fn try_from(value: Vec<RecurringScheduleRow>) -> Result<Self, Self::Error> {
+        let recurring_schedules = value
+            .into_iter()
+            .map(RecurringSchedule::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RecurringSchedules {
+            recurring_schedules,
+        })
+    }
+}
+
+/// What a materializer pass changed in `[window_start, window_end)`.
+#[derive(Debug, Serialize)]
+pub struct Materialized {
+    window_start: String,
+    window_end: String,
+    inserted: usize,
+    deleted: usize,
+    unchanged: usize,
+    /// Occurrences left out because another source lists the slot.
+    covered: usize,
+}
+
+impl Materialized {
+    fn new(start: jiff::Timestamp, end: jiff::Timestamp, counts: MaterializeCounts) -> Self {
+        Materialized {
+            window_start: start.to_string(),
+            window_end: end.to_string(),
+            inserted: counts.inserted,
+            deleted: counts.deleted,
+            unchanged: counts.unchanged,
+            covered: counts.covered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::RepoError;
+    use jiff::civil::date;
+    use sqlx::types::chrono::Utc;
+    use std::sync::Mutex;
+
+    fn la() -> TimeZone {
+        TimeZone::get("America/Los_Angeles").expect("bundled tzdb")
+    }
+
+    fn row(rrule: &str, start: &str, end: Option<&str>, from: &str) -> RecurringScheduleRow {
+        RecurringScheduleRow {
+            id: Uuid::new_v4(),
+            brewery_id: Uuid::new_v4(),
+            food_vendor_id: Uuid::new_v4(),
+            rrule: rrule.to_string(),
+            start_time: start.parse().unwrap(),
+            end_time: end.map(|end| end.parse().unwrap()),
+            effective_from: from.parse().unwrap(),
+            effective_until: None,
+            exceptions: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn starts(row: &RecurringScheduleRow, horizon: &RecurringHorizon) -> Vec<String> {
+        RecurringRule::try_from(row)
+            .unwrap()
+            .occurrences(horizon)
+            .iter()
+            .map(|entry| entry.start.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rrule_parses_into_canonical_form() {
+        let recurrence: Recurrence = "rrule:freq=weekly;byday=th,fr;interval=1".parse().unwrap();
+        assert_eq!(recurrence.to_string(), "FREQ=WEEKLY;BYDAY=TH,FR");
+
+        assert!("FREQ=MONTHLY".parse::<Recurrence>().is_err());
+        assert!("BYDAY=MO".parse::<Recurrence>().is_err());
+        assert!("FREQ=WEEKLY;BYDAY=1MO".parse::<Recurrence>().is_err());
+        assert!("FREQ=WEEKLY;INTERVAL=0".parse::<Recurrence>().is_err());
+        assert!("FREQ=WEEKLY;COUNT=4".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn weekly_interval_counts_weeks_from_effective_from() {
+        let recurrence: Recurrence = "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH".parse().unwrap();
+        // 2026-10-14 is a Wednesday; its week starts Monday 2026-10-12.
+        let anchor = date(2026, 10, 14);
+        assert!(!recurrence.occurs_on(anchor, date(2026, 10, 13)));
+        assert!(recurrence.occurs_on(anchor, date(2026, 10, 15)));
+        assert!(!recurrence.occurs_on(anchor, date(2026, 10, 20)));
+        assert!(recurrence.occurs_on(anchor, date(2026, 10, 27)));
+        assert!(recurrence.occurs_on(anchor, date(2026, 10, 29)));
+
+        let plain: Recurrence = "FREQ=WEEKLY".parse().unwrap();
+        assert!(plain.occurs_on(anchor, date(2026, 10, 21)));
+        assert!(!plain.occurs_on(anchor, date(2026, 10, 22)));
+    }
+
+    #[test]
+    fn occurrences_follow_local_time_across_dst() {
+        // US clocks fall back on Sunday 2026-11-01.
+        let now: jiff::Timestamp = "2026-10-29T20:00:00Z".parse().unwrap();
+        let horizon = RecurringHorizon::new(now, &la(), 7);
+        let rule = row("FREQ=DAILY", "17:00:00", Some("01:00:00"), "2026-01-01");
+
+        let entries = RecurringRule::try_from(&rule)
+            .unwrap()
+            .occurrences(&horizon);
+        assert_eq!(entries.len(), 7);
+        assert_eq!(entries[0].start.to_string(), "2026-10-30T00:00:00Z");
+        assert_eq!(
+            entries[0].end.map(|end| end.to_string()),
+            Some("2026-10-30T08:00:00Z".to_string())
+        );
+        assert_eq!(entries[3].start.to_string(), "2026-11-02T01:00:00Z");
+    }
+
+    #[test]
+    fn occurrences_respect_effective_range_and_exceptions() {
+        let now: jiff::Timestamp = "2026-10-19T20:00:00Z".parse().unwrap();
+        let horizon = RecurringHorizon::new(now, &la(), 14);
+        let mut rule = row("FREQ=WEEKLY;BYDAY=TH", "17:00:00", None, "2026-10-01");
+        assert_eq!(
+            starts(&rule, &horizon),
+            ["2026-10-23T00:00:00Z", "2026-10-30T00:00:00Z"]
+        );
+
+        rule.exceptions = vec!["2026-10-22".parse().unwrap()];
+        assert_eq!(starts(&rule, &horizon), ["2026-10-30T00:00:00Z"]);
+
+        rule.effective_until = Some("2026-10-28".parse().unwrap());
+        assert!(starts(&rule, &horizon).is_empty());
+
+        let later = row("FREQ=WEEKLY;BYDAY=TH", "17:00:00", None, "2026-10-25");
+        assert_eq!(starts(&later, &horizon), ["2026-10-30T00:00:00Z"]);
+    }
+
+    #[derive(Default)]
+    struct MockRecurringRepo {
+        rows: Vec<RecurringScheduleRow>,
+        materialized: Mutex<Vec<(jiff::Timestamp, jiff::Timestamp, usize)>>,
+    }
+
+    impl RecurringScheduleRepo for MockRecurringRepo {
+        async fn get_recurring_schedules(&self) -> Result<Vec<RecurringScheduleRow>, RepoError> {
+            Ok(self
+                .rows
+                .iter()
+                .map(|r| RecurringScheduleRow {
+                    rrule: r.rrule.clone(),
+                    exceptions: r.exceptions.clone(),
+                    ..*r
+                })
+                .collect())
+        }
+
+        async fn create_recurring_schedule(
+            &self,
+            _schedule: &RecurringScheduleWrite,
+        ) -> Result<RecurringScheduleRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn delete_recurring_schedule(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+
+        async fn materialize_recurring(
+            &self,
+            start: jiff::Timestamp,
+            end: jiff::Timestamp,
+            _tz: &str,
+            entries: &[RecurringEntryWrite],
+        ) -> Result<MaterializeCounts, RepoError> {
+            self.materialized
+                .lock()
+                .expect("lock poisoned")
+                .push((start, end, entries.len()));
+            Ok(MaterializeCounts {
+                inserted: entries.len(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn materialize_covers_local_days_and_skips_unparseable_rules() {
+        let repo = MockRecurringRepo {
+            rows: vec![
+                row(
+                    "FREQ=WEEKLY;BYDAY=TH",
+                    "17:00:00",
+                    Some("21:00:00"),
+                    "2026-10-01",
+                ),
+                row("FREQ=YEARLY", "17:00:00", None, "2026-10-01"),
+            ],
+            ..Default::default()
+        };
+        let now: jiff::Timestamp = "2026-10-20T05:00:00Z".parse().unwrap();
+        let horizon = RecurringHorizon::new(now, &la(), 7);
+
+        let materialized = materialize_recurring(&repo, &horizon).await.unwrap();
+        assert_eq!(materialized.inserted, 1);
+        assert_eq!(materialized.window_start, "2026-10-19T07:00:00Z");
+
+        let calls = repo.materialized.lock().unwrap();
+        assert_eq!(
+            calls[0],
+            (
+                "2026-10-19T07:00:00Z".parse().unwrap(),
+                "2026-10-26T07:00:00Z".parse().unwrap(),
+                1
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn create_rejects_unsupported_rules_before_writing() {
+        let input: CreateRecurringSchedule = serde_json::from_value(serde_json::json!({
+            "brewery_id": Uuid::new_v4(),
+            "vendor_id": Uuid::new_v4(),
+            "rrule": "FREQ=MONTHLY",
+            "start_time": "17:00",
+            "end_time": "17:00",
+            "effective_from": "2026-10-01",
+            "effective_until": "2026-09-01",
+        }))
+        .unwrap();
+        let horizon = RecurringHorizon::new(jiff::Timestamp::now(), &la(), 7);
+
+        let result =
+            create_recurring_schedule(&MockRecurringRepo::default(), input, &horizon).await;
+        let Err(DomainError::Validation(fields)) = result else {
+            panic!("expected validation errors");
+        };
+        let fields: Vec<_> = fields.iter().map(|f| f.field).collect();
+        assert_eq!(fields, ["rrule", "effective_until", "end_time"]);
+    }
+}
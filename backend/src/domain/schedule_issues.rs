@@ -0,0 +1,721 @@
+use crate::db::{
+    BreweryHoursRepo, IssueSyncCounts, ScheduleIssueRepo, ScheduleIssueRow, ScheduleIssueWrite,
+    ScheduleRow,
+};
+use crate::domain::DomainError;
+use crate::domain::brewery_hours::{BusinessHours, OpenAt, load_business_hours};
+use crate::domain::schedules::{IMPORT_SOURCE, MANUAL_SOURCE, range_hours, row_hours, to_jiff};
+use jiff::SignedDuration;
+use jiff::tz::TimeZone;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Most issues returned by one query.
+const MAX_SCHEDULE_ISSUES: i64 = 200;
+/// Entries that ended or were written longer ago than this are not checked again.
+const LOOKBACK: SignedDuration = SignedDuration::from_hours(24);
+/// Longest plausible timed slot.
+const MAX_DURATION: SignedDuration = SignedDuration::from_hours(16);
+/// Longest plausible all-day slot, allowing for a DST change.
+const MAX_ALL_DAY_DURATION: SignedDuration = SignedDuration::from_hours(25);
+/// Shortest plausible timed slot.
+const MIN_DURATION: SignedDuration = SignedDuration::from_mins(15);
+
+/// Checks recent and upcoming entries for overlapping slots of one vendor, implausible
+/// durations, slots outside their brewery's hours and entries written after they ended,
+/// and stores what it finds as reviewable issues.
+pub async fn check_schedules<R: ScheduleIssueRepo + BreweryHoursRepo>(
+    db: &R,
+    now: jiff::Timestamp,
+    tz: &TimeZone,
+) -> Result<ScheduleCheck, DomainError> {
+    let rows = db.get_entries_to_check(now - LOOKBACK).await?;
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in &rows {
+        match CheckedEntry::new(row, tz) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("Skipping schedule entry in check: {}", e),
+        }
+    }
+
+    let local_date = |ts: jiff::Timestamp| ts.to_zoned(tz.clone()).date();
+    let first = entries.iter().map(|entry| local_date(entry.start)).min();
+    let last = entries.iter().map(|entry| local_date(entry.start)).max();
+    let hours = match first.zip(last) {
+        Some((first, last)) => load_business_hours(db, first, last).await?,
+        None => HashMap::new(),
+    };
+
+    let issues = find_issues(&entries, &hours, tz);
+    let checked: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+    let counts = db.sync_schedule_issues(&checked, &issues).await?;
+    Ok(ScheduleCheck::new(checked.len(), counts))
+}
+
+pub async fn get_schedule_issues<R: ScheduleIssueRepo>(
+    db: &R,
+    status: Option<IssueStatus>,
+) -> Result<ScheduleIssues, DomainError> {
+    let rows = db
+        .get_schedule_issues(status.map(IssueStatus::as_str), MAX_SCHEDULE_ISSUES)
+        .await?;
+    let issues = rows
+        .into_iter()
+        .map(ScheduleIssue::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ScheduleIssues { issues })
+}
+
+/// Dismisses an issue that is fine as is, or reopens a dismissed one.
+pub async fn review_schedule_issue<R: ScheduleIssueRepo>(
+    db: &R,
+    id: Uuid,
+    input: ReviewScheduleIssue,
+    reviewer: &str,
+) -> Result<ScheduleIssue, DomainError> {
+    db.review_schedule_issue(id, input.status.as_str(), reviewer)
+        .await?
+        .ok_or(DomainError::NotFound)
+        .and_then(ScheduleIssue::try_from)
+}
+
+/// An entry reduced to what the checks look at.
+struct CheckedEntry {
+    id: Uuid,
+    brewery_id: Uuid,
+    brewery_name: String,
+    vendor_id: Uuid,
+    start: jiff::Timestamp,
+    end: Option<jiff::Timestamp>,
+    /// `end`, or the end of the local day for "until close" slots.
+    slot_end: jiff::Timestamp,
+    all_day: bool,
+    source: String,
+    written_at: jiff::Timestamp,
+}
+
+impl CheckedEntry {
+    fn new(row: &ScheduleRow, tz: &TimeZone) -> Result<Self, DomainError> {
+        let (start, end) = row_hours(row)?;
+        let slot_end = match end {
+            Some(end) => end,
+            None => start
+                .to_zoned(tz.clone())
+                .tomorrow()
+                .and_then(|day| day.start_of_day())
+                .expect("dates are within jiff's range")
+                .timestamp(),
+        };
+        Ok(CheckedEntry {
+            id: row.id,
+            brewery_id: row.brewery_id,
+            brewery_name: row.brewery_name.clone(),
+            vendor_id: row.food_vendor_id,
+            start,
+            end,
+            slot_end,
+            all_day: row.all_day,
+            source: row.source.clone(),
+            written_at: to_jiff(row.updated_at),
+        })
+    }
+}
+
+fn find_issues(
+    entries: &[CheckedEntry],
+    hours: &HashMap<Uuid, BusinessHours>,
+    tz: &TimeZone,
+) -> Vec<ScheduleIssueWrite> {
+    let local = |ts: jiff::Timestamp| {
+        ts.to_zoned(tz.clone())
+            .strftime("%Y-%m-%d %H:%M")
+            .to_string()
+    };
+    let issue = |kind: &str, entry: &CheckedEntry, related: Option<Uuid>, detail: String| {
+        ScheduleIssueWrite {
+            kind: kind.to_string(),
+            schedule_entry_id: entry.id,
+            related_entry_id: related,
+            detail,
+        }
+    };
+
+    let mut issues = Vec::new();
+    for entry in entries {
+        if let Some(end) = entry.end {
+            let duration = end.duration_since(entry.start);
+            let limit = if entry.all_day {
+                MAX_ALL_DAY_DURATION
+            } else {
+                MAX_DURATION
+            };
+            if duration > limit {
+                issues.push(issue(
+                    "implausible_duration",
+                    entry,
+                    None,
+                    format!("lasts {:#}, longer than {:#}", duration, limit),
+                ));
+            } else if !entry.all_day && duration < MIN_DURATION {
+                issues.push(issue(
+                    "implausible_duration",
+                    entry,
+                    None,
+                    format!("lasts {:#}, shorter than {:#}", duration, MIN_DURATION),
+                ));
+            }
+        }
+
+        // Breweries without recorded hours are not checked.
+        let open = match hours.get(&entry.brewery_id) {
+            Some(hours) if !entry.all_day => hours.open_at(entry.start, tz),
+            _ => OpenAt::Unknown,
+        };
+        let outside = match open {
+            OpenAt::Unknown => None,
+            OpenAt::Closed => Some(format!(
+                "starts {}, when {} is closed",
+                local(entry.start),
+                entry.brewery_name
+            )),
+            OpenAt::Open { until } => entry.end.filter(|end| *end > until).map(|end| {
+                format!(
+                    "ends {}, after {} closes at {}",
+                    local(end),
+                    entry.brewery_name,
+                    local(until)
+                )
+            }),
+        };
+        if let Some(detail) = outside {
+            issues.push(issue("outside_business_hours", entry, None, detail));
+        }
+
+        // Admins may correct history by hand or backfill it with an import.
+        let backfilled = [MANUAL_SOURCE, IMPORT_SOURCE].contains(&entry.source.as_str());
+        if !backfilled && entry.written_at > entry.slot_end {
+            issues.push(issue(
+                "past_dated",
+                entry,
+                None,
+                format!(
+                    "written at {}, after the slot ended at {}",
+                    local(entry.written_at),
+                    local(entry.slot_end)
+                ),
+            ));
+        }
+    }
+
+    // Entries arrive ordered by vendor and start, so overlaps are neighbours within a
+    // vendor's run.
+    for (i, entry) in entries.iter().enumerate() {
+        for other in entries[i + 1..]
+            .iter()
+            .take_while(|other| other.vendor_id == entry.vendor_id)
+            .take_while(|other| other.start < entry.slot_end)
+        {
+            issues.push(issue(
+                "vendor_overlap",
+                entry,
+                Some(other.id),
+                format!(
+                    "vendor is also at {} from {}",
+                    other.brewery_name,
+                    local(other.start)
+                ),
+            ));
+        }
+    }
+    issues
+}
+
+/// Review state of an issue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueStatus {
+    /// Waiting for review or for the data to be fixed.
+    Open,
+    /// Reviewed and accepted as is; later checks leave it dismissed.
+    Dismissed,
+}
+
+impl IssueStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IssueStatus::Open => "open",
+            IssueStatus::Dismissed => "dismissed",
+        }
+    }
+
+    fn from_row(id: Uuid, value: &str) -> Result<Self, DomainError> {
+        match value {
+            "open" => Ok(IssueStatus::Open),
+            "dismissed" => Ok(IssueStatus::Dismissed),
+            _ => Err(DomainError::InvalidRow {
+                id,
+                reason: "schedule issue status is unknown",
+            }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewScheduleIssue {
+    status: IssueStatus,
+}
+
+#[derive(Serialize)]
+pub struct ScheduleIssue {
+    id: Uuid,
+    kind: String,
+    status: IssueStatus,
+    detail: String,
+    schedule_id: Uuid,
+    /// The other entry of a `vendor_overlap`.
+    related_schedule_id: Option<Uuid>,
+    brewery_id: Uuid,
+    brewery_name: String,
+    vendor_id: Uuid,
+    vendor_name: String,
+    start_at: String,
+    end_at: Option<String>,
+    detected_at: String,
+    reviewed_at: Option<String>,
+    reviewed_by: Option<String>,
+}
+
+impl TryFrom<ScheduleIssueRow> for ScheduleIssue {
+    type Error = DomainError;
+
+    fn try_from(value: ScheduleIssueRow) -> Result<Self, Self::Error> {
+        let (start, end) = range_hours(value.schedule_entry_id, &value.open_hours)?;
+        Ok(ScheduleIssue {
+            id: value.id,
+            status: IssueStatus::from_row(value.id, &value.status)?,
+            kind: value.kind,
+            detail: value.detail,
+            schedule_id: value.schedule_entry_id,
+            related_schedule_id: value.related_entry_id,
+            brewery_id: value.brewery_id,
+            brewery_name: value.brewery_name,
+            vendor_id: value.food_vendor_id,
+            vendor_name: value.food_vendor_name,
+            start_at: start.to_string(),
+            end_at: end.map(|end| end.to_string()),
+            detected_at: value.detected_at.to_rfc3339(),
+            reviewed_at: value.reviewed_at.map(|at| at.to_rfc3339()),
+            reviewed_by: value.reviewed_by,
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScheduleIssues {
+    issues: Vec<ScheduleIssue>,
+}
+
+/// What one schedule check found.
+#[derive(Debug, Serialize)]
+pub struct ScheduleCheck {
+    entries_checked: usize,
+    opened: usize,
+    unchanged: usize,
+    cleared: usize,
+}
+
+impl ScheduleCheck {
+    fn new(entries_checked: usize, counts: IssueSyncCounts) -> Self {
+        ScheduleCheck {
+            entries_checked,
+            opened: counts.opened,
+            unchanged: counts.unchanged,
+            cleared: counts.cleared,
+        }
+    }
+
+    /// Issues raised by this check that were not known before.
+    pub fn opened(&self) -> usize {
+        self.opened
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        BreweryClosureRow, BreweryClosureWrite, ProvenanceRow, RepoError, WeeklyHoursRow,
+        WeeklyHoursWrite,
+    };
+    use sqlx::postgres::types::PgRange;
+    use sqlx::types::chrono::{DateTime, Utc};
+    use std::ops::Bound;
+    use std::sync::Mutex;
+
+    fn la() -> TimeZone {
+        TimeZone::get("America/Los_Angeles").expect("bundled tzdb")
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        ts.parse().unwrap()
+    }
+
+    const STOUP: Uuid = Uuid::from_u128(7);
+
+    fn row(vendor: Uuid, start: &str, end: Option<&str>, written: &str) -> ScheduleRow {
+        ScheduleRow {
+            id: Uuid::new_v4(),
+            brewery_id: STOUP,
+            brewery_name: "Stoup Brewing".to_string(),
+            food_vendor_id: vendor,
+            food_vendor_name: "Tacos & Beer".to_string(),
+            open_hours: PgRange {
+                start: Bound::Included(at(start)),
+                end: end.map_or(Bound::Unbounded, |end| Bound::Excluded(at(end))),
+            },
+            all_day: false,
+            status: "confirmed".to_string(),
+            source: "stoup-site".to_string(),
+            provenance: ProvenanceRow::default(),
+            updated_at: at(written),
+            override_id: None,
+        }
+    }
+
+    /// Issues found with Stoup open from noon to 02:00 every day.
+    fn kinds(rows: &[ScheduleRow]) -> Vec<(String, Uuid, Option<Uuid>)> {
+        let hours = HashMap::from([(STOUP, BusinessHours::daily("12:00", "02:00"))]);
+        kinds_with_hours(rows, &hours)
+    }
+
+    fn kinds_with_hours(
+        rows: &[ScheduleRow],
+        hours: &HashMap<Uuid, BusinessHours>,
+    ) -> Vec<(String, Uuid, Option<Uuid>)> {
+        let entries: Vec<_> = rows
+            .iter()
+            .map(|row| CheckedEntry::new(row, &la()).unwrap())
+            .collect();
+        find_issues(&entries, hours, &la())
+            .into_iter()
+            .map(|issue| (issue.kind, issue.schedule_entry_id, issue.related_entry_id))
+            .collect()
+    }
+
+    const WRITTEN: &str = "2026-10-19T12:00:00Z";
+
+    #[test]
+    fn plausible_entries_raise_no_issues() {
+        let vendor = Uuid::new_v4();
+        let rows = [
+            // 17:00-21:00 and 17:00-close on the next day, Pacific time.
+            row(
+                vendor,
+                "2026-10-23T00:00:00Z",
+                Some("2026-10-23T04:00:00Z"),
+                WRITTEN,
+            ),
+            row(vendor, "2026-10-24T00:00:00Z", None, WRITTEN),
+            // Ends exactly at 02:00.
+            row(
+                Uuid::new_v4(),
+                "2026-10-23T03:00:00Z",
+                Some("2026-10-23T09:00:00Z"),
+                WRITTEN,
+            ),
+        ];
+        assert!(kinds(&rows).is_empty());
+    }
+
+    #[test]
+    fn overlapping_slots_of_one_vendor_are_paired() {
+        let vendor = Uuid::new_v4();
+        let rows = [
+            row(vendor, "2026-10-23T00:00:00Z", None, WRITTEN),
+            row(
+                vendor,
+                "2026-10-23T02:00:00Z",
+                Some("2026-10-23T05:00:00Z"),
+                WRITTEN,
+            ),
+            // Starts the next local day, after the until-close slot ends.
+            row(
+                vendor,
+                "2026-10-23T07:30:00Z",
+                Some("2026-10-23T08:30:00Z"),
+                WRITTEN,
+            ),
+        ];
+        assert_eq!(
+            kinds(&rows),
+            [("vendor_overlap".to_string(), rows[0].id, Some(rows[1].id))]
+        );
+    }
+
+    #[test]
+    fn long_short_overnight_and_late_entries_are_flagged() {
+        let rows = [
+            row(
+                Uuid::new_v4(),
+                "2026-10-23T00:00:00Z",
+                Some("2026-10-24T06:00:00Z"),
+                WRITTEN,
+            ),
+            row(
+                Uuid::new_v4(),
+                "2026-10-23T00:00:00Z",
+                Some("2026-10-23T00:05:00Z"),
+                WRITTEN,
+            ),
+            row(
+                Uuid::new_v4(),
+                "2026-10-23T10:00:00Z",
+                Some("2026-10-23T12:00:00Z"),
+                WRITTEN,
+            ),
+            row(
+                Uuid::new_v4(),
+                "2026-10-18T00:00:00Z",
+                Some("2026-10-18T04:00:00Z"),
+                WRITTEN,
+            ),
+        ];
+        let found: Vec<_> = kinds(&rows)
+            .into_iter()
+            .map(|(kind, id, _)| (kind, id))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("implausible_duration".to_string(), rows[0].id),
+                // Runs past the 02:00 close as well.
+                ("outside_business_hours".to_string(), rows[0].id),
+                ("implausible_duration".to_string(), rows[1].id),
+                ("outside_business_hours".to_string(), rows[2].id),
+                ("past_dated".to_string(), rows[3].id),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_are_checked_against_brewery_hours() {
+        let mut stoup = BusinessHours::daily("12:00", "22:00");
+        stoup.close_on("2026-10-24".parse().unwrap(), "Private event");
+        let hours = HashMap::from([(STOUP, stoup)]);
+        let mut elsewhere = row(
+            Uuid::new_v4(),
+            "2026-10-23T10:00:00Z",
+            Some("2026-10-23T12:00:00Z"),
+            WRITTEN,
+        );
+        elsewhere.brewery_id = Uuid::new_v4();
+        let rows = [
+            // 17:00-23:00, past the 22:00 close.
+            row(
+                Uuid::new_v4(),
+                "2026-10-23T00:00:00Z",
+                Some("2026-10-23T06:00:00Z"),
+                WRITTEN,
+            ),
+            // Until close on the day Stoup is closed.
+            row(Uuid::new_v4(), "2026-10-25T00:00:00Z", None, WRITTEN),
+            // 03:00-05:00 at a brewery without recorded hours.
+            elsewhere,
+        ];
+
+        let details: Vec<_> = rows
+            .iter()
+            .map(|row| CheckedEntry::new(row, &la()).unwrap())
+            .collect::<Vec<_>>();
+        let issues = find_issues(&details, &hours, &la());
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.kind.as_str(),
+                    issue.schedule_entry_id,
+                    issue.detail.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "outside_business_hours",
+                    rows[0].id,
+                    "ends 2026-10-22 23:00, after Stoup Brewing closes at 2026-10-22 22:00"
+                ),
+                (
+                    "outside_business_hours",
+                    rows[1].id,
+                    "starts 2026-10-24 17:00, when Stoup Brewing is closed"
+                ),
+            ]
+        );
+        assert!(kinds_with_hours(&rows[2..], &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn manual_and_imported_entries_may_be_written_after_they_end() {
+        for source in [MANUAL_SOURCE, IMPORT_SOURCE] {
+            let mut late = row(
+                Uuid::new_v4(),
+                "2026-10-18T00:00:00Z",
+                Some("2026-10-18T04:00:00Z"),
+                WRITTEN,
+            );
+            late.source = source.to_string();
+            assert!(kinds(&[late]).is_empty(), "{}", source);
+        }
+    }
+
+    type SyncCall = (Vec<Uuid>, Vec<ScheduleIssueWrite>);
+
+    #[derive(Default)]
+    struct MockIssueRepo {
+        rows: Vec<ScheduleRow>,
+        since: Mutex<Option<jiff::Timestamp>>,
+        synced: Mutex<Option<SyncCall>>,
+    }
+
+    impl ScheduleIssueRepo for MockIssueRepo {
+        async fn get_entries_to_check(
+            &self,
+            since: jiff::Timestamp,
+        ) -> Result<Vec<ScheduleRow>, RepoError> {
+            *self.since.lock().expect("lock poisoned") = Some(since);
+            Ok(self
+                .rows
+                .iter()
+                .map(|r| ScheduleRow {
+                    brewery_name: r.brewery_name.clone(),
+                    food_vendor_name: r.food_vendor_name.clone(),
+                    status: r.status.clone(),
+                    source: r.source.clone(),
+                    provenance: r.provenance.clone(),
+                    ..*r
+                })
+                .collect())
+        }
+
+        async fn sync_schedule_issues(
+            &self,
+            checked: &[Uuid],
+            issues: &[ScheduleIssueWrite],
+        ) -> Result<IssueSyncCounts, RepoError> {
+            let copied = issues
+                .iter()
+                .map(|issue| ScheduleIssueWrite {
+                    kind: issue.kind.clone(),
+                    detail: issue.detail.clone(),
+                    ..*issue
+                })
+                .collect();
+            *self.synced.lock().expect("lock poisoned") = Some((checked.to_vec(), copied));
+            Ok(IssueSyncCounts {
+                opened: issues.len(),
+                unchanged: 0,
+                cleared: 2,
+            })
+        }
+
+        async fn get_schedule_issues(
+            &self,
+            _status: Option<&str>,
+            _limit: i64,
+        ) -> Result<Vec<ScheduleIssueRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn review_schedule_issue(
+            &self,
+            _id: Uuid,
+            _status: &str,
+            _reviewed_by: &str,
+        ) -> Result<Option<ScheduleIssueRow>, RepoError> {
+            Ok(None)
+        }
+    }
+
+    impl BreweryHoursRepo for MockIssueRepo {
+        async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_brewery_closures(
+            &self,
+            _from: jiff::civil::Date,
+            _until: jiff::civil::Date,
+        ) -> Result<Vec<BreweryClosureRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn replace_weekly_hours(
+            &self,
+            _brewery_id: Uuid,
+            _hours: &[WeeklyHoursWrite],
+        ) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn upsert_brewery_closure(
+            &self,
+            _closure: &BreweryClosureWrite,
+        ) -> Result<BreweryClosureRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn delete_brewery_closure(
+            &self,
+            _brewery_id: Uuid,
+            _id: Uuid,
+        ) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    #[tokio::test]
+    async fn check_syncs_issues_for_every_checked_entry() {
+        let vendor = Uuid::new_v4();
+        let repo = MockIssueRepo {
+            rows: vec![
+                row(
+                    vendor,
+                    "2026-10-23T00:00:00Z",
+                    Some("2026-10-24T06:00:00Z"),
+                    WRITTEN,
+                ),
+                row(Uuid::new_v4(), "2026-10-24T00:00:00Z", None, WRITTEN),
+            ],
+            ..Default::default()
+        };
+        let now: jiff::Timestamp = WRITTEN.parse().unwrap();
+
+        let check = check_schedules(&repo, now, &la()).await.unwrap();
+        assert_eq!(check.entries_checked, 2);
+        assert_eq!(check.opened(), 1);
+        assert_eq!(check.cleared, 2);
+        assert_eq!(
+            *repo.since.lock().unwrap(),
+            Some("2026-10-18T12:00:00Z".parse().unwrap())
+        );
+
+        let (checked, issues) = repo.synced.lock().unwrap().take().unwrap();
+        assert_eq!(checked, [repo.rows[0].id, repo.rows[1].id]);
+        assert_eq!(issues[0].kind, "implausible_duration");
+        assert_eq!(issues[0].detail, "lasts 30h, longer than 16h");
+    }
+
+    #[tokio::test]
+    async fn reviewing_a_missing_issue_is_not_found() {
+        let input: ReviewScheduleIssue =
+            serde_json::from_value(serde_json::json!({ "status": "dismissed" })).unwrap();
+        let result =
+            review_schedule_issue(&MockIssueRepo::default(), Uuid::new_v4(), input, "ops").await;
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+}
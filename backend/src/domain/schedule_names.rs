@@ -0,0 +1,176 @@
+use crate::db::{DriftedNameRow, ScheduleNameRepo};
+use crate::domain::DomainError;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Finds schedule entries whose copied brewery or vendor name no longer matches the
+/// parent row and, unless `dry_run` is set, copies the current names onto them.
+pub async fn repair_schedule_names<R: ScheduleNameRepo>(
+    db: &R,
+    dry_run: bool,
+) -> Result<NameRepair, DomainError> {
+    let drifted: Vec<DriftedName> = db
+        .get_drifted_names()
+        .await?
+        .into_iter()
+        .flat_map(DriftedName::from_row)
+        .collect();
+    let repaired = if dry_run || drifted.is_empty() {
+        0
+    } else {
+        db.repair_drifted_names().await?
+    };
+    Ok(NameRepair {
+        dry_run,
+        drifted,
+        repaired,
+    })
+}
+
+/// One stale name copy.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DriftedName {
+    schedule_id: Uuid,
+    field: &'static str,
+    stored: String,
+    current: String,
+}
+
+impl DriftedName {
+    fn from_row(row: DriftedNameRow) -> Vec<DriftedName> {
+        let mut drifted = Vec::new();
+        if row.brewery_name != row.canonical_brewery_name {
+            drifted.push(DriftedName {
+                schedule_id: row.id,
+                field: "brewery_name",
+                stored: row.brewery_name,
+                current: row.canonical_brewery_name,
+            });
+        }
+        if row.food_vendor_name != row.canonical_vendor_name {
+            drifted.push(DriftedName {
+                schedule_id: row.id,
+                field: "vendor_name",
+                stored: row.food_vendor_name,
+                current: row.canonical_vendor_name,
+            });
+        }
+        drifted
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct NameRepair {
+    dry_run: bool,
+    drifted: Vec<DriftedName>,
+    /// Schedule entries rewritten; 0 for dry runs.
+    repaired: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixture::Fixture;
+    use crate::db::{RepoError, ScheduleFilter};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct MockNameRepo {
+        drifted: Vec<(Uuid, &'static str, &'static str)>,
+        repaired: AtomicBool,
+    }
+
+    impl ScheduleNameRepo for MockNameRepo {
+        async fn get_drifted_names(&self) -> Result<Vec<DriftedNameRow>, RepoError> {
+            Ok(self
+                .drifted
+                .iter()
+                .map(|(id, stored, current)| DriftedNameRow {
+                    id: *id,
+                    brewery_name: "Stoup Brewing".to_string(),
+                    canonical_brewery_name: "Stoup Brewing".to_string(),
+                    food_vendor_name: stored.to_string(),
+                    canonical_vendor_name: current.to_string(),
+                })
+                .collect())
+        }
+
+        async fn repair_drifted_names(&self) -> Result<u64, RepoError> {
+            self.repaired.store(true, Ordering::SeqCst);
+            Ok(self.drifted.len() as u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_only_the_drifted_field() {
+        let id = Uuid::new_v4();
+        let repo = MockNameRepo {
+            drifted: vec![(id, "Tacos and Beer", "Tacos & Beer")],
+            ..Default::default()
+        };
+
+        let repair = repair_schedule_names(&repo, false).await.unwrap();
+        assert_eq!(
+            repair.drifted,
+            [DriftedName {
+                schedule_id: id,
+                field: "vendor_name",
+                stored: "Tacos and Beer".to_string(),
+                current: "Tacos & Beer".to_string(),
+            }]
+        );
+        assert_eq!(repair.repaired, 1);
+        assert!(repo.repaired.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn dry_run_writes_nothing() {
+        let repo = MockNameRepo {
+            drifted: vec![(Uuid::new_v4(), "Tacos and Beer", "Tacos & Beer")],
+            ..Default::default()
+        };
+
+        let repair = repair_schedule_names(&repo, true).await.unwrap();
+        assert_eq!(repair.drifted.len(), 1);
+        assert_eq!(repair.repaired, 0);
+        assert!(!repo.repaired.load(Ordering::SeqCst));
+    }
+
+    /// The vendor name schedules report for the fixture's entry.
+    async fn listed_vendor_name(fixture: &Fixture) -> String {
+        fixture
+            .listed_entry(&ScheduleFilter::default())
+            .await
+            .expect("fixture entry is listed")
+            .food_vendor_name
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn vendor_rename_shows_up_in_schedules() {
+        let fixture = Fixture::new("Tacos and Beer").await;
+        assert_eq!(listed_vendor_name(&fixture).await, "Tacos and Beer");
+
+        let rename = serde_json::from_value(serde_json::json!({ "name": "Tacos & Beer" })).unwrap();
+        crate::domain::update_vendor(&fixture.db, fixture.vendor_id, rename)
+            .await
+            .unwrap();
+        assert_eq!(listed_vendor_name(&fixture).await, "Tacos & Beer");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn repair_fixes_drifted_names() {
+        let fixture = Fixture::new("Where Ya At Matt").await;
+        sqlx::query("UPDATE schedule_entries SET food_vendor_name = 'Stale' WHERE id = $1")
+            .bind(fixture.schedule_id)
+            .execute(fixture.db.pool())
+            .await
+            .unwrap();
+        assert_eq!(listed_vendor_name(&fixture).await, "Stale");
+
+        repair_schedule_names(&fixture.db, false).await.unwrap();
+        assert!(fixture.db.get_drifted_names().await.unwrap().is_empty());
+        assert_eq!(listed_vendor_name(&fixture).await, "Where Ya At Matt");
+    }
+}
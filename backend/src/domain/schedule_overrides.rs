@@ -0,0 +1,484 @@
+use crate::db::{
+    ScheduleOverrideRepo, ScheduleOverrideRow, ScheduleOverrideWrite, ScheduleRepo, ScheduleRow,
+    SlotWrite,
+};
+use crate::domain::DomainError;
+use crate::domain::schedules::{row_hours, schedule_write_error};
+use crate::domain::validation::{FieldError, Validator, double_option, patch};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::ops::Bound;
+use uuid::Uuid;
+
+pub async fn get_schedule_overrides<R: ScheduleOverrideRepo>(
+    db: &R,
+) -> Result<ScheduleOverrides, DomainError> {
+    let rows = db.get_active_overrides().await?;
+    ScheduleOverrides::try_from(rows)
+}
+
+/// Records an override. Cancels and edits copy the targeted entry's brewery, vendor
+/// and hours so they keep matching after a re-scrape replaces the entry.
+pub async fn create_schedule_override<R: ScheduleRepo + ScheduleOverrideRepo>(
+    db: &R,
+    input: CreateScheduleOverride,
+    author: &str,
+) -> Result<ScheduleOverride, DomainError> {
+    let mut v = Validator::default();
+    v.non_empty("reason", &input.reason);
+    if let Some(expires_at) = input.expires_at {
+        v.check(
+            expires_at > jiff::Timestamp::now(),
+            "expires_at",
+            "must be in the future",
+        );
+    }
+    v.finish()?;
+
+    let (kind, brewery_id, target, replacement, all_day) = match input.action {
+        OverrideAction::Add {
+            brewery_id,
+            vendor_id,
+            start_at,
+            end_at,
+            all_day,
+        } => {
+            let replacement = SlotWrite {
+                food_vendor_id: vendor_id,
+                start: start_at,
+                end: end_at,
+            };
+            ("add", brewery_id, None, Some(replacement), all_day)
+        }
+        OverrideAction::Cancel { schedule_id } => {
+            let entry = target_entry(db, schedule_id).await?;
+            let (start, end) = row_hours(&entry)?;
+            let target = SlotWrite {
+                food_vendor_id: entry.food_vendor_id,
+                start,
+                end,
+            };
+            (
+                "cancel",
+                entry.brewery_id,
+                Some(target),
+                None,
+                entry.all_day,
+            )
+        }
+        OverrideAction::Edit {
+            schedule_id,
+            vendor_id,
+            start_at,
+            end_at,
+            all_day,
+        } => {
+            let entry = target_entry(db, schedule_id).await?;
+            let (start, end) = row_hours(&entry)?;
+            let replacement = SlotWrite {
+                food_vendor_id: patch(entry.food_vendor_id, vendor_id),
+                start: patch(start, start_at),
+                end: patch(end, end_at),
+            };
+            let target = SlotWrite {
+                food_vendor_id: entry.food_vendor_id,
+                start,
+                end,
+            };
+            let all_day = patch(entry.all_day, all_day);
+            (
+                "edit",
+                entry.brewery_id,
+                Some(target),
+                Some(replacement),
+                all_day,
+            )
+        }
+    };
+
+    if let Some(SlotWrite {
+        start,
+        end: Some(end),
+        ..
+    }) = &replacement
+    {
+        let mut v = Validator::default();
+        v.check(end > start, "end_at", "must be after start_at");
+        v.finish()?;
+    }
+
+    let row = db
+        .create_override(&ScheduleOverrideWrite {
+            kind,
+            brewery_id,
+            target,
+            replacement,
+            all_day,
+            reason: input.reason.trim().to_string(),
+            author: author.to_string(),
+            expires_at: input.expires_at,
+        })
+        .await
+        .map_err(schedule_write_error)?;
+    ScheduleOverride::try_from(row)
+}
+
+pub async fn delete_schedule_override<R: ScheduleOverrideRepo>(
+    db: &R,
+    id: Uuid,
+) -> Result<(), DomainError> {
+    if db.delete_override(id).await? {
+        Ok(())
+    } else {
+        Err(DomainError::NotFound)
+    }
+}
+
+async fn target_entry<R: ScheduleRepo>(
+    db: &R,
+    schedule_id: Uuid,
+) -> Result<ScheduleRow, DomainError> {
+    db.get_schedule(schedule_id).await?.ok_or_else(|| {
+        DomainError::Validation(vec![FieldError {
+            field: "schedule_id",
+            message: "does not exist".to_string(),
+        }])
+    })
+}
+
+/// What an override does, tagged by `kind`.
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OverrideAction {
+    /// Adds a slot the scraper does not know about.
+    Add {
+        brewery_id: Uuid,
+        vendor_id: Uuid,
+        start_at: jiff::Timestamp,
+        end_at: Option<jiff::Timestamp>,
+        #[serde(default)]
+        all_day: bool,
+    },
+    /// Hides a scraped slot.
+    Cancel { schedule_id: Uuid },
+    /// Replaces fields of a scraped slot; omitted fields keep the scraped values.
+    Edit {
+        schedule_id: Uuid,
+        vendor_id: Option<Uuid>,
+        start_at: Option<jiff::Timestamp>,
+        #[serde(default, deserialize_with = "double_option")]
+        end_at: Option<Option<jiff::Timestamp>>,
+        all_day: Option<bool>,
+    },
+}
+
+/// Admin input for a new schedule override.
+#[derive(Deserialize)]
+pub struct CreateScheduleOverride {
+    #[serde(flatten)]
+    action: OverrideAction,
+    reason: String,
+    expires_at: Option<jiff::Timestamp>,
+}
+
+#[derive(Serialize)]
+pub struct OverrideSlot {
+    vendor_id: Uuid,
+    start_at: String,
+    end_at: Option<String>,
+}
+
+impl OverrideSlot {
+    fn new(
+        id: Uuid,
+        vendor_id: Option<Uuid>,
+        hours: Option<PgRange<DateTime<Utc>>>,
+    ) -> Result<Option<Self>, DomainError> {
+        let (Some(vendor_id), Some(hours)) = (vendor_id, hours) else {
+            return Ok(None);
+        };
+        let start_at = match hours.start {
+            Bound::Included(ts) | Bound::Excluded(ts) => ts.to_rfc3339(),
+            Bound::Unbounded => {
+                return Err(DomainError::InvalidRow {
+                    id,
+                    reason: "override hours start is unbounded",
+                });
+            }
+        };
+        let end_at = match hours.end {
+            Bound::Included(ts) | Bound::Excluded(ts) => Some(ts.to_rfc3339()),
+            Bound::Unbounded => None,
+        };
+        Ok(Some(OverrideSlot {
+            vendor_id,
+            start_at,
+            end_at,
+        }))
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScheduleOverride {
+    id: Uuid,
+    kind: String,
+    brewery_id: Uuid,
+    /// Slot being cancelled or edited.
+    target: Option<OverrideSlot>,
+    /// Slot being added, or the edit's replacement.
+    replacement: Option<OverrideSlot>,
+    all_day: bool,
+    reason: String,
+    author: String,
+    expires_at: Option<String>,
+    created_at: String,
+}
+
+impl TryFrom<ScheduleOverrideRow> for ScheduleOverride {
+    type Error = DomainError;
+
+    fn try_from(value: ScheduleOverrideRow) -> Result<Self, Self::Error> {
+        Ok(ScheduleOverride {
+            target: OverrideSlot::new(value.id, value.target_vendor_id, value.target_hours)?,
+            replacement: OverrideSlot::new(value.id, value.food_vendor_id, value.open_hours)?,
+            id: value.id,
+            kind: value.kind,
+            brewery_id: value.brewery_id,
+            all_day: value.all_day,
+            reason: value.reason,
+            author: value.author,
+            expires_at: value.expires_at.map(|ts| ts.to_rfc3339()),
+            created_at: value.created_at.to_rfc3339(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScheduleOverrides {
+    overrides: Vec<ScheduleOverride>,
+}
+
+impl TryFrom<Vec<ScheduleOverrideRow>> for ScheduleOverrides {
+    type Error = DomainError;
+
+    fn try_from(value: Vec<ScheduleOverrideRow>) -> Result<Self, Self::Error> {
+        let overrides = value
+            .into_iter()
+            .map(ScheduleOverride::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ScheduleOverrides { overrides })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{BreweryFreshnessRow, ProvenanceRow, RepoError, ScheduleFilter, ScheduleWrite};
+    use sqlx::types::chrono::TimeZone;
+    use std::sync::Mutex;
+
+    struct MockOverrideRepo {
+        entry: Mutex<Option<ScheduleRow>>,
+    }
+
+    impl ScheduleRepo for MockOverrideRepo {
+        async fn get_schedules_within(
+            &self,
+            _start: jiff::Timestamp,
+            _end: jiff::Timestamp,
+            _tz: &str,
+            _filter: &ScheduleFilter,
+            _limit: i64,
+        ) -> Result<Vec<ScheduleRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_brewery_freshness(&self) -> Result<Vec<BreweryFreshnessRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_schedule(&self, id: Uuid) -> Result<Option<ScheduleRow>, RepoError> {
+            let mut entry = self.entry.lock().expect("lock poisoned");
+            Ok(entry.take_if(|entry| entry.id == id))
+        }
+
+        async fn create_schedule(
+            &self,
+            _schedule: &ScheduleWrite,
+        ) -> Result<ScheduleRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn update_schedule(
+            &self,
+            _id: Uuid,
+            _schedule: &ScheduleWrite,
+        ) -> Result<Option<ScheduleRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn cancel_schedule(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    impl ScheduleOverrideRepo for MockOverrideRepo {
+        async fn get_active_overrides(&self) -> Result<Vec<ScheduleOverrideRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn create_override(
+            &self,
+            schedule_override: &ScheduleOverrideWrite,
+        ) -> Result<ScheduleOverrideRow, RepoError> {
+            let hours = |slot: &SlotWrite| PgRange {
+                start: Bound::Included(chrono(slot.start)),
+                end: slot
+                    .end
+                    .map_or(Bound::Unbounded, |end| Bound::Excluded(chrono(end))),
+            };
+            let target = schedule_override.target.as_ref();
+            let replacement = schedule_override.replacement.as_ref();
+            Ok(ScheduleOverrideRow {
+                id: Uuid::from_u128(7),
+                kind: schedule_override.kind.to_string(),
+                brewery_id: schedule_override.brewery_id,
+                target_vendor_id: target.map(|slot| slot.food_vendor_id),
+                target_hours: target.map(hours),
+                food_vendor_id: replacement.map(|slot| slot.food_vendor_id),
+                open_hours: replacement.map(hours),
+                all_day: schedule_override.all_day,
+                reason: schedule_override.reason.clone(),
+                author: schedule_override.author.clone(),
+                expires_at: None,
+                created_at: Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap(),
+            })
+        }
+
+        async fn delete_override(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    fn chrono(ts: jiff::Timestamp) -> DateTime<Utc> {
+        DateTime::from_timestamp_micros(ts.as_microsecond()).expect("in range")
+    }
+
+    fn repo_with_entry() -> MockOverrideRepo {
+        MockOverrideRepo {
+            entry: Mutex::new(Some(ScheduleRow {
+                id: Uuid::from_u128(1),
+                brewery_id: Uuid::from_u128(2),
+                brewery_name: "Stoup Brewing".to_string(),
+                food_vendor_id: Uuid::from_u128(3),
+                food_vendor_name: "Where Ya At Matt".to_string(),
+                open_hours: PgRange {
+                    start: Bound::Included(Utc.with_ymd_and_hms(2026, 2, 1, 17, 0, 0).unwrap()),
+                    end: Bound::Excluded(Utc.with_ymd_and_hms(2026, 2, 1, 21, 0, 0).unwrap()),
+                },
+                all_day: false,
+                status: "confirmed".to_string(),
+                source: "seed".to_string(),
+                provenance: ProvenanceRow::default(),
+                updated_at: Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap(),
+                override_id: None,
+            })),
+        }
+    }
+
+    fn input(json: &str) -> CreateScheduleOverride {
+        serde_json::from_str(json).expect("valid override json")
+    }
+
+    #[tokio::test]
+    async fn cancel_copies_the_scraped_slot() {
+        let repo = repo_with_entry();
+        let input = input(
+            r#"{"kind": "cancel", "schedule_id": "00000000-0000-0000-0000-000000000001",
+                "reason": "Cancelled on Instagram"}"#,
+        );
+
+        let created = create_schedule_override(&repo, input, "ops")
+            .await
+            .expect("created");
+
+        assert_eq!(created.kind, "cancel");
+        assert_eq!(created.brewery_id, Uuid::from_u128(2));
+        assert_eq!(created.author, "ops");
+        let target = created.target.expect("target slot");
+        assert_eq!(target.vendor_id, Uuid::from_u128(3));
+        assert_eq!(target.start_at, "2026-02-01T17:00:00+00:00");
+        assert_eq!(target.end_at.as_deref(), Some("2026-02-01T21:00:00+00:00"));
+        assert!(created.replacement.is_none());
+    }
+
+    #[tokio::test]
+    async fn edit_keeps_scraped_values_for_omitted_fields() {
+        let repo = repo_with_entry();
+        let input = input(
+            r#"{"kind": "edit", "schedule_id": "00000000-0000-0000-0000-000000000001",
+                "vendor_id": "00000000-0000-0000-0000-000000000009", "end_at": null,
+                "reason": "Swapped trucks"}"#,
+        );
+
+        let created = create_schedule_override(&repo, input, "ops")
+            .await
+            .expect("created");
+
+        let replacement = created.replacement.expect("replacement slot");
+        assert_eq!(replacement.vendor_id, Uuid::from_u128(9));
+        assert_eq!(replacement.start_at, "2026-02-01T17:00:00+00:00");
+        assert_eq!(replacement.end_at, None);
+        assert_eq!(
+            created.target.expect("target slot").vendor_id,
+            Uuid::from_u128(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn add_rejects_blank_reason_and_inverted_hours() {
+        let repo = repo_with_entry();
+
+        let blank = input(
+            r#"{"kind": "add", "brewery_id": "00000000-0000-0000-0000-000000000002",
+                "vendor_id": "00000000-0000-0000-0000-000000000003",
+                "start_at": "2026-02-01T17:00:00Z", "reason": " "}"#,
+        );
+        let Err(DomainError::Validation(errors)) =
+            create_schedule_override(&repo, blank, "ops").await
+        else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "reason");
+
+        let inverted = input(
+            r#"{"kind": "add", "brewery_id": "00000000-0000-0000-0000-000000000002",
+                "vendor_id": "00000000-0000-0000-0000-000000000003",
+                "start_at": "2026-02-01T17:00:00Z", "end_at": "2026-02-01T16:00:00Z",
+                "reason": "Pop-up"}"#,
+        );
+        let Err(DomainError::Validation(errors)) =
+            create_schedule_override(&repo, inverted, "ops").await
+        else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "end_at");
+    }
+
+    #[tokio::test]
+    async fn cancel_reports_unknown_schedule_id() {
+        let repo = repo_with_entry();
+        let input = input(
+            r#"{"kind": "cancel", "schedule_id": "00000000-0000-0000-0000-000000000042",
+                "reason": "Gone"}"#,
+        );
+
+        let Err(DomainError::Validation(errors)) =
+            create_schedule_override(&repo, input, "ops").await
+        else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "schedule_id");
+    }
+}
@@ -1,101 +1,936 @@
-use crate::db::{ScheduleRepo, ScheduleRow};
-use anyhow::anyhow;
-use serde::Serialize;
+use crate::db::{
+    BreweryFreshnessRow, BreweryHoursRepo, ProvenanceRow, RepoError, ScheduleFilter, ScheduleRepo,
+    ScheduleRow, ScheduleWrite, StatsRepo,
+};
+use crate::domain::DomainError;
+use crate::domain::brewery_hours::{BusinessHours, ClosedDay, OpenAt, load_business_hours};
+use crate::domain::predictions::{PredictedEntry, predict_entries};
+use crate::domain::validation::{FieldError, Validator, double_option, patch};
+use crate::metrics;
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::ops::Bound;
+use std::time::Duration;
 use uuid::Uuid;
 
-pub async fn get_schedules<R: ScheduleRepo>(
+/// Most rows returned by one schedule query; pagination is deferred to Phase 7.
+const MAX_SCHEDULE_ROWS: usize = 100;
+
+/// Returns schedules overlapping `[start, end)`, clamped to the scraped horizon.
+///
+/// A brewery's data is assumed complete for `horizon_days` after its last scrape.
+/// Windows starting after every brewery's horizon are rejected; windows ending
+/// after it are clamped and marked `clamped_to_horizon`. With `include_predicted`, the
+/// window is kept and local days past each brewery's horizon get `predicted` entries
+/// instead. More than `MAX_SCHEDULE_ROWS` schedules or predictions are cut and marked
+/// `truncated`.
+/// Days in `tz` that a brewery is closed are listed with its freshness, and "until
+/// close" entries end when it closes.
+pub async fn get_schedules<R: ScheduleRepo + BreweryHoursRepo + StatsRepo>(
     db: &R,
     start: jiff::Timestamp,
-    duration_hours: u64,
-) -> anyhow::Result<VendorSchedules> {
-    let schedules = db.get_schedules_within(start, duration_hours).await?;
-    let daily_trucks = VendorSchedules::try_from(schedules).map_err(|e| anyhow!(e))?;
-    Ok(daily_trucks)
+    end: jiff::Timestamp,
+    filter: &ScheduleFilter,
+    include_predicted: bool,
+    horizon_days: u64,
+    tz: &TimeZone,
+) -> Result<SchedulesEnvelope, DomainError> {
+    let horizon = Duration::from_hours(horizon_days * 24);
+    let mut breweries: Vec<BreweryFreshness> = db
+        .get_brewery_freshness()
+        .await?
+        .into_iter()
+        .map(|row| BreweryFreshness::new(row, horizon))
+        .collect();
+
+    let window_start = to_chrono(start);
+    let mut window_end = to_chrono(end);
+    // Scraped entries are only looked up as far as some brewery's horizon.
+    let mut rows_end = window_end;
+    let mut clamped_to_horizon = false;
+    let mut truncated = false;
+
+    if let Some(latest) = breweries.iter().filter_map(|b| b.available_until).max() {
+        if window_start >= latest && !include_predicted {
+            return Err(DomainError::OutsideHorizon {
+                data_available_until: latest.to_rfc3339(),
+            });
+        }
+        if window_end > latest {
+            rows_end = latest.max(window_start);
+            if !include_predicted {
+                window_end = latest;
+                clamped_to_horizon = true;
+            }
+        }
+    }
+
+    let mut rows = if rows_end > window_start {
+        db.get_schedules_within(
+            start,
+            to_jiff(rows_end),
+            tz.iana_name().unwrap_or("UTC"),
+            filter,
+            MAX_SCHEDULE_ROWS as i64 + 1,
+        )
+        .await?
+    } else {
+        Vec::new()
+    };
+    if rows.len() > MAX_SCHEDULE_ROWS {
+        rows.truncate(MAX_SCHEDULE_ROWS);
+        truncated = true;
+    }
+
+    // Every brewery's lineup is known up to the earliest horizon; unknown if any
+    // brewery has never been scraped.
+    let data_available_until = breweries.iter().map(|b| b.available_until).min().flatten();
+
+    let days = local_days(start, to_jiff(window_end), tz);
+    let hours = load_business_hours(db, days[0], days[days.len() - 1]).await?;
+    for brewery in &mut breweries {
+        if let Some(hours) = hours.get(&brewery.brewery_id) {
+            brewery.closed_days = days
+                .iter()
+                .filter_map(|&day| hours.closed_day(day))
+                .collect();
+        }
+    }
+    let mut schedules = VendorSchedules::from_rows_lenient(rows);
+    schedules.end_at_close(&hours, tz);
+
+    let predicted = if include_predicted {
+        let horizons = breweries
+            .iter()
+            .filter_map(|b| Some((b.brewery_id, to_jiff(b.available_until?))))
+            .collect();
+        let mut predicted = predict_entries(
+            db,
+            start,
+            to_jiff(window_end),
+            &horizons,
+            &hours,
+            &filter.tags,
+            tz,
+        )
+        .await?;
+        if predicted.len() > MAX_SCHEDULE_ROWS {
+            predicted.truncate(MAX_SCHEDULE_ROWS);
+            truncated = true;
+        }
+        Some(predicted)
+    } else {
+        None
+    };
+
+    Ok(SchedulesEnvelope {
+        window_start: window_start.to_rfc3339(),
+        window_end: window_end.to_rfc3339(),
+        data_available_until: data_available_until.map(|ts| ts.to_rfc3339()),
+        clamped_to_horizon,
+        truncated,
+        breweries,
+        schedules,
+        predicted,
+    })
+}
+
+/// Local dates in `tz` that `[start, end)` touches.
+pub(super) fn local_days(start: jiff::Timestamp, end: jiff::Timestamp, tz: &TimeZone) -> Vec<Date> {
+    let first = start.to_zoned(tz.clone()).date();
+    let last = (end - jiff::SignedDuration::from_nanos(1))
+        .max(start)
+        .to_zoned(tz.clone())
+        .date();
+    first
+        .series(jiff::Span::new().days(1))
+        .take_while(|day| *day <= last)
+        .collect()
+}
+
+/// Source recorded on entries written through the admin API.
+pub(super) const MANUAL_SOURCE: &str = "manual";
+
+/// Source recorded on entries created by a bulk import.
+pub(super) const IMPORT_SOURCE: &str = "import";
+
+pub async fn create_schedule<R: ScheduleRepo>(
+    db: &R,
+    input: CreateSchedule,
+) -> Result<Schedule, DomainError> {
+    let write = input.into_write();
+    validate_schedule(write.start, write.end)?;
+    let row = db
+        .create_schedule(&write)
+        .await
+        .map_err(schedule_write_error)?;
+    Schedule::try_from(row)
+}
+
+pub async fn update_schedule<R: ScheduleRepo>(
+    db: &R,
+    id: Uuid,
+    input: UpdateSchedule,
+) -> Result<Schedule, DomainError> {
+    let current = db.get_schedule(id).await?.ok_or(DomainError::NotFound)?;
+    let write = input.apply(current)?;
+    validate_schedule(write.start, write.end)?;
+    let row = db
+        .update_schedule(id, &write)
+        .await
+        .map_err(schedule_write_error)?
+        .ok_or(DomainError::NotFound)?;
+    Schedule::try_from(row)
+}
+
+/// Cancels the entry; it stays listed with `include_cancelled`.
+pub async fn cancel_schedule<R: ScheduleRepo>(db: &R, id: Uuid) -> Result<(), DomainError> {
+    if db.cancel_schedule(id).await? {
+        Ok(())
+    } else {
+        Err(DomainError::NotFound)
+    }
+}
+
+pub(super) fn validate_schedule(
+    start: jiff::Timestamp,
+    end: Option<jiff::Timestamp>,
+) -> Result<(), DomainError> {
+    let mut v = Validator::default();
+    if let Some(end) = end {
+        v.check(end > start, "end_at", "must be after start_at");
+    }
+    v.finish()
+}
+
+/// Reports unknown parents as field errors instead of a generic conflict.
+pub(super) fn schedule_write_error(e: RepoError) -> DomainError {
+    let field = match &e {
+        RepoError::ForeignKeyViolation { constraint } if constraint.contains("brewery_id") => {
+            "brewery_id"
+        }
+        RepoError::ForeignKeyViolation { constraint } if constraint.contains("food_vendor_id") => {
+            "vendor_id"
+        }
+        _ => return e.into(),
+    };
+    DomainError::Validation(vec![FieldError {
+        field,
+        message: "does not exist".to_string(),
+    }])
+}
+
+/// Admin input for a new schedule entry. Omitting `end_at` means "until close".
+#[derive(Deserialize)]
+pub struct CreateSchedule {
+    brewery_id: Uuid,
+    vendor_id: Uuid,
+    start_at: jiff::Timestamp,
+    end_at: Option<jiff::Timestamp>,
+    #[serde(default)]
+    all_day: bool,
+    #[serde(default)]
+    status: ScheduleStatus,
+}
+
+impl CreateSchedule {
+    fn into_write(self) -> ScheduleWrite {
+        ScheduleWrite {
+            brewery_id: self.brewery_id,
+            food_vendor_id: self.vendor_id,
+            start: self.start_at,
+            end: self.end_at,
+            all_day: self.all_day,
+            status: self.status.as_str().to_string(),
+            source: MANUAL_SOURCE.to_string(),
+            source_kind: SourceKind::Manual.as_str().to_string(),
+        }
+    }
+}
+
+/// Admin input for a partial schedule entry update. `"end_at": null` switches the
+/// entry to "until close".
+#[derive(Deserialize)]
+pub struct UpdateSchedule {
+    brewery_id: Option<Uuid>,
+    vendor_id: Option<Uuid>,
+    start_at: Option<jiff::Timestamp>,
+    #[serde(default, deserialize_with = "double_option")]
+    end_at: Option<Option<jiff::Timestamp>>,
+    all_day: Option<bool>,
+    status: Option<ScheduleStatus>,
+}
+
+impl UpdateSchedule {
+    fn apply(self, current: ScheduleRow) -> Result<ScheduleWrite, DomainError> {
+        let (start, end) = row_hours(&current)?;
+
+        Ok(ScheduleWrite {
+            brewery_id: patch(current.brewery_id, self.brewery_id),
+            food_vendor_id: patch(current.food_vendor_id, self.vendor_id),
+            start: patch(start, self.start_at),
+            end: patch(end, self.end_at),
+            all_day: patch(current.all_day, self.all_day),
+            status: patch(current.status, self.status.map(|s| s.as_str().to_string())),
+            source: MANUAL_SOURCE.to_string(),
+            source_kind: SourceKind::Manual.as_str().to_string(),
+        })
+    }
+}
+
+/// Start and optional end of a stored entry's `open_hours`.
+pub(super) fn row_hours(
+    row: &ScheduleRow,
+) -> Result<(jiff::Timestamp, Option<jiff::Timestamp>), DomainError> {
+    range_hours(row.id, &row.open_hours)
+}
+
+/// Start and optional end of the `open_hours` of entry `id`.
+pub(super) fn range_hours(
+    id: Uuid,
+    open_hours: &PgRange<DateTime<Utc>>,
+) -> Result<(jiff::Timestamp, Option<jiff::Timestamp>), DomainError> {
+    let start = match open_hours.start {
+        Bound::Included(ts) | Bound::Excluded(ts) => to_jiff(ts),
+        Bound::Unbounded => {
+            return Err(DomainError::InvalidRow {
+                id,
+                reason: "schedule open_hours start is unbounded",
+            });
+        }
+    };
+    let end = match open_hours.end {
+        Bound::Included(ts) | Bound::Excluded(ts) => Some(to_jiff(ts)),
+        Bound::Unbounded => None,
+    };
+    Ok((start, end))
+}
+
+pub(super) fn to_chrono(ts: jiff::Timestamp) -> DateTime<Utc> {
+    DateTime::from_timestamp_micros(ts.as_microsecond())
+        .expect("jiff timestamps fit in chrono's range")
+}
+
+pub(super) fn to_jiff(ts: DateTime<Utc>) -> jiff::Timestamp {
+    jiff::Timestamp::from_microsecond(ts.timestamp_micros())
+        .expect("stored timestamps are within jiff's range")
+}
+
+/// Whether a vendor is expected to show up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleStatus {
+    #[default]
+    Confirmed,
+    /// Announced but not yet firm, such as "TBD" or "maybe" on a brewery's calendar.
+    Tentative,
+    /// Kept for history; hidden from schedules unless `include_cancelled` is set.
+    Cancelled,
+}
+
+impl ScheduleStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScheduleStatus::Confirmed => "confirmed",
+            ScheduleStatus::Tentative => "tentative",
+            ScheduleStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub(super) fn from_row(id: Uuid, value: &str) -> Result<Self, DomainError> {
+        match value {
+            "confirmed" => Ok(ScheduleStatus::Confirmed),
+            "tentative" => Ok(ScheduleStatus::Tentative),
+            "cancelled" => Ok(ScheduleStatus::Cancelled),
+            _ => Err(DomainError::InvalidRow {
+                id,
+                reason: "schedule status is unknown",
+            }),
+        }
+    }
+}
+
+/// What kind of source produced an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// Parsed from a brewery's web page or social post.
+    Scraper,
+    /// Read from a brewery's iCalendar feed.
+    Ics,
+    /// Written through the admin API, a schedule override or a bulk import.
+    Manual,
+    /// Materialized from a recurring schedule.
+    Recurring,
+    /// Guessed from history past the scraped horizon; never stored.
+    Prediction,
+}
+
+impl SourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceKind::Scraper => "scraper",
+            SourceKind::Ics => "ics",
+            SourceKind::Manual => "manual",
+            SourceKind::Recurring => "recurring",
+            SourceKind::Prediction => "prediction",
+        }
+    }
+
+    fn from_row(id: Uuid, value: &str) -> Result<Self, DomainError> {
+        match value {
+            "scraper" => Ok(SourceKind::Scraper),
+            "ics" => Ok(SourceKind::Ics),
+            "manual" => Ok(SourceKind::Manual),
+            "recurring" => Ok(SourceKind::Recurring),
+            _ => Err(DomainError::InvalidRow {
+                id,
+                reason: "schedule source kind is unknown",
+            }),
+        }
+    }
+}
+
+/// Where an entry came from, so users can click through to the original post and bad
+/// data can be traced back to the parser that produced it.
+#[derive(Debug, Serialize)]
+pub struct Provenance {
+    kind: SourceKind,
+    /// The writer that recorded the entry, such as a scraper name, `manual` or `import`.
+    name: String,
+    url: Option<String>,
+    /// The scrape run that last added or changed the entry.
+    scrape_run_id: Option<Uuid>,
+    parser_version: Option<String>,
+    /// Identifies the page or feed content that run parsed, such as `sha256:<hex>`.
+    snapshot_id: Option<String>,
+    /// From 0 to 1; entries nobody doubts are 1.
+    pub(super) confidence: f64,
+}
+
+impl Provenance {
+    fn from_row(id: Uuid, name: String, row: ProvenanceRow) -> Result<Self, DomainError> {
+        Ok(Provenance {
+            kind: SourceKind::from_row(id, &row.source_kind)?,
+            name,
+            url: row.source_url,
+            scrape_run_id: row.scrape_run_id,
+            parser_version: row.parser_version,
+            snapshot_id: row.snapshot_id,
+            confidence: row.confidence,
+        })
+    }
+
+    pub(super) fn prediction(confidence: f64) -> Self {
+        Provenance {
+            kind: SourceKind::Prediction,
+            name: SourceKind::Prediction.as_str().to_string(),
+            url: None,
+            scrape_run_id: None,
+            parser_version: None,
+            snapshot_id: None,
+            confidence,
+        }
+    }
 }
 
 #[derive(Serialize)]
 pub struct Schedule {
+    id: Uuid,
     brewery_name: String,
     brewery_id: Uuid,
     vendor_name: String,
     vendor_id: Uuid,
     start_at: String,
-    end_at: String,
+    end_at: Option<String>,
+    all_day: bool,
+    until_close: bool,
+    status: ScheduleStatus,
+    source: Provenance,
     updated_at: String,
+    /// The schedule override that added or edited this entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    override_id: Option<Uuid>,
+    #[serde(skip)]
+    start: jiff::Timestamp,
 }
 
 impl TryFrom<ScheduleRow> for Schedule {
-    type Error = &'static str;
+    type Error = DomainError;
 
     fn try_from(value: ScheduleRow) -> Result<Self, Self::Error> {
         if value.brewery_name.is_empty() {
-            return Err("brewery name is empty");
+            return Err(DomainError::InvalidRow {
+                id: value.id,
+                reason: "brewery name is empty",
+            });
         }
 
         if value.food_vendor_name.is_empty() {
-            return Err("vendor name is empty");
+            return Err(DomainError::InvalidRow {
+                id: value.id,
+                reason: "vendor name is empty",
+            });
         }
 
+        let status = ScheduleStatus::from_row(value.id, &value.status)?;
+        let source = Provenance::from_row(value.id, value.source, value.provenance)?;
         let open_hours = value.open_hours;
 
-        let start_at = match open_hours.start {
-            Bound::Included(ts) | Bound::Excluded(ts) => ts.to_rfc3339(),
-            Bound::Unbounded => return Err("schedule open_hours start is unbounded"),
+        let start = match open_hours.start {
+            Bound::Included(ts) | Bound::Excluded(ts) => ts,
+            Bound::Unbounded => {
+                return Err(DomainError::InvalidRow {
+                    id: value.id,
+                    reason: "schedule open_hours start is unbounded",
+                });
+            }
         };
 
-        let end_at = match open_hours.end {
-            Bound::Included(ts) | Bound::Excluded(ts) => ts.to_rfc3339(),
-            Bound::Unbounded => return Err("schedule open_hours end is unbounded"),
+        // Stored ranges are normalized to `[start, end)`; an unbounded end means the
+        // vendor stays until the brewery closes.
+        let (end_at, until_close) = match open_hours.end {
+            Bound::Included(ts) | Bound::Excluded(ts) => (Some(ts.to_rfc3339()), false),
+            Bound::Unbounded => (None, true),
         };
 
         Ok(Schedule {
+            id: value.id,
             brewery_name: value.brewery_name,
             brewery_id: value.brewery_id,
             vendor_name: value.food_vendor_name,
             vendor_id: value.food_vendor_id,
-            start_at,
+            start_at: start.to_rfc3339(),
             end_at,
+            all_day: value.all_day,
+            until_close,
+            status,
+            source,
             updated_at: value.updated_at.to_rfc3339(),
+            override_id: value.override_id,
+            start: to_jiff(start),
         })
     }
 }
 
+/// Scrape freshness for one brewery.
+#[derive(Serialize)]
+pub struct BreweryFreshness {
+    brewery_id: Uuid,
+    brewery_name: String,
+    last_scraped_at: Option<String>,
+    data_available_until: Option<String>,
+    /// Local days in the window the brewery is closed.
+    closed_days: Vec<ClosedDay>,
+    #[serde(skip)]
+    available_until: Option<DateTime<Utc>>,
+}
+
+impl BreweryFreshness {
+    fn new(row: BreweryFreshnessRow, horizon: Duration) -> Self {
+        let available_until = row.last_scraped_at.map(|ts| ts + horizon);
+        BreweryFreshness {
+            brewery_id: row.brewery_id,
+            brewery_name: row.brewery_name,
+            last_scraped_at: row.last_scraped_at.map(|ts| ts.to_rfc3339()),
+            data_available_until: available_until.map(|ts| ts.to_rfc3339()),
+            closed_days: Vec::new(),
+            available_until,
+        }
+    }
+}
+
+/// Schedule results plus what the server knows about the requested window, so
+/// clients can tell "no trucks" from "not scraped that far ahead yet".
+#[derive(Serialize)]
+pub struct SchedulesEnvelope {
+    window_start: String,
+    window_end: String,
+    data_available_until: Option<String>,
+    /// The window was cut short at the data horizon.
+    clamped_to_horizon: bool,
+    /// Schedules or predictions were cut at `MAX_SCHEDULE_ROWS`.
+    truncated: bool,
+    breweries: Vec<BreweryFreshness>,
+    #[serde(flatten)]
+    schedules: VendorSchedules,
+    /// Guesses past each brewery's horizon, kept apart from scraped entries; only
+    /// present when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    predicted: Option<Vec<PredictedEntry>>,
+}
+
+impl SchedulesEnvelope {
+    pub fn without_warnings(mut self) -> Self {
+        self.schedules = self.schedules.without_warnings();
+        self
+    }
+}
+
+/// A row dropped by lenient mapping, reported back to the client on request.
+#[derive(Debug, Serialize)]
+pub struct RowWarning {
+    id: Uuid,
+    reason: String,
+}
+
 #[derive(Serialize)]
 pub struct VendorSchedules {
     schedules: Vec<Schedule>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<RowWarning>,
+}
+
+impl VendorSchedules {
+    /// Maps rows one by one, dropping invalid rows instead of failing the whole batch.
+    /// Dropped rows are logged with their ids, counted, and kept as `warnings`.
+    pub fn from_rows_lenient(rows: Vec<ScheduleRow>) -> Self {
+        let mut schedules = Vec::with_capacity(rows.len());
+        let mut warnings = Vec::new();
+        for row in rows {
+            let id = row.id;
+            match Schedule::try_from(row) {
+                Ok(schedule) => schedules.push(schedule),
+                Err(e) => {
+                    warn!("Skipping invalid schedule row {}: {}", id, e);
+                    let reason = match e {
+                        DomainError::InvalidRow { reason, .. } => reason.to_string(),
+                        other => other.to_string(),
+                    };
+                    warnings.push(RowWarning { id, reason });
+                }
+            }
+        }
+
+        if !warnings.is_empty() {
+            metrics::record_invalid_schedule_rows(warnings.len() as u64);
+        }
+
+        VendorSchedules {
+            schedules,
+            warnings,
+        }
+    }
+
+    /// Drops collected warnings so they are not serialized.
+    pub fn without_warnings(mut self) -> Self {
+        self.warnings.clear();
+        self
+    }
+
+    /// Ends "until close" entries when their brewery closes, where its hours are known.
+    fn end_at_close(&mut self, hours: &HashMap<Uuid, BusinessHours>, tz: &TimeZone) {
+        for schedule in self.schedules.iter_mut().filter(|s| s.until_close) {
+            if let Some(hours) = hours.get(&schedule.brewery_id)
+                && let OpenAt::Open { until } = hours.open_at(schedule.start, tz)
+            {
+                schedule.end_at = Some(to_chrono(until).to_rfc3339());
+            }
+        }
+    }
 }
 
 impl TryFrom<Vec<ScheduleRow>> for VendorSchedules {
-    type Error = &'static str;
+    type Error = DomainError;
 
     fn try_from(value: Vec<ScheduleRow>) -> Result<Self, Self::Error> {
         let schedules = value
             .into_iter()
             .map(Schedule::try_from)
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(VendorSchedules { schedules })
+        Ok(VendorSchedules {
+            schedules,
+            warnings: Vec::new(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sqlx::postgres::types::PgRange;
+    use crate::db::{
+        AppearanceStatsRow, BreweryClosureRow, BreweryClosureWrite, RepoError, StatsSubject,
+        TagFilter, WeekdayPatternRow, WeeklyHoursRow, WeeklyHoursWrite,
+    };
     use sqlx::types::chrono::{TimeZone, Utc};
     use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Weekly open periods as brewery, ISO weekday, opens at and closes at.
+    type WeeklyPeriods = Vec<(Uuid, i16, &'static str, &'static str)>;
 
     struct MockScheduleRepo {
         rows: Mutex<Option<Vec<ScheduleRow>>>,
+        freshness: Vec<(Uuid, Option<DateTime<Utc>>)>,
+        weekly: WeeklyPeriods,
+        patterns: Vec<WeekdayPatternRow>,
     }
 
     impl ScheduleRepo for MockScheduleRepo {
         async fn get_schedules_within(
             &self,
             _start: jiff::Timestamp,
-            _duration_hours: u64,
-        ) -> anyhow::Result<Vec<ScheduleRow>> {
-            Ok(self.rows.lock().expect("lock poisoned").take().unwrap_or_default())
+            _end: jiff::Timestamp,
+            _tz: &str,
+            _filter: &ScheduleFilter,
+            _limit: i64,
+        ) -> Result<Vec<ScheduleRow>, RepoError> {
+            Ok(self
+                .rows
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or_default())
+        }
+
+        async fn get_brewery_freshness(&self) -> Result<Vec<BreweryFreshnessRow>, RepoError> {
+            Ok(self
+                .freshness
+                .iter()
+                .map(|(brewery_id, last_scraped_at)| BreweryFreshnessRow {
+                    brewery_id: *brewery_id,
+                    brewery_name: "Stoup Brewing".to_string(),
+                    last_scraped_at: *last_scraped_at,
+                })
+                .collect())
+        }
+
+        async fn get_schedule(&self, id: Uuid) -> Result<Option<ScheduleRow>, RepoError> {
+            let mut rows = self.rows.lock().expect("lock poisoned");
+            let rows = rows.get_or_insert_with(Vec::new);
+            Ok(rows
+                .iter()
+                .position(|row| row.id == id)
+                .map(|index| rows.remove(index)))
+        }
+
+        async fn create_schedule(
+            &self,
+            schedule: &ScheduleWrite,
+        ) -> Result<ScheduleRow, RepoError> {
+            Ok(row_from_write(Uuid::from_u128(99), schedule))
+        }
+
+        async fn update_schedule(
+            &self,
+            id: Uuid,
+            schedule: &ScheduleWrite,
+        ) -> Result<Option<ScheduleRow>, RepoError> {
+            Ok(Some(row_from_write(id, schedule)))
+        }
+
+        async fn cancel_schedule(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    struct UnavailableScheduleRepo;
+
+    impl ScheduleRepo for UnavailableScheduleRepo {
+        async fn get_schedules_within(
+            &self,
+            _start: jiff::Timestamp,
+            _end: jiff::Timestamp,
+            _tz: &str,
+            _filter: &ScheduleFilter,
+            _limit: i64,
+        ) -> Result<Vec<ScheduleRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn get_brewery_freshness(&self) -> Result<Vec<BreweryFreshnessRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn get_schedule(&self, _id: Uuid) -> Result<Option<ScheduleRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn create_schedule(
+            &self,
+            _schedule: &ScheduleWrite,
+        ) -> Result<ScheduleRow, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn update_schedule(
+            &self,
+            _id: Uuid,
+            _schedule: &ScheduleWrite,
+        ) -> Result<Option<ScheduleRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn cancel_schedule(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+    }
+
+    impl BreweryHoursRepo for MockScheduleRepo {
+        async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(self
+                .weekly
+                .iter()
+                .map(
+                    |&(brewery_id, weekday, opens_at, closes_at)| WeeklyHoursRow {
+                        id: Uuid::new_v4(),
+                        brewery_id,
+                        weekday,
+                        opens_at: opens_at.parse().unwrap(),
+                        closes_at: closes_at.parse().unwrap(),
+                    },
+                )
+                .collect())
+        }
+
+        async fn get_brewery_closures(
+            &self,
+            _from: jiff::civil::Date,
+            _until: jiff::civil::Date,
+        ) -> Result<Vec<BreweryClosureRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn replace_weekly_hours(
+            &self,
+            _brewery_id: Uuid,
+            _hours: &[WeeklyHoursWrite],
+        ) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn upsert_brewery_closure(
+            &self,
+            _closure: &BreweryClosureWrite,
+        ) -> Result<BreweryClosureRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn delete_brewery_closure(
+            &self,
+            _brewery_id: Uuid,
+            _id: Uuid,
+        ) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    impl BreweryHoursRepo for UnavailableScheduleRepo {
+        async fn get_weekly_hours(&self) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn get_brewery_closures(
+            &self,
+            _from: jiff::civil::Date,
+            _until: jiff::civil::Date,
+        ) -> Result<Vec<BreweryClosureRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn replace_weekly_hours(
+            &self,
+            _brewery_id: Uuid,
+            _hours: &[WeeklyHoursWrite],
+        ) -> Result<Vec<WeeklyHoursRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn upsert_brewery_closure(
+            &self,
+            _closure: &BreweryClosureWrite,
+        ) -> Result<BreweryClosureRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn delete_brewery_closure(
+            &self,
+            _brewery_id: Uuid,
+            _id: Uuid,
+        ) -> Result<bool, RepoError> {
+            Ok(false)
+        }
+    }
+
+    impl StatsRepo for MockScheduleRepo {
+        async fn get_appearance_stats(
+            &self,
+            _subject: StatsSubject,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+        ) -> Result<Vec<AppearanceStatsRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_weekday_patterns(
+            &self,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+            _tags: &TagFilter,
+        ) -> Result<Vec<WeekdayPatternRow>, RepoError> {
+            Ok(self
+                .patterns
+                .iter()
+                .map(|row| WeekdayPatternRow {
+                    brewery_name: row.brewery_name.clone(),
+                    food_vendor_name: row.food_vendor_name.clone(),
+                    ..*row
+                })
+                .collect())
+        }
+    }
+
+    impl StatsRepo for UnavailableScheduleRepo {
+        async fn get_appearance_stats(
+            &self,
+            _subject: StatsSubject,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+        ) -> Result<Vec<AppearanceStatsRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+
+        async fn get_weekday_patterns(
+            &self,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+            _tags: &TagFilter,
+        ) -> Result<Vec<WeekdayPatternRow>, RepoError> {
+            Err(RepoError::Unavailable(sqlx::Error::PoolTimedOut))
+        }
+    }
+
+    fn row_from_write(id: Uuid, schedule: &ScheduleWrite) -> ScheduleRow {
+        ScheduleRow {
+            id,
+            brewery_id: schedule.brewery_id,
+            brewery_name: "Stoup Brewing".to_string(),
+            food_vendor_id: schedule.food_vendor_id,
+            food_vendor_name: "Where Ya At Matt".to_string(),
+            open_hours: PgRange {
+                start: Bound::Included(to_chrono(schedule.start)),
+                end: schedule
+                    .end
+                    .map_or(Bound::Unbounded, |end| Bound::Excluded(to_chrono(end))),
+            },
+            all_day: schedule.all_day,
+            status: schedule.status.clone(),
+            source: schedule.source.clone(),
+            provenance: ProvenanceRow {
+                source_kind: schedule.source_kind.clone(),
+                ..ProvenanceRow::default()
+            },
+            updated_at: to_chrono(schedule.start),
+            override_id: None,
         }
     }
 
@@ -123,38 +958,619 @@ mod tests {
                 start: Bound::Included(start),
                 end: Bound::Excluded(end),
             },
+            all_day: false,
+            status: "confirmed".to_string(),
             source: "seed".to_string(),
+            provenance: ProvenanceRow::default(),
             updated_at,
+            override_id: None,
         }
     }
 
     #[tokio::test]
     async fn get_schedules_maps_timestamps_and_names() {
         let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
             rows: Mutex::new(Some(vec![sample_schedule_row(
                 "Stoup Brewing",
                 "El Pirata Tortas Y Burritos",
             )])),
+            freshness: Vec::new(),
         };
 
         let start = "2026-02-01T17:00:00Z"
             .parse::<jiff::Timestamp>()
             .expect("valid timestamp");
-        let result = get_schedules(&repo, start, 24).await.expect("valid result");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
 
-        assert_eq!(result.schedules.len(), 1);
-        let first = &result.schedules[0];
+        assert_eq!(result.schedules.schedules.len(), 1);
+        let first = &result.schedules.schedules[0];
         assert_eq!(first.brewery_name, "Stoup Brewing");
         assert_eq!(first.vendor_name, "El Pirata Tortas Y Burritos");
         assert_eq!(first.start_at, "2026-02-01T17:00:00+00:00");
-        assert_eq!(first.end_at, "2026-02-02T03:00:00+00:00");
+        assert_eq!(first.end_at.as_deref(), Some("2026-02-02T03:00:00+00:00"));
+        assert!(!first.all_day);
+        assert!(!first.until_close);
         assert_eq!(first.updated_at, "2026-02-01T18:30:00+00:00");
     }
 
+    #[test]
+    fn schedule_try_from_maps_provenance() {
+        let run_id = Uuid::from_u128(9);
+        let mut row = sample_schedule_row("Stoup Brewing", "El Pirata Tortas Y Burritos");
+        row.source = "ics".to_string();
+        row.provenance = ProvenanceRow {
+            source_kind: "ics".to_string(),
+            source_url: Some("https://example.com/trucks.ics".to_string()),
+            scrape_run_id: Some(run_id),
+            parser_version: Some("ics/1".to_string()),
+            confidence: 0.8,
+            snapshot_id: Some("sha256:ab12".to_string()),
+        };
+
+        let source = Schedule::try_from(row).expect("valid row").source;
+
+        assert_eq!(source.kind, SourceKind::Ics);
+        assert_eq!(source.name, "ics");
+        assert_eq!(
+            source.url.as_deref(),
+            Some("https://example.com/trucks.ics")
+        );
+        assert_eq!(source.scrape_run_id, Some(run_id));
+        assert_eq!(source.parser_version.as_deref(), Some("ics/1"));
+        assert_eq!(source.snapshot_id.as_deref(), Some("sha256:ab12"));
+        assert_eq!(source.confidence, 0.8);
+
+        let mut unknown = sample_schedule_row("Stoup Brewing", "El Pirata Tortas Y Burritos");
+        unknown.provenance.source_kind = "prediction".to_string();
+        assert!(Schedule::try_from(unknown).is_err());
+    }
+
     #[test]
     fn schedule_try_from_rejects_empty_vendor_name() {
         let row = sample_schedule_row("Stoup Brewing", "");
         let result = Schedule::try_from(row);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn schedule_try_from_reports_row_id() {
+        let mut row = sample_schedule_row("", "Where Ya At Matt");
+        row.id = Uuid::from_u128(7);
+        let result = Schedule::try_from(row);
+        assert!(matches!(
+            result,
+            Err(DomainError::InvalidRow { id, .. }) if id == Uuid::from_u128(7)
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_schedules_reports_unavailable_repo() {
+        let start = "2026-02-01T17:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &UnavailableScheduleRepo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await;
+        assert!(matches!(result, Err(DomainError::RepoUnavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn get_schedules_skips_invalid_rows() {
+        let mut unbounded = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        unbounded.id = Uuid::from_u128(2);
+        unbounded.open_hours.start = Bound::Unbounded;
+        let mut unnamed = sample_schedule_row("Stoup Brewing", "");
+        unnamed.id = Uuid::from_u128(3);
+
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(vec![
+                sample_schedule_row("Stoup Brewing", "El Pirata Tortas Y Burritos"),
+                unbounded,
+                unnamed,
+                sample_schedule_row("Bale Breaker x Yonder Cider", "Tacos & Beer"),
+            ])),
+            freshness: Vec::new(),
+        };
+
+        let before = metrics::invalid_schedule_rows();
+        let start = "2026-02-01T17:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        let result = result.schedules;
+        assert_eq!(result.schedules.len(), 2);
+        assert_eq!(
+            result.schedules[0].vendor_name,
+            "El Pirata Tortas Y Burritos"
+        );
+        assert_eq!(result.schedules[1].vendor_name, "Tacos & Beer");
+        let warning_ids: Vec<_> = result.warnings.iter().map(|w| w.id).collect();
+        assert_eq!(warning_ids, vec![Uuid::from_u128(2), Uuid::from_u128(3)]);
+        assert_eq!(result.warnings[1].reason, "vendor name is empty");
+        assert!(metrics::invalid_schedule_rows() >= before + 2);
+    }
+
+    #[test]
+    fn without_warnings_keeps_schedules() {
+        let result = VendorSchedules::from_rows_lenient(vec![
+            sample_schedule_row("Stoup Brewing", "Where Ya At Matt"),
+            sample_schedule_row("Stoup Brewing", ""),
+        ])
+        .without_warnings();
+
+        assert_eq!(result.schedules.len(), 1);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn schedule_try_from_treats_inclusive_end_as_end() {
+        let mut row = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        if let Bound::Excluded(end) = row.open_hours.end {
+            row.open_hours.end = Bound::Included(end);
+        }
+
+        let schedule = Schedule::try_from(row).expect("valid schedule");
+        assert_eq!(
+            schedule.end_at.as_deref(),
+            Some("2026-02-02T03:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn schedule_try_from_maps_unbounded_end_to_until_close() {
+        let mut row = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        row.open_hours.end = Bound::Unbounded;
+
+        let schedule = Schedule::try_from(row).expect("valid schedule");
+        assert!(schedule.until_close);
+        assert_eq!(schedule.end_at, None);
+        assert_eq!(schedule.start_at, "2026-02-01T17:00:00+00:00");
+    }
+
+    #[test]
+    fn schedule_try_from_passes_through_all_day() {
+        let mut row = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        row.all_day = true;
+
+        let schedule = Schedule::try_from(row).expect("valid schedule");
+        assert!(schedule.all_day);
+    }
+
+    fn scraped_at(day: u32) -> Option<DateTime<Utc>> {
+        Utc.with_ymd_and_hms(2026, 2, day, 0, 0, 0).single()
+    }
+
+    #[tokio::test]
+    async fn get_schedules_reports_window_and_freshness() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(vec![sample_schedule_row(
+                "Stoup Brewing",
+                "Where Ya At Matt",
+            )])),
+            freshness: vec![
+                (Uuid::from_u128(1), scraped_at(1)),
+                (Uuid::from_u128(2), scraped_at(3)),
+            ],
+        };
+
+        let start = "2026-02-01T17:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        assert_eq!(result.window_start, "2026-02-01T17:00:00+00:00");
+        assert_eq!(result.window_end, "2026-02-02T17:00:00+00:00");
+        assert!(!result.truncated);
+        assert!(!result.clamped_to_horizon);
+        assert_eq!(
+            result.data_available_until.as_deref(),
+            Some("2026-02-08T00:00:00+00:00")
+        );
+        assert_eq!(
+            result.breweries[1].last_scraped_at.as_deref(),
+            Some("2026-02-03T00:00:00+00:00")
+        );
+        assert_eq!(
+            result.breweries[1].data_available_until.as_deref(),
+            Some("2026-02-10T00:00:00+00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_schedules_never_scraped_breweries_do_not_extend_horizon() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: vec![
+                (Uuid::from_u128(1), scraped_at(1)),
+                (Uuid::from_u128(2), None),
+            ],
+        };
+
+        let start = "2026-03-01T00:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await;
+
+        assert!(matches!(result, Err(DomainError::OutsideHorizon { .. })));
+    }
+
+    #[tokio::test]
+    async fn get_schedules_clamps_window_to_horizon() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: vec![(Uuid::from_u128(1), scraped_at(1))],
+        };
+
+        let start = "2026-02-07T00:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(48),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        assert_eq!(result.window_end, "2026-02-08T00:00:00+00:00");
+        assert!(result.clamped_to_horizon);
+        assert!(!result.truncated);
+    }
+
+    #[tokio::test]
+    async fn get_schedules_rejects_window_beyond_horizon() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: vec![(Uuid::from_u128(1), scraped_at(1))],
+        };
+
+        let start = "2026-02-08T00:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(DomainError::OutsideHorizon { data_available_until })
+                if data_available_until == "2026-02-08T00:00:00+00:00"
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_schedules_predicts_past_the_horizon() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: vec![WeekdayPatternRow {
+                brewery_id: Uuid::from_u128(1),
+                brewery_name: "Stoup Brewing".to_string(),
+                food_vendor_id: Uuid::from_u128(2),
+                food_vendor_name: "Where Ya At Matt".to_string(),
+                // Sunday, like 2026-02-08.
+                weekday: 7,
+                appearances: 3,
+                hosted_days: 5,
+                usual_start: "17:00:00".parse().ok(),
+                usual_end: "21:00:00".parse().ok(),
+            }],
+            rows: Mutex::new(None),
+            freshness: vec![(Uuid::from_u128(1), scraped_at(1))],
+        };
+
+        let start = "2026-02-08T00:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            true,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        assert!(result.schedules.schedules.is_empty());
+        assert_eq!(result.window_end, "2026-02-09T00:00:00+00:00");
+        assert!(!result.truncated);
+        assert!(!result.clamped_to_horizon);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["predicted"][0]["vendor_name"], "Where Ya At Matt");
+        assert_eq!(
+            json["predicted"][0]["start_at"],
+            "2026-02-08T17:00:00+00:00"
+        );
+        assert_eq!(json["predicted"][0]["source"]["kind"], "prediction");
+        assert_eq!(json["predicted"][0]["source"]["confidence"], 0.5);
+    }
+
+    #[tokio::test]
+    async fn get_schedules_applies_brewery_hours() {
+        let mut until_close = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        until_close.open_hours.end = Bound::Unbounded;
+        let repo = MockScheduleRepo {
+            // Open Sundays only; 2026-02-01 is a Sunday.
+            weekly: vec![(Uuid::nil(), 7, "12:00:00", "22:00:00")],
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(vec![until_close])),
+            freshness: vec![(Uuid::nil(), scraped_at(1))],
+        };
+
+        let start = "2026-02-01T17:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(48),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        let schedule = &result.schedules.schedules[0];
+        assert!(schedule.until_close);
+        assert_eq!(
+            schedule.end_at.as_deref(),
+            Some("2026-02-01T22:00:00+00:00")
+        );
+        assert_eq!(
+            serde_json::to_value(&result.breweries[0].closed_days).unwrap(),
+            serde_json::json!([
+                { "date": "2026-02-02", "reason": null },
+                { "date": "2026-02-03", "reason": null },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn get_schedules_truncates_at_row_cap() {
+        let rows = (0..=MAX_SCHEDULE_ROWS)
+            .map(|_| sample_schedule_row("Stoup Brewing", "Where Ya At Matt"))
+            .collect();
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(rows)),
+            freshness: Vec::new(),
+        };
+
+        let start = "2026-02-01T17:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp");
+        let result = get_schedules(
+            &repo,
+            start,
+            start + Duration::from_hours(24),
+            &ScheduleFilter::default(),
+            false,
+            7,
+            &jiff::tz::TimeZone::UTC,
+        )
+        .await
+        .expect("valid result");
+
+        assert_eq!(result.schedules.schedules.len(), MAX_SCHEDULE_ROWS);
+        assert!(result.truncated);
+        assert!(!result.clamped_to_horizon);
+        assert_eq!(result.data_available_until, None);
+    }
+
+    fn create_input(start_at: &str, end_at: Option<&str>) -> CreateSchedule {
+        CreateSchedule {
+            brewery_id: Uuid::from_u128(1),
+            vendor_id: Uuid::from_u128(2),
+            start_at: start_at.parse().expect("valid timestamp"),
+            end_at: end_at.map(|end| end.parse().expect("valid timestamp")),
+            all_day: false,
+            status: ScheduleStatus::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_schedule_rejects_end_before_start() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: Vec::new(),
+        };
+
+        let result = create_schedule(
+            &repo,
+            create_input("2026-02-01T20:00:00Z", Some("2026-02-01T17:00:00Z")),
+        )
+        .await;
+
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "end_at");
+    }
+
+    #[tokio::test]
+    async fn create_schedule_without_end_is_until_close() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: Vec::new(),
+        };
+
+        let schedule = create_schedule(&repo, create_input("2026-02-01T17:00:00Z", None))
+            .await
+            .expect("created");
+
+        assert!(schedule.until_close);
+        assert_eq!(schedule.end_at, None);
+        assert_eq!(schedule.source.kind, SourceKind::Manual);
+        assert_eq!(schedule.source.name, "manual");
+    }
+
+    #[tokio::test]
+    async fn update_schedule_clears_end_with_null() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(vec![sample_schedule_row(
+                "Stoup Brewing",
+                "Where Ya At Matt",
+            )])),
+            freshness: Vec::new(),
+        };
+        let input: UpdateSchedule =
+            serde_json::from_str(r#"{"end_at": null}"#).expect("valid json");
+
+        let schedule = update_schedule(&repo, Uuid::nil(), input)
+            .await
+            .expect("updated");
+
+        assert!(schedule.until_close);
+        assert_eq!(schedule.start_at, "2026-02-01T17:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn cancel_schedule_reports_missing_row() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(None),
+            freshness: Vec::new(),
+        };
+
+        let result = cancel_schedule(&repo, Uuid::nil()).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound)));
+    }
+
+    #[test]
+    fn schedule_write_error_names_missing_vendor() {
+        let err = schedule_write_error(RepoError::ForeignKeyViolation {
+            constraint: "schedule_entries_food_vendor_id_fkey".to_string(),
+        });
+
+        let DomainError::Validation(errors) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "vendor_id");
+    }
+
+    #[test]
+    fn schedule_try_from_maps_status() {
+        let mut row = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        row.status = "tentative".to_string();
+        let schedule = Schedule::try_from(row).expect("valid row");
+        assert_eq!(schedule.status, ScheduleStatus::Tentative);
+
+        let mut row = sample_schedule_row("Stoup Brewing", "Where Ya At Matt");
+        row.status = "maybe".to_string();
+        assert!(matches!(
+            Schedule::try_from(row),
+            Err(DomainError::InvalidRow {
+                reason: "schedule status is unknown",
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_schedule_marks_entry_cancelled() {
+        let repo = MockScheduleRepo {
+            weekly: Vec::new(),
+            patterns: Vec::new(),
+            rows: Mutex::new(Some(vec![sample_schedule_row(
+                "Stoup Brewing",
+                "Where Ya At Matt",
+            )])),
+            freshness: Vec::new(),
+        };
+        let input: UpdateSchedule =
+            serde_json::from_str(r#"{"status": "cancelled"}"#).expect("valid json");
+
+        let schedule = update_schedule(&repo, Uuid::nil(), input)
+            .await
+            .expect("updated");
+
+        assert_eq!(schedule.status, ScheduleStatus::Cancelled);
+        assert_eq!(
+            schedule.end_at.as_deref(),
+            Some("2026-02-02T03:00:00+00:00")
+        );
+    }
 }
@@ -0,0 +1,1043 @@
+use crate::db::{
+    FailedScrapeRunWrite, RECURRING_SOURCE, ScheduleChangeWrite, ScheduleRow, ScrapeRunChangeRow,
+    ScrapeRunRepo, ScrapeRunRow, ScrapeRunWrite, ScrapeStatusRow, SlotWrite, VendorRepo,
+};
+use crate::domain::DomainError;
+use crate::domain::schedules::{
+    IMPORT_SOURCE, MANUAL_SOURCE, ScheduleStatus, SourceKind, row_hours, schedule_write_error,
+};
+use crate::domain::validation::{FieldError, Validator};
+use jiff::tz::TimeZone;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::ops::Bound;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Same-vendor slots whose starts are further apart than this are reported as one
+/// removal and one addition rather than a time shift.
+const SHIFT_MATCH_WINDOW: jiff::SignedDuration = jiff::SignedDuration::from_hours(12);
+
+/// Replaces the brewery's entries from `result.source` within the scraped window with
+/// the scraped ones, and records the run with what changed.
+pub async fn record_scrape_run<R: ScrapeRunRepo>(
+    db: &R,
+    result: ScrapeResult,
+    tz: &TimeZone,
+) -> Result<ScrapeRunDiff, DomainError> {
+    validate_scrape(&result)?;
+    let entries_parsed = result.entries.len() as i32;
+    let run = db
+        .create_scrape_run(
+            &ScrapeRunWrite {
+                brewery_id: result.brewery_id,
+                source: result.source,
+                window_start: result.window_start,
+                window_end: result.window_end,
+                started_at: result.started_at,
+                source_kind: result.origin.kind.as_str().to_string(),
+                source_url: result.origin.source_url,
+                parser_version: result.origin.parser_version,
+                snapshot_id: result.origin.snapshot_id,
+                entries_parsed,
+            },
+            tz.iana_name().unwrap_or("UTC"),
+            |current| diff_entries(current, &result.entries),
+        )
+        .await
+        .map_err(schedule_write_error)??;
+    let changes = db.get_scrape_run_changes(run.id).await?;
+    ScrapeRunDiff::new(run, changes)
+}
+
+/// Matches parsed vendor names to vendors, ignoring case, and covers the parsed span
+/// from the first start to the last. Unknown names are rejected so a new truck gets
+/// added by hand instead of guessed at.
+pub async fn build_scrape_result<R: VendorRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    source: &str,
+    origin: ScrapeOrigin,
+    started_at: jiff::Timestamp,
+    parsed: Vec<ParsedEntry>,
+) -> Result<ScrapeResult, DomainError> {
+    let (Some(first), Some(last)) = (parsed.first(), parsed.last()) else {
+        return Err(DomainError::Validation(vec![FieldError {
+            field: "entries",
+            message: "none were parsed".to_string(),
+        }]));
+    };
+    let window_start = first.start_at;
+    let window_end = last.start_at + jiff::SignedDuration::from_micros(1);
+
+    let vendors = db.get_vendors().await?;
+    let mut unknown: Vec<&str> = Vec::new();
+    let mut entries = Vec::new();
+    for entry in &parsed {
+        match vendors
+            .iter()
+            .find(|vendor| vendor.name.trim().eq_ignore_ascii_case(&entry.vendor_name))
+        {
+            Some(vendor) => entries.push(ScrapedEntry {
+                vendor_id: vendor.id,
+                start_at: entry.start_at,
+                end_at: entry.end_at,
+                all_day: entry.all_day,
+                status: entry.status,
+                confidence: certain(),
+            }),
+            None if !unknown.contains(&entry.vendor_name.as_str()) => {
+                unknown.push(&entry.vendor_name)
+            }
+            None => {}
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(DomainError::Validation(vec![FieldError {
+            field: "entries",
+            message: format!("unknown vendors: {}", unknown.join(", ")),
+        }]));
+    }
+
+    Ok(ScrapeResult {
+        brewery_id,
+        source: source.to_string(),
+        origin,
+        window_start,
+        window_end,
+        started_at,
+        entries,
+    })
+}
+
+/// Records a scrape that could not be applied so `get_scrape_status` can count it.
+pub async fn record_failed_scrape_run<R: ScrapeRunRepo>(
+    db: &R,
+    brewery_id: Uuid,
+    source: &str,
+    started_at: jiff::Timestamp,
+    snapshot_id: Option<String>,
+    error: &str,
+) -> Result<(), DomainError> {
+    warn!(
+        "Scrape of brewery {} from {} failed: {}",
+        brewery_id, source, error
+    );
+    db.create_failed_scrape_run(&FailedScrapeRunWrite {
+        brewery_id,
+        source: source.to_string(),
+        started_at,
+        snapshot_id,
+        error: error.to_string(),
+    })
+    .await?;
+    Ok(())
+}
+
+/// Reports each brewery's latest scrape outcomes. The next run is due `interval` after
+/// the last one started, or now for breweries that were never scraped.
+pub async fn get_scrape_status<R: ScrapeRunRepo>(
+    db: &R,
+    interval: Duration,
+    now: DateTime<Utc>,
+) -> Result<ScrapeStatus, DomainError> {
+    let rows = db.get_scrape_status().await?;
+    Ok(ScrapeStatus {
+        breweries: rows
+            .into_iter()
+            .map(|row| BreweryScrapeStatus::new(row, interval, now))
+            .collect(),
+    })
+}
+
+pub async fn get_scrape_run_diff<R: ScrapeRunRepo>(
+    db: &R,
+    id: Uuid,
+) -> Result<ScrapeRunDiff, DomainError> {
+    let run = db.get_scrape_run(id).await?.ok_or(DomainError::NotFound)?;
+    let changes = db.get_scrape_run_changes(id).await?;
+    ScrapeRunDiff::new(run, changes)
+}
+
+fn validate_scrape(result: &ScrapeResult) -> Result<(), DomainError> {
+    let mut v = Validator::default();
+    v.non_empty("source", &result.source);
+    v.check(
+        ![MANUAL_SOURCE, IMPORT_SOURCE, RECURRING_SOURCE].contains(&result.source.as_str()),
+        "source",
+        "is reserved for admin entries, imports and recurring schedules",
+    );
+    v.check(
+        matches!(result.origin.kind, SourceKind::Scraper | SourceKind::Ics),
+        "kind",
+        "must be scraper or ics",
+    );
+    v.url("source_url", result.origin.source_url.as_deref());
+    v.check(
+        result
+            .entries
+            .iter()
+            .all(|e| (0.0..=1.0).contains(&e.confidence)),
+        "entries",
+        "confidence must be between 0 and 1",
+    );
+    v.check(
+        result.window_end > result.window_start,
+        "window_end",
+        "must be after window_start",
+    );
+    v.check(
+        result
+            .entries
+            .iter()
+            .all(|e| (result.window_start..result.window_end).contains(&e.start_at)),
+        "entries",
+        "must start within the window",
+    );
+    v.check(
+        result
+            .entries
+            .iter()
+            .all(|e| e.end_at.is_none_or(|end| end > e.start_at)),
+        "entries",
+        "end_at must be after start_at",
+    );
+    v.finish()
+}
+
+/// Matches scraped entries against the current ones. Identical slots are left alone
+/// unless their status changed, the same vendor starting within `SHIFT_MATCH_WINDOW` is
+/// a time shift, another vendor in overlapping hours is a vendor swap, and whatever is
+/// left is added or removed. Removed entries are kept as cancelled, so current entries
+/// that already are cancelled and no longer listed stay as they are.
+fn diff_entries(
+    current: &[ScheduleRow],
+    scraped: &[ScrapedEntry],
+) -> Result<Vec<ScheduleChangeWrite>, DomainError> {
+    let mut current = current
+        .iter()
+        .map(|row| {
+            let (start_at, end_at) = row_hours(row)?;
+            Ok(Some((
+                row.id,
+                ScrapedEntry {
+                    vendor_id: row.food_vendor_id,
+                    start_at,
+                    end_at,
+                    all_day: row.all_day,
+                    status: ScheduleStatus::from_row(row.id, &row.status)?,
+                    confidence: row.provenance.confidence,
+                },
+            )))
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+    let mut changes = Vec::new();
+    let mut unmatched = Vec::new();
+    for entry in scraped {
+        match take_closest(&mut current, entry, |old| old.same_slot(entry)) {
+            Some((schedule_id, old)) if old.status != entry.status => {
+                changes.push(changed("status_changed", schedule_id, entry))
+            }
+            Some(_) => {}
+            None => unmatched.push(entry),
+        }
+    }
+
+    let mut swap_candidates = Vec::new();
+    for entry in unmatched {
+        let shifted = take_closest(&mut current, entry, |old| {
+            old.vendor_id == entry.vendor_id
+                && entry.start_at.duration_since(old.start_at).abs() <= SHIFT_MATCH_WINDOW
+        });
+        match shifted {
+            Some((schedule_id, _)) => changes.push(changed("time_shifted", schedule_id, entry)),
+            None => swap_candidates.push(entry),
+        }
+    }
+
+    for entry in swap_candidates {
+        let swapped = take_closest(&mut current, entry, |old| {
+            old.vendor_id != entry.vendor_id && overlaps(old, entry)
+        });
+        changes.push(match swapped {
+            Some((schedule_id, _)) => changed("vendor_swapped", schedule_id, entry),
+            None => ScheduleChangeWrite::Added {
+                slot: entry.slot(),
+                all_day: entry.all_day,
+                status: entry.status.as_str().to_string(),
+                confidence: entry.confidence,
+            },
+        });
+    }
+
+    changes.extend(
+        current
+            .into_iter()
+            .flatten()
+            .filter(|(_, old)| old.status != ScheduleStatus::Cancelled)
+            .map(|(schedule_id, _)| ScheduleChangeWrite::Removed { schedule_id }),
+    );
+    Ok(changes)
+}
+
+/// Takes the unclaimed entry matching `matches` whose start is closest to `entry`'s.
+fn take_closest(
+    current: &mut [Option<(Uuid, ScrapedEntry)>],
+    entry: &ScrapedEntry,
+    matches: impl Fn(&ScrapedEntry) -> bool,
+) -> Option<(Uuid, ScrapedEntry)> {
+    let slot = current
+        .iter_mut()
+        .filter(|slot| slot.as_ref().is_some_and(|(_, old)| matches(old)))
+        .min_by_key(|slot| {
+            let (_, old) = slot.as_ref().expect("filtered to claimed slots");
+            entry.start_at.duration_since(old.start_at).abs()
+        })?;
+    slot.take()
+}
+
+fn overlaps(a: &ScrapedEntry, b: &ScrapedEntry) -> bool {
+    a.end_at.is_none_or(|end| b.start_at < end) && b.end_at.is_none_or(|end| a.start_at < end)
+}
+
+fn changed(kind: &'static str, schedule_id: Uuid, entry: &ScrapedEntry) -> ScheduleChangeWrite {
+    ScheduleChangeWrite::Changed {
+        kind,
+        schedule_id,
+        slot: entry.slot(),
+        all_day: entry.all_day,
+        status: entry.status.as_str().to_string(),
+        confidence: entry.confidence,
+    }
+}
+
+/// A slot as a parser reads it, before its vendor name is matched to a vendor.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ParsedEntry {
+    pub vendor_name: String,
+    pub start_at: jiff::Timestamp,
+    pub end_at: Option<jiff::Timestamp>,
+    pub all_day: bool,
+    pub status: ScheduleStatus,
+}
+
+/// Normalized output of one scrape of a brewery's schedule.
+#[derive(Deserialize)]
+pub struct ScrapeResult {
+    pub brewery_id: Uuid,
+    /// Names the scraper, for example `stoup-site`. Entries are only replaced by runs
+    /// from the source that wrote them.
+    pub source: String,
+    #[serde(flatten)]
+    pub origin: ScrapeOrigin,
+    /// Span the source covers. Entries from the source that start in it and are no
+    /// longer listed are marked cancelled.
+    pub window_start: jiff::Timestamp,
+    pub window_end: jiff::Timestamp,
+    #[serde(default = "jiff::Timestamp::now")]
+    pub started_at: jiff::Timestamp,
+    pub entries: Vec<ScrapedEntry>,
+}
+
+/// Where a scrape's entries came from, recorded on every entry it adds or changes.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScrapeOrigin {
+    /// `scraper` or `ics`.
+    #[serde(default = "scraper_kind")]
+    pub kind: SourceKind,
+    /// The page or post the entries were read from.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub parser_version: Option<String>,
+    /// Identifies the content parsed, such as `sha256:<hex>` of the fetched page, so
+    /// entries can be traced back to exactly what the source said.
+    #[serde(default)]
+    pub snapshot_id: Option<String>,
+}
+
+fn scraper_kind() -> SourceKind {
+    SourceKind::Scraper
+}
+
+/// One slot listed by a source. Omitting `end_at` means "until close".
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ScrapedEntry {
+    pub vendor_id: Uuid,
+    pub start_at: jiff::Timestamp,
+    pub end_at: Option<jiff::Timestamp>,
+    #[serde(default)]
+    pub all_day: bool,
+    /// A cancelled slot is kept as `cancelled` rather than left out.
+    #[serde(default)]
+    pub status: ScheduleStatus,
+    /// How sure the parser is of the slot, from 0 to 1.
+    #[serde(default = "certain")]
+    pub confidence: f64,
+}
+
+fn certain() -> f64 {
+    1.0
+}
+
+impl ScrapedEntry {
+    /// Whether both list the same vendor and hours, whatever the status and confidence.
+    fn same_slot(&self, other: &ScrapedEntry) -> bool {
+        (self.vendor_id, self.start_at, self.end_at, self.all_day)
+            == (other.vendor_id, other.start_at, other.end_at, other.all_day)
+    }
+
+    fn slot(&self) -> SlotWrite {
+        SlotWrite {
+            food_vendor_id: self.vendor_id,
+            start: self.start_at,
+            end: self.end_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScrapeRun {
+    id: Uuid,
+    brewery_id: Uuid,
+    source: String,
+    /// `succeeded` or `failed`.
+    outcome: String,
+    /// Null for failed runs.
+    window_start: Option<String>,
+    window_end: Option<String>,
+    started_at: String,
+    finished_at: String,
+    entries_parsed: i32,
+    entries_inserted: i32,
+    entries_updated: i32,
+    entries_deleted: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Identifies the content the run parsed, when its source reported it.
+    snapshot_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ChangeSlot {
+    vendor_id: Uuid,
+    vendor_name: String,
+    start_at: String,
+    end_at: Option<String>,
+    status: ScheduleStatus,
+}
+
+/// One entry changed by a run. `before` is null for additions and `after` for
+/// removals.
+#[derive(Serialize)]
+pub struct ScheduleChange {
+    schedule_id: Uuid,
+    before: Option<ChangeSlot>,
+    after: Option<ChangeSlot>,
+}
+
+/// What a scrape run changed, grouped by kind.
+#[derive(Serialize)]
+pub struct ScrapeRunDiff {
+    run: ScrapeRun,
+    added: Vec<ScheduleChange>,
+    removed: Vec<ScheduleChange>,
+    time_shifted: Vec<ScheduleChange>,
+    vendor_swapped: Vec<ScheduleChange>,
+    status_changed: Vec<ScheduleChange>,
+}
+
+impl ScrapeRunDiff {
+    fn new(run: ScrapeRunRow, changes: Vec<ScrapeRunChangeRow>) -> Result<Self, DomainError> {
+        let (window_start, window_end) = match &run.scraped_hours {
+            Some(hours) => {
+                let (start, end) = range_bounds(run.id, hours)?;
+                (Some(start), end)
+            }
+            None => (None, None),
+        };
+        let mut diff = ScrapeRunDiff {
+            run: ScrapeRun {
+                id: run.id,
+                brewery_id: run.brewery_id,
+                source: run.source,
+                outcome: run.outcome,
+                window_start,
+                window_end,
+                started_at: run.started_at.to_rfc3339(),
+                finished_at: run.finished_at.to_rfc3339(),
+                entries_parsed: run.entries_parsed,
+                entries_inserted: run.entries_inserted,
+                entries_updated: run.entries_updated,
+                entries_deleted: run.entries_deleted,
+                error: run.error,
+                snapshot_id: run.snapshot_id,
+            },
+            added: Vec::new(),
+            removed: Vec::new(),
+            time_shifted: Vec::new(),
+            vendor_swapped: Vec::new(),
+            status_changed: Vec::new(),
+        };
+
+        for row in changes {
+            let id = row.schedule_entry_id;
+            let change = ScheduleChange {
+                schedule_id: id,
+                before: change_slot(
+                    id,
+                    row.before_vendor_id,
+                    row.before_vendor_name,
+                    row.before_hours,
+                    row.before_status,
+                )?,
+                after: change_slot(
+                    id,
+                    row.after_vendor_id,
+                    row.after_vendor_name,
+                    row.after_hours,
+                    row.after_status,
+                )?,
+            };
+            match row.kind.as_str() {
+                "added" => diff.added.push(change),
+                "removed" => diff.removed.push(change),
+                "time_shifted" => diff.time_shifted.push(change),
+                "vendor_swapped" => diff.vendor_swapped.push(change),
+                "status_changed" => diff.status_changed.push(change),
+                _ => {
+                    return Err(DomainError::InvalidRow {
+                        id,
+                        reason: "scrape run change kind is unknown",
+                    });
+                }
+            }
+        }
+        Ok(diff)
+    }
+}
+
+/// Scrape health for one brewery.
+#[derive(Serialize)]
+pub struct BreweryScrapeStatus {
+    brewery_id: Uuid,
+    brewery_name: String,
+    last_success_at: Option<String>,
+    last_run_at: Option<String>,
+    last_outcome: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+    /// Failed runs since the last successful one; a climbing count usually means the
+    /// brewery's site changed and its parser needs fixing.
+    consecutive_failures: i64,
+    next_run_at: String,
+}
+
+impl BreweryScrapeStatus {
+    fn new(row: ScrapeStatusRow, interval: Duration, now: DateTime<Utc>) -> Self {
+        let next_run_at = row.last_run_at.map_or(now, |last| last + interval);
+        BreweryScrapeStatus {
+            brewery_id: row.brewery_id,
+            brewery_name: row.brewery_name,
+            last_success_at: row.last_success_at.map(|ts| ts.to_rfc3339()),
+            last_run_at: row.last_run_at.map(|ts| ts.to_rfc3339()),
+            last_outcome: row.last_outcome,
+            last_error: row.last_error,
+            consecutive_failures: row.consecutive_failures,
+            next_run_at: next_run_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScrapeStatus {
+    breweries: Vec<BreweryScrapeStatus>,
+}
+
+fn change_slot(
+    id: Uuid,
+    vendor_id: Option<Uuid>,
+    vendor_name: Option<String>,
+    hours: Option<PgRange<DateTime<Utc>>>,
+    status: Option<String>,
+) -> Result<Option<ChangeSlot>, DomainError> {
+    let (Some(vendor_id), Some(hours)) = (vendor_id, hours) else {
+        return Ok(None);
+    };
+    let (start_at, end_at) = range_bounds(id, &hours)?;
+    Ok(Some(ChangeSlot {
+        vendor_id,
+        vendor_name: vendor_name.unwrap_or_default(),
+        start_at,
+        end_at,
+        status: ScheduleStatus::from_row(id, status.as_deref().unwrap_or("confirmed"))?,
+    }))
+}
+
+fn range_bounds(
+    id: Uuid,
+    hours: &PgRange<DateTime<Utc>>,
+) -> Result<(String, Option<String>), DomainError> {
+    let start = match hours.start {
+        Bound::Included(ts) | Bound::Excluded(ts) => ts.to_rfc3339(),
+        Bound::Unbounded => {
+            return Err(DomainError::InvalidRow {
+                id,
+                reason: "scrape run hours start is unbounded",
+            });
+        }
+    };
+    let end = match hours.end {
+        Bound::Included(ts) | Bound::Excluded(ts) => Some(ts.to_rfc3339()),
+        Bound::Unbounded => None,
+    };
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixture::Fixture;
+    use crate::db::{ProvenanceRow, RepoError};
+    use std::sync::Mutex;
+
+    const VENDOR_A: Uuid = Uuid::from_u128(0xa);
+    const VENDOR_B: Uuid = Uuid::from_u128(0xb);
+
+    fn at(hour: i64) -> jiff::Timestamp {
+        "2026-02-01T00:00:00Z"
+            .parse::<jiff::Timestamp>()
+            .expect("valid timestamp")
+            + jiff::SignedDuration::from_hours(hour)
+    }
+
+    fn entry(vendor_id: Uuid, start: i64, end: i64) -> ScrapedEntry {
+        ScrapedEntry {
+            vendor_id,
+            start_at: at(start),
+            end_at: Some(at(end)),
+            all_day: false,
+            status: ScheduleStatus::Confirmed,
+            confidence: 1.0,
+        }
+    }
+
+    fn row(id: u128, vendor_id: Uuid, start: i64, end: i64) -> ScheduleRow {
+        let to_chrono = |ts: jiff::Timestamp| {
+            DateTime::from_timestamp_micros(ts.as_microsecond()).expect("in range")
+        };
+        ScheduleRow {
+            id: Uuid::from_u128(id),
+            brewery_id: Uuid::from_u128(1),
+            brewery_name: "Stoup Brewing".to_string(),
+            food_vendor_id: vendor_id,
+            food_vendor_name: String::new(),
+            open_hours: PgRange {
+                start: Bound::Included(to_chrono(at(start))),
+                end: Bound::Excluded(to_chrono(at(end))),
+            },
+            all_day: false,
+            status: "confirmed".to_string(),
+            source: "stoup-site".to_string(),
+            provenance: ProvenanceRow::default(),
+            updated_at: Utc::now(),
+            override_id: None,
+        }
+    }
+
+    fn describe(change: &ScheduleChangeWrite) -> (&'static str, Option<u128>) {
+        match change {
+            ScheduleChangeWrite::Added { .. } => ("added", None),
+            ScheduleChangeWrite::Removed { schedule_id } => {
+                ("removed", Some(schedule_id.as_u128()))
+            }
+            ScheduleChangeWrite::Changed {
+                kind, schedule_id, ..
+            } => (kind, Some(schedule_id.as_u128())),
+        }
+    }
+
+    #[test]
+    fn diff_ignores_unchanged_entries() {
+        let rescored = ScrapedEntry {
+            confidence: 0.5,
+            ..entry(VENDOR_A, 17, 21)
+        };
+        let changes = diff_entries(&[row(1, VENDOR_A, 17, 21)], &[rescored]).expect("diff");
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn diff_classifies_shifts_swaps_additions_and_removals() {
+        let current = [
+            row(1, VENDOR_A, 17, 21),
+            row(2, VENDOR_A, 41, 45),
+            row(3, VENDOR_B, 65, 69),
+        ];
+        let scraped = [
+            // Same vendor, two hours later.
+            entry(VENDOR_A, 19, 23),
+            // Another vendor in the same slot.
+            entry(VENDOR_B, 41, 45),
+            // Nothing there before.
+            entry(VENDOR_B, 89, 93),
+        ];
+
+        let changes = diff_entries(&current, &scraped).expect("diff");
+
+        let changes: Vec<_> = changes.iter().map(describe).collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("time_shifted", Some(1)),
+                ("vendor_swapped", Some(2)),
+                ("added", None),
+                ("removed", Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_prefers_the_same_vendor_over_a_swap() {
+        let current = [row(1, VENDOR_A, 17, 21)];
+        let scraped = [entry(VENDOR_B, 17, 21), entry(VENDOR_A, 18, 22)];
+
+        let changes = diff_entries(&current, &scraped).expect("diff");
+
+        let changes: Vec<_> = changes.iter().map(describe).collect();
+        assert_eq!(changes, vec![("time_shifted", Some(1)), ("added", None)]);
+    }
+
+    #[test]
+    fn diff_treats_distant_same_vendor_slots_as_new() {
+        let changes =
+            diff_entries(&[row(1, VENDOR_A, 17, 21)], &[entry(VENDOR_A, 41, 45)]).expect("diff");
+
+        let changes: Vec<_> = changes.iter().map(describe).collect();
+        assert_eq!(changes, vec![("added", None), ("removed", Some(1))]);
+    }
+
+    #[test]
+    fn diff_records_a_cancelled_slot_as_a_status_change() {
+        let cancelled = ScrapedEntry {
+            status: ScheduleStatus::Cancelled,
+            ..entry(VENDOR_A, 17, 21)
+        };
+
+        let changes = diff_entries(&[row(1, VENDOR_A, 17, 21)], &[cancelled]).expect("diff");
+
+        let [ScheduleChangeWrite::Changed { kind, status, .. }] = changes.as_slice() else {
+            panic!("expected one change");
+        };
+        assert_eq!((*kind, status.as_str()), ("status_changed", "cancelled"));
+    }
+
+    #[test]
+    fn diff_leaves_unlisted_cancelled_entries_alone() {
+        let cancelled = ScheduleRow {
+            status: "cancelled".to_string(),
+            ..row(1, VENDOR_A, 17, 21)
+        };
+
+        let changes = diff_entries(&[cancelled, row(2, VENDOR_B, 41, 45)], &[]).expect("diff");
+
+        let changes: Vec<_> = changes.iter().map(describe).collect();
+        assert_eq!(changes, vec![("removed", Some(2))]);
+    }
+
+    struct MockScrapeRunRepo {
+        /// `(id, vendor)` of the current 17:00-21:00 entries.
+        current: Vec<(u128, Uuid)>,
+        changes_written: Mutex<usize>,
+    }
+
+    impl ScrapeRunRepo for MockScrapeRunRepo {
+        async fn create_scrape_run<E>(
+            &self,
+            run: &ScrapeRunWrite,
+            _tz: &str,
+            diff: impl FnOnce(&[ScheduleRow]) -> Result<Vec<ScheduleChangeWrite>, E>,
+        ) -> Result<Result<ScrapeRunRow, E>, RepoError> {
+            let current: Vec<_> = self
+                .current
+                .iter()
+                .map(|(id, vendor_id)| row(*id, *vendor_id, 17, 21))
+                .collect();
+            let changes = match diff(&current) {
+                Ok(changes) => changes.len(),
+                Err(e) => return Ok(Err(e)),
+            };
+            *self.changes_written.lock().expect("lock poisoned") = changes;
+            Ok(Ok(ScrapeRunRow {
+                id: Uuid::from_u128(99),
+                brewery_id: run.brewery_id,
+                source: run.source.clone(),
+                scraped_hours: Some(row(0, VENDOR_A, 0, 24 * 7).open_hours),
+                started_at: Utc::now(),
+                finished_at: Utc::now(),
+                outcome: "succeeded".to_string(),
+                entries_parsed: run.entries_parsed,
+                entries_inserted: 0,
+                entries_updated: 0,
+                entries_deleted: changes as i32,
+                error: None,
+                snapshot_id: run.snapshot_id.clone(),
+            }))
+        }
+
+        async fn create_failed_scrape_run(
+            &self,
+            _run: &FailedScrapeRunWrite,
+        ) -> Result<ScrapeRunRow, RepoError> {
+            Err(RepoError::NotFound)
+        }
+
+        async fn get_scrape_run(&self, _id: Uuid) -> Result<Option<ScrapeRunRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn get_scrape_run_changes(
+            &self,
+            _run_id: Uuid,
+        ) -> Result<Vec<ScrapeRunChangeRow>, RepoError> {
+            Ok(vec![ScrapeRunChangeRow {
+                kind: "removed".to_string(),
+                schedule_entry_id: Uuid::from_u128(1),
+                before_vendor_id: Some(VENDOR_A),
+                before_vendor_name: Some("Where Ya At Matt".to_string()),
+                before_hours: Some(row(1, VENDOR_A, 17, 21).open_hours),
+                before_status: Some("confirmed".to_string()),
+                after_vendor_id: None,
+                after_vendor_name: None,
+                after_hours: None,
+                after_status: None,
+            }])
+        }
+
+        async fn get_scrape_status(&self) -> Result<Vec<ScrapeStatusRow>, RepoError> {
+            let status = |name: &str, last_run_at| ScrapeStatusRow {
+                brewery_id: Uuid::new_v4(),
+                brewery_name: name.to_string(),
+                last_success_at: None,
+                last_run_at,
+                last_outcome: last_run_at.map(|_| "failed".to_string()),
+                last_error: last_run_at.map(|_| "no schedule table found".to_string()),
+                consecutive_failures: if last_run_at.is_some() { 3 } else { 0 },
+            };
+            Ok(vec![
+                status(
+                    "Stoup Brewing",
+                    Some(Utc::now() - Duration::from_secs(60 * 60)),
+                ),
+                status("Urban Family", None),
+            ])
+        }
+    }
+
+    fn scrape_result(source: &str, entries: Vec<ScrapedEntry>) -> ScrapeResult {
+        ScrapeResult {
+            brewery_id: Uuid::from_u128(1),
+            source: source.to_string(),
+            origin: ScrapeOrigin {
+                kind: SourceKind::Scraper,
+                source_url: Some("https://stoupbrewing.com/ballard/".to_string()),
+                parser_version: Some("stoup-site/1".to_string()),
+                snapshot_id: None,
+            },
+            window_start: at(0),
+            window_end: at(24 * 7),
+            started_at: at(0),
+            entries,
+        }
+    }
+
+    #[tokio::test]
+    async fn record_scrape_run_writes_the_diff_and_groups_changes() {
+        let repo = MockScrapeRunRepo {
+            current: vec![(1, VENDOR_A)],
+            changes_written: Mutex::new(0),
+        };
+
+        let diff = record_scrape_run(
+            &repo,
+            scrape_result("stoup-site", Vec::new()),
+            &TimeZone::UTC,
+        )
+        .await
+        .expect("recorded");
+
+        assert_eq!(*repo.changes_written.lock().expect("lock poisoned"), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.removed[0].after.is_none());
+        assert_eq!(
+            diff.removed[0].before.as_ref().expect("before").vendor_name,
+            "Where Ya At Matt"
+        );
+        assert!(diff.added.is_empty());
+    }
+
+    #[tokio::test]
+    async fn record_scrape_run_rejects_manual_source_and_entries_outside_window() {
+        let repo = MockScrapeRunRepo {
+            current: Vec::new(),
+            changes_written: Mutex::new(0),
+        };
+
+        let result = record_scrape_run(
+            &repo,
+            scrape_result("manual", vec![entry(VENDOR_A, 24 * 8, 24 * 8 + 4)]),
+            &TimeZone::UTC,
+        )
+        .await;
+
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["source", "entries"]);
+    }
+
+    #[tokio::test]
+    async fn record_scrape_run_rejects_bad_provenance() {
+        let repo = MockScrapeRunRepo {
+            current: Vec::new(),
+            changes_written: Mutex::new(0),
+        };
+        let mut result = scrape_result(
+            "stoup-site",
+            vec![ScrapedEntry {
+                confidence: 1.5,
+                ..entry(VENDOR_A, 17, 21)
+            }],
+        );
+        result.origin.kind = SourceKind::Prediction;
+        result.origin.source_url = Some("ftp://stoupbrewing.com".to_string());
+
+        let Err(DomainError::Validation(errors)) =
+            record_scrape_run(&repo, result, &TimeZone::UTC).await
+        else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["kind", "source_url", "entries"]);
+    }
+
+    #[tokio::test]
+    async fn scrape_status_schedules_the_next_run_after_the_last() {
+        let repo = MockScrapeRunRepo {
+            current: Vec::new(),
+            changes_written: Mutex::new(0),
+        };
+        let now = Utc::now();
+
+        let status = get_scrape_status(&repo, Duration::from_secs(12 * 60 * 60), now)
+            .await
+            .expect("status");
+
+        let stoup = &status.breweries[0];
+        assert_eq!(stoup.consecutive_failures, 3);
+        assert_eq!(stoup.last_error.as_deref(), Some("no schedule table found"));
+        let last_run_at = DateTime::parse_from_rfc3339(stoup.last_run_at.as_deref().expect("ran"))
+            .expect("rfc3339");
+        let next_run_at = DateTime::parse_from_rfc3339(&stoup.next_run_at).expect("rfc3339");
+        assert_eq!((next_run_at - last_run_at).num_hours(), 12);
+
+        let never_scraped = &status.breweries[1];
+        assert_eq!(never_scraped.next_run_at, now.to_rfc3339());
+        assert!(never_scraped.last_outcome.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn scrape_runs_cancel_entries_they_no_longer_list() {
+        let fixture = Fixture::new("Where Ya At Matt").await;
+        let result = serde_json::from_value(serde_json::json!({
+            "brewery_id": fixture.brewery_id,
+            "source": "test",
+            "window_start": fixture.start,
+            "window_end": fixture.start + jiff::SignedDuration::from_hours(1),
+            "entries": [],
+        }))
+        .unwrap();
+
+        let diff = record_scrape_run(&fixture.db, result, &jiff::tz::TimeZone::UTC)
+            .await
+            .unwrap();
+        let diff = serde_json::to_value(diff).unwrap();
+        assert_eq!(diff["removed"].as_array().unwrap().len(), 1);
+        let (status,): (String,) =
+            sqlx::query_as("SELECT status FROM schedule_entries WHERE id = $1")
+                .bind(fixture.schedule_id)
+                .fetch_one(fixture.db.pool())
+                .await
+                .unwrap();
+        assert_eq!(status, "cancelled");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres; run with --ignored"]
+    async fn past_until_close_entries_leave_later_recurring_entries() {
+        let fixture = Fixture::new("Marination").await;
+        let insert = |offset: &'static str, end: &'static str, source: &'static str| {
+            let sql = format!(
+                r#"
+    INSERT INTO schedule_entries (brewery_id, food_vendor_id, open_hours, source)
+    VALUES ($1, $2, TSTZRANGE($3::timestamptz + INTERVAL '{}', {}), '{}')
+    RETURNING id
+                "#,
+                offset, end, source
+            );
+            let db = fixture.db.clone();
+            let (brewery_id, vendor_id, start) =
+                (fixture.brewery_id, fixture.vendor_id, fixture.start);
+            async move {
+                let (id,): (Uuid,) = sqlx::query_as(&sql)
+                    .bind(brewery_id)
+                    .bind(vendor_id)
+                    .bind(start.to_string())
+                    .fetch_one(db.pool())
+                    .await
+                    .unwrap();
+                id
+            }
+        };
+        insert("-3 days", "NULL", "test").await;
+        let recurring_id = insert(
+            "26 hours",
+            "$3::timestamptz + INTERVAL '28 hours'",
+            "recurring",
+        )
+        .await;
+        let result = serde_json::from_value(serde_json::json!({
+            "brewery_id": fixture.brewery_id,
+            "source": "test",
+            "window_start": fixture.start,
+            "window_end": fixture.start + jiff::SignedDuration::from_hours(1),
+            "entries": [{
+                "vendor_id": fixture.vendor_id,
+                "start_at": fixture.start,
+                "end_at": fixture.start + jiff::SignedDuration::from_hours(1),
+            }],
+        }))
+        .unwrap();
+
+        record_scrape_run(
+            &fixture.db,
+            result,
+            &jiff::tz::TimeZone::get("America/Los_Angeles").unwrap(),
+        )
+        .await
+        .unwrap();
+        let (kept,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM schedule_entries WHERE id = $1)")
+                .bind(recurring_id)
+                .fetch_one(fixture.db.pool())
+                .await
+                .unwrap();
+        assert!(kept);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::db::{AppearanceStatsRow, PartnerCountRow, StatsRepo, StatsSubject, WeekdayCountRow};
+use crate::domain::DomainError;
+use crate::domain::brewery_hours::DayOfWeek;
+use crate::domain::schedules::to_chrono;
+use crate::domain::validation::FieldError;
+use jiff::civil::Weekday;
+use jiff::tz::TimeZone;
+use serde::Serialize;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Longest accepted lookback, in days.
+pub const MAX_LOOKBACK_DAYS: u64 = 3650;
+
+/// How often each vendor appeared over the `lookback_days` before `now`, most frequent
+/// first, with weekdays and hours local to `tz`.
+pub async fn get_vendor_stats<R: StatsRepo>(
+    db: &R,
+    now: jiff::Timestamp,
+    lookback_days: u64,
+    tz: &TimeZone,
+) -> Result<VendorStatsReport, DomainError> {
+    let window = StatsWindow::new(now, lookback_days)?;
+    let vendors = load_stats(db, StatsSubject::Vendors, &window, tz)
+        .await?
+        .into_iter()
+        .map(|(id, name, appearances, breweries)| VendorStats {
+            vendor_id: id,
+            vendor_name: name,
+            appearances,
+            breweries,
+        })
+        .collect();
+    Ok(VendorStatsReport { window, vendors })
+}
+
+/// How often each brewery hosted a vendor over the `lookback_days` before `now`, most
+/// frequent first, with weekdays and hours local to `tz`.
+pub async fn get_brewery_stats<R: StatsRepo>(
+    db: &R,
+    now: jiff::Timestamp,
+    lookback_days: u64,
+    tz: &TimeZone,
+) -> Result<BreweryStatsReport, DomainError> {
+    let window = StatsWindow::new(now, lookback_days)?;
+    let breweries = load_stats(db, StatsSubject::Breweries, &window, tz)
+        .await?
+        .into_iter()
+        .map(|(id, name, appearances, vendors)| BreweryStats {
+            brewery_id: id,
+            brewery_name: name,
+            appearances,
+            vendors,
+        })
+        .collect();
+    Ok(BreweryStatsReport { window, breweries })
+}
+
+type StatsParts = (Uuid, String, Appearances, Vec<PartnerCount>);
+
+async fn load_stats<R: StatsRepo>(
+    db: &R,
+    subject: StatsSubject,
+    window: &StatsWindow,
+    tz: &TimeZone,
+) -> Result<Vec<StatsParts>, DomainError> {
+    db.get_appearance_stats(
+        subject,
+        window.since,
+        window.until,
+        tz.iana_name().unwrap_or("UTC"),
+    )
+    .await?
+    .into_iter()
+    .map(split_row)
+    .collect()
+}
+
+fn split_row(row: AppearanceStatsRow) -> Result<StatsParts, DomainError> {
+    let weekdays = row
+        .weekdays
+        .0
+        .into_iter()
+        .map(|count| WeekdayCount::new(row.id, count))
+        .collect::<Result<Vec<_>, _>>()?;
+    let appearances = Appearances {
+        count: row.appearances,
+        first_seen: row.first_seen.to_rfc3339(),
+        last_seen: row.last_seen.to_rfc3339(),
+        weekdays,
+        usual_start: row.usual_start,
+        usual_end: row.usual_end,
+    };
+    let partners = row.partners.0.into_iter().map(PartnerCount::from).collect();
+    Ok((row.id, row.name, appearances, partners))
+}
+
+/// The span of history the statistics cover.
+#[derive(Serialize)]
+pub struct StatsWindow {
+    #[serde(serialize_with = "rfc3339")]
+    since: jiff::Timestamp,
+    #[serde(serialize_with = "rfc3339")]
+    until: jiff::Timestamp,
+    lookback_days: u64,
+}
+
+fn rfc3339<S: serde::Serializer>(ts: &jiff::Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_chrono(*ts).to_rfc3339())
+}
+
+impl StatsWindow {
+    fn new(now: jiff::Timestamp, lookback_days: u64) -> Result<Self, DomainError> {
+        let now = jiff::Timestamp::from_second(now.as_second()).unwrap_or(now);
+        let since = (1..=MAX_LOOKBACK_DAYS)
+            .contains(&lookback_days)
+            .then(|| {
+                now.checked_sub(Duration::from_hours(lookback_days * 24))
+                    .ok()
+            })
+            .flatten()
+            .ok_or_else(|| {
+                DomainError::Validation(vec![FieldError {
+                    field: "days",
+                    message: format!("must be between 1 and {}", MAX_LOOKBACK_DAYS),
+                }])
+            })?;
+        Ok(StatsWindow {
+            since,
+            until: now,
+            lookback_days,
+        })
+    }
+}
+
+/// When and how often a vendor or brewery appeared on schedules.
+#[derive(Serialize)]
+struct Appearances {
+    #[serde(rename = "appearances")]
+    count: i64,
+    first_seen: String,
+    last_seen: String,
+    /// Most frequent first.
+    weekdays: Vec<WeekdayCount>,
+    /// Most common local start and end times; `None` when every appearance was all-day
+    /// or, for the end, "until close".
+    usual_start: Option<String>,
+    usual_end: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WeekdayCount {
+    weekday: DayOfWeek,
+    appearances: i64,
+}
+
+impl WeekdayCount {
+    fn new(id: Uuid, row: WeekdayCountRow) -> Result<Self, DomainError> {
+        let weekday = i8::try_from(row.weekday)
+            .ok()
+            .and_then(|n| Weekday::from_monday_one_offset(n).ok())
+            .ok_or(DomainError::InvalidRow {
+                id,
+                reason: "stats weekday is out of range",
+            })?;
+        Ok(WeekdayCount {
+            weekday: weekday.into(),
+            appearances: row.appearances,
+        })
+    }
+}
+
+/// A brewery a vendor visited, or a vendor a brewery hosted.
+#[derive(Serialize)]
+struct PartnerCount {
+    id: Uuid,
+    name: String,
+    appearances: i64,
+}
+
+impl From<PartnerCountRow> for PartnerCount {
+    fn from(value: PartnerCountRow) -> Self {
+        PartnerCount {
+            id: value.id,
+            name: value.name,
+            appearances: value.appearances,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct VendorStats {
+    vendor_id: Uuid,
+    vendor_name: String,
+    #[serde(flatten)]
+    appearances: Appearances,
+    /// Distinct breweries visited, most visited first.
+    breweries: Vec<PartnerCount>,
+}
+
+#[derive(Serialize)]
+pub struct BreweryStats {
+    brewery_id: Uuid,
+    brewery_name: String,
+    #[serde(flatten)]
+    appearances: Appearances,
+    /// Distinct vendors hosted, most frequent first.
+    vendors: Vec<PartnerCount>,
+}
+
+#[derive(Serialize)]
+pub struct VendorStatsReport {
+    #[serde(flatten)]
+    window: StatsWindow,
+    vendors: Vec<VendorStats>,
+}
+
+#[derive(Serialize)]
+pub struct BreweryStatsReport {
+    #[serde(flatten)]
+    window: StatsWindow,
+    breweries: Vec<BreweryStats>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{RepoError, TagFilter, WeekdayPatternRow};
+    use sqlx::types::Json;
+    use sqlx::types::chrono::{DateTime, Utc};
+    use std::sync::Mutex;
+
+    /// Returns one row with the given ISO weekday and records what was asked for.
+    struct MockStatsRepo {
+        weekday: i32,
+        calls: Mutex<Vec<(StatsSubject, jiff::Timestamp, String)>>,
+    }
+
+    impl MockStatsRepo {
+        fn new(weekday: i32) -> Self {
+            MockStatsRepo {
+                weekday,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl StatsRepo for MockStatsRepo {
+        async fn get_appearance_stats(
+            &self,
+            subject: StatsSubject,
+            since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            tz: &str,
+        ) -> Result<Vec<AppearanceStatsRow>, RepoError> {
+            self.calls
+                .lock()
+                .expect("lock poisoned")
+                .push((subject, since, tz.to_string()));
+            let seen: DateTime<Utc> = "2026-02-04T01:00:00Z".parse().unwrap();
+            Ok(vec![AppearanceStatsRow {
+                id: Uuid::nil(),
+                name: "Where Ya At Matt".to_string(),
+                appearances: 3,
+                first_seen: seen,
+                last_seen: seen,
+                usual_start: Some("17:00:00".to_string()),
+                usual_end: None,
+                weekdays: Json(vec![WeekdayCountRow {
+                    weekday: self.weekday,
+                    appearances: 3,
+                }]),
+                partners: Json(vec![PartnerCountRow {
+                    id: Uuid::from_u128(1),
+                    name: "Stoup Brewing".to_string(),
+                    appearances: 3,
+                }]),
+            }])
+        }
+
+        async fn get_weekday_patterns(
+            &self,
+            _since: jiff::Timestamp,
+            _until: jiff::Timestamp,
+            _tz: &str,
+            _tags: &TagFilter,
+        ) -> Result<Vec<WeekdayPatternRow>, RepoError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn now() -> jiff::Timestamp {
+        "2026-10-19T06:49:37.5Z".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn vendor_stats_name_weekdays_and_breweries() {
+        let repo = MockStatsRepo::new(3);
+        let tz = TimeZone::get("America/Los_Angeles").unwrap();
+
+        let stats = get_vendor_stats(&repo, now(), 30, &tz).await.unwrap();
+        let json = serde_json::to_value(&stats).unwrap();
+
+        assert_eq!(json["since"], "2026-09-19T06:49:37+00:00");
+        assert_eq!(json["until"], "2026-10-19T06:49:37+00:00");
+        let vendor = &json["vendors"][0];
+        assert_eq!(vendor["vendor_name"], "Where Ya At Matt");
+        assert_eq!(vendor["appearances"], 3);
+        assert_eq!(vendor["weekdays"][0]["weekday"], "wednesday");
+        assert_eq!(vendor["usual_start"], "17:00:00");
+        assert_eq!(vendor["breweries"][0]["name"], "Stoup Brewing");
+
+        let calls = repo.calls.lock().unwrap();
+        assert_eq!(calls[0].0, StatsSubject::Vendors);
+        assert_eq!(calls[0].2, "America/Los_Angeles");
+    }
+
+    #[tokio::test]
+    async fn stats_reject_lookbacks_out_of_range() {
+        let repo = MockStatsRepo::new(3);
+        for days in [0, MAX_LOOKBACK_DAYS + 1] {
+            let result = get_brewery_stats(&repo, now(), days, &TimeZone::UTC).await;
+            let Err(DomainError::Validation(errors)) = result else {
+                panic!("expected validation error for {} days", days);
+            };
+            assert_eq!(errors[0].field, "days");
+        }
+        assert!(repo.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stats_reject_unknown_weekdays() {
+        let repo = MockStatsRepo::new(8);
+        let result = get_brewery_stats(&repo, now(), 30, &TimeZone::UTC).await;
+        assert!(matches!(result, Err(DomainError::InvalidRow { .. })));
+    }
+}
@@ -0,0 +1,312 @@
+use crate::db::{TagFilter, TagRepo, TagRow, TagWrite, VendorTagRow};
+use crate::domain::DomainError;
+use crate::domain::validation::Validator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub async fn get_tags<R: TagRepo>(db: &R) -> Result<Tags, DomainError> {
+    let tags = db
+        .get_tags()
+        .await?
+        .into_iter()
+        .map(Tag::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Tags { tags })
+}
+
+pub async fn create_tag<R: TagRepo>(db: &R, input: CreateTag) -> Result<Tag, DomainError> {
+    let write = input.into_write()?;
+    let row = db
+        .create_tag(&write)
+        .await?
+        .ok_or(DomainError::Conflict("a tag with this slug already exists"))?;
+    Tag::try_from(row)
+}
+
+/// Replaces the tags of a vendor, named by slug.
+pub async fn set_vendor_tags<R: TagRepo>(
+    db: &R,
+    vendor_id: Uuid,
+    input: SetVendorTags,
+) -> Result<VendorTags, DomainError> {
+    let known = db.get_tags().await?;
+    let tag_ids = input.resolve(&known)?;
+    let tags = db
+        .set_vendor_tags(vendor_id, &tag_ids)
+        .await?
+        .into_iter()
+        .map(Tag::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(VendorTags { vendor_id, tags })
+}
+
+/// Groups tag rows by vendor.
+pub(super) fn tags_by_vendor(
+    rows: Vec<VendorTagRow>,
+) -> Result<HashMap<Uuid, Vec<Tag>>, DomainError> {
+    let mut by_vendor: HashMap<Uuid, Vec<Tag>> = HashMap::new();
+    for row in rows {
+        let vendor_id = row.food_vendor_id;
+        by_vendor
+            .entry(vendor_id)
+            .or_default()
+            .push(Tag::try_from(row)?);
+    }
+    Ok(by_vendor)
+}
+
+/// Whether a vendor with `tags` passes `filter`; mirrors the schedule query.
+pub(super) fn matches(filter: &TagFilter, tags: &[Tag]) -> bool {
+    let has =
+        |kind: TagKind, slug: &String| tags.iter().any(|tag| tag.kind == kind && tag.slug == *slug);
+    (filter.cuisines.is_empty()
+        || filter
+            .cuisines
+            .iter()
+            .any(|slug| has(TagKind::Cuisine, slug)))
+        && filter
+            .dietary
+            .iter()
+            .all(|slug| has(TagKind::Dietary, slug))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagKind {
+    Cuisine,
+    /// Vegetarian, vegan or gluten-free options.
+    Dietary,
+    /// Price level; a vendor has at most one.
+    Price,
+}
+
+impl TagKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            TagKind::Cuisine => "cuisine",
+            TagKind::Dietary => "dietary",
+            TagKind::Price => "price",
+        }
+    }
+
+    fn parse(value: &str) -> Option<TagKind> {
+        match value {
+            "cuisine" => Some(TagKind::Cuisine),
+            "dietary" => Some(TagKind::Dietary),
+            "price" => Some(TagKind::Price),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Tag {
+    kind: TagKind,
+    slug: String,
+    name: String,
+}
+
+impl Tag {
+    fn from_parts(id: Uuid, kind: &str, slug: String, name: String) -> Result<Tag, DomainError> {
+        let kind = TagKind::parse(kind).ok_or(DomainError::InvalidRow {
+            id,
+            reason: "unknown tag kind",
+        })?;
+        Ok(Tag { kind, slug, name })
+    }
+}
+
+impl TryFrom<TagRow> for Tag {
+    type Error = DomainError;
+
+    fn try_from(value: TagRow) -> Result<Self, Self::Error> {
+        Tag::from_parts(value.id, &value.kind, value.slug, value.name)
+    }
+}
+
+impl TryFrom<VendorTagRow> for Tag {
+    type Error = DomainError;
+
+    fn try_from(value: VendorTagRow) -> Result<Self, Self::Error> {
+        Tag::from_parts(value.food_vendor_id, &value.kind, value.slug, value.name)
+    }
+}
+
+#[derive(Serialize)]
+pub struct Tags {
+    tags: Vec<Tag>,
+}
+
+#[derive(Serialize)]
+pub struct VendorTags {
+    vendor_id: Uuid,
+    tags: Vec<Tag>,
+}
+
+/// Admin input for a new tag.
+#[derive(Deserialize)]
+pub struct CreateTag {
+    kind: TagKind,
+    slug: String,
+    name: String,
+}
+
+impl CreateTag {
+    fn into_write(self) -> Result<TagWrite, DomainError> {
+        let mut v = Validator::default();
+        v.check(
+            is_slug(&self.slug),
+            "slug",
+            "must be lowercase letters and digits separated by single hyphens",
+        );
+        v.non_empty("name", &self.name);
+        v.finish()?;
+        Ok(TagWrite {
+            kind: self.kind.as_str().to_string(),
+            slug: self.slug,
+            name: self.name.trim().to_string(),
+        })
+    }
+}
+
+fn is_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// Admin input replacing a vendor's tags.
+#[derive(Deserialize)]
+pub struct SetVendorTags {
+    tags: Vec<String>,
+}
+
+impl SetVendorTags {
+    /// Looks up the ids of the named tags.
+    fn resolve(self, known: &[TagRow]) -> Result<Vec<Uuid>, DomainError> {
+        let mut v = Validator::default();
+        let mut tag_ids = Vec::new();
+        let mut prices = 0;
+        for slug in &self.tags {
+            match known.iter().find(|tag| tag.slug == *slug) {
+                Some(tag) => {
+                    if !tag_ids.contains(&tag.id) {
+                        tag_ids.push(tag.id);
+                        prices += usize::from(tag.kind == TagKind::Price.as_str());
+                    }
+                }
+                None => v.check(false, "tags", &format!("unknown tag {}", slug)),
+            }
+        }
+        v.check(prices <= 1, "tags", "must include at most one price tag");
+        v.finish()?;
+        Ok(tag_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag_row(kind: &str, slug: &str) -> TagRow {
+        TagRow {
+            id: Uuid::new_v4(),
+            kind: kind.to_string(),
+            slug: slug.to_string(),
+            name: slug.to_string(),
+        }
+    }
+
+    fn tag(kind: TagKind, slug: &str) -> Tag {
+        Tag {
+            kind,
+            slug: slug.to_string(),
+            name: slug.to_string(),
+        }
+    }
+
+    #[test]
+    fn set_vendor_tags_rejects_unknown_slugs_and_two_prices() {
+        let known = [
+            tag_row("cuisine", "mexican"),
+            tag_row("price", "price-1"),
+            tag_row("price", "price-2"),
+        ];
+        let input = SetVendorTags {
+            tags: vec![
+                "mexican".to_string(),
+                "price-1".to_string(),
+                "price-2".to_string(),
+                "vegan".to_string(),
+            ],
+        };
+
+        let Err(DomainError::Validation(errors)) = input.resolve(&known) else {
+            panic!("expected validation error");
+        };
+        let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["unknown tag vegan", "must include at most one price tag"]
+        );
+    }
+
+    #[test]
+    fn set_vendor_tags_ignores_repeated_slugs() {
+        let known = [tag_row("cuisine", "mexican"), tag_row("price", "price-1")];
+        let input = SetVendorTags {
+            tags: vec![
+                "price-1".to_string(),
+                "mexican".to_string(),
+                "price-1".to_string(),
+            ],
+        };
+
+        let ids = input.resolve(&known).expect("valid tags");
+        assert_eq!(ids, [known[1].id, known[0].id]);
+    }
+
+    #[test]
+    fn filter_needs_any_cuisine_and_every_dietary_option() {
+        let tags = [
+            tag(TagKind::Cuisine, "mexican"),
+            tag(TagKind::Dietary, "vegetarian"),
+        ];
+        let filter = |cuisines: &[&str], dietary: &[&str]| TagFilter {
+            cuisines: cuisines.iter().map(|s| s.to_string()).collect(),
+            dietary: dietary.iter().map(|s| s.to_string()).collect(),
+        };
+
+        assert!(matches(&filter(&[], &[]), &tags));
+        assert!(matches(&filter(&["cajun", "mexican"], &[]), &tags));
+        assert!(matches(&filter(&[], &["vegetarian"]), &tags));
+        assert!(!matches(&filter(&["cajun"], &[]), &tags));
+        assert!(!matches(&filter(&[], &["vegetarian", "vegan"]), &tags));
+        // A dietary slug never matches a cuisine tag.
+        assert!(!matches(&filter(&[], &["mexican"]), &tags));
+    }
+
+    #[test]
+    fn create_tag_validates_slug() {
+        for (slug, ok) in [
+            ("gluten-free", true),
+            ("price-2", true),
+            ("Gluten Free", false),
+            ("-vegan", false),
+            ("vegan--options", false),
+            ("", false),
+        ] {
+            let input = CreateTag {
+                kind: TagKind::Dietary,
+                slug: slug.to_string(),
+                name: "Name".to_string(),
+            };
+            assert_eq!(input.into_write().is_ok(), ok, "{}", slug);
+        }
+    }
+}
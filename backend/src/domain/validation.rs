@@ -0,0 +1,124 @@
+use crate::domain::DomainError;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// One rejected input field.
+#[derive(Debug, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+/// Collects field errors so a request reports every problem at once.
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn check(&mut self, ok: bool, field: &'static str, message: &str) {
+        if !ok {
+            self.errors.push(FieldError {
+                field,
+                message: message.to_string(),
+            });
+        }
+    }
+
+    pub fn non_empty(&mut self, field: &'static str, value: &str) {
+        self.check(!value.trim().is_empty(), field, "must not be empty");
+    }
+
+    pub fn coordinates(&mut self, lat: Option<f64>, lng: Option<f64>) {
+        if let Some(lat) = lat {
+            self.check(
+                (-90.0..=90.0).contains(&lat),
+                "lat",
+                "must be between -90 and 90",
+            );
+        }
+        if let Some(lng) = lng {
+            self.check(
+                (-180.0..=180.0).contains(&lng),
+                "lng",
+                "must be between -180 and 180",
+            );
+        }
+        self.check(
+            lat.is_some() == lng.is_some(),
+            if lat.is_some() { "lng" } else { "lat" },
+            "lat and lng must be set together",
+        );
+    }
+
+    /// Accepts a missing value or an absolute `http` or `https` URL.
+    pub fn url(&mut self, field: &'static str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.check(is_web_url(value), field, "must be an http or https URL");
+        }
+    }
+
+    pub fn finish(self) -> Result<(), DomainError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::Validation(self.errors))
+        }
+    }
+}
+
+/// Whether `value` parses as an `http` or `https` URL with a host.
+pub fn is_web_url(value: &str) -> bool {
+    web_url_host(value).is_some()
+}
+
+/// The lowercase host of an `http` or `https` URL, or `None` for anything else.
+pub fn web_url_host(value: &str) -> Option<String> {
+    let url = url::Url::parse(value).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.host_str().map(str::to_ascii_lowercase)
+}
+
+/// Distinguishes an explicit `null` (`Some(None)`) from an absent field (`None`) in
+/// PATCH bodies. Use with `#[serde(default, deserialize_with = "double_option")]`.
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Applies a PATCH field to the current value.
+pub fn patch<T>(current: T, update: Option<T>) -> T {
+    update.unwrap_or(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validator_collects_every_error() {
+        let mut v = Validator::default();
+        v.non_empty("name", "  ");
+        v.coordinates(Some(91.0), None);
+
+        let Err(DomainError::Validation(errors)) = v.finish() else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["name", "lat", "lng"]);
+    }
+
+    #[test]
+    fn web_urls_need_http_and_a_host() {
+        assert!(is_web_url("https://www.stoupbrewing.com/ballard/"));
+        assert!(is_web_url("http://example.com"));
+        assert!(!is_web_url("www.example.com"));
+        assert!(!is_web_url("ftp://example.com"));
+        assert!(!is_web_url("mailto:hello@example.com"));
+        assert!(!is_web_url("https://"));
+    }
+}
@@ -1,11 +1,135 @@
-use crate::db::{VendorRepo, VendorRow};
-use anyhow::anyhow;
-use serde::Serialize;
+use crate::db::{
+    LinkRepo, RepoError, TagFilter, TagRepo, VendorOrBrewery, VendorRepo, VendorRow, VendorWrite,
+};
+use crate::domain::DomainError;
+use crate::domain::links::{Profile, load_profile};
+use crate::domain::tags::{Tag, matches, tags_by_vendor};
+use crate::domain::validation::{Validator, double_option, patch};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-pub async fn get_vendors<R: VendorRepo>(db: &R) -> anyhow::Result<Vendors> {
-    let vendors = db.get_vendors().await?;
-    let vendors = Vendors::try_from(vendors).map_err(|e| anyhow!(e))?;
-    Ok(vendors)
+/// Returns vendors with their tags, keeping those that pass `filter`.
+pub async fn get_vendors<R: VendorRepo + TagRepo>(
+    db: &R,
+    filter: &TagFilter,
+) -> Result<Vendors, DomainError> {
+    let rows = db.get_vendors().await?;
+    let mut tags = tags_by_vendor(db.get_vendor_tags().await?)?;
+    let mut vendors = Vec::with_capacity(rows.len());
+    for row in rows {
+        let tags = tags.remove(&row.id).unwrap_or_default();
+        if matches(filter, &tags) {
+            vendors.push(Vendor::try_from(row)?.with_tags(tags));
+        }
+    }
+    Ok(Vendors { vendors })
+}
+
+/// Returns one vendor with its tags, links and images.
+pub async fn get_vendor<R: VendorRepo + TagRepo + LinkRepo>(
+    db: &R,
+    id: Uuid,
+) -> Result<VendorDetail, DomainError> {
+    let row = db.get_vendor(id).await?.ok_or(DomainError::NotFound)?;
+    let tags = tags_by_vendor(db.get_vendor_tags().await?)?
+        .remove(&id)
+        .unwrap_or_default();
+    let profile = load_profile(db, VendorOrBrewery::Vendor(id)).await?;
+    Ok(VendorDetail {
+        vendor: Vendor::try_from(row)?.with_tags(tags),
+        profile,
+    })
+}
+
+pub async fn create_vendor<R: VendorRepo>(
+    db: &R,
+    input: CreateVendor,
+) -> Result<Vendor, DomainError> {
+    let write = input.into_write();
+    validate_vendor(&write)?;
+    let row = db.create_vendor(&write).await?;
+    Vendor::try_from(row)
+}
+
+pub async fn update_vendor<R: VendorRepo + TagRepo>(
+    db: &R,
+    id: Uuid,
+    input: UpdateVendor,
+) -> Result<Vendor, DomainError> {
+    let current = db.get_vendor(id).await?.ok_or(DomainError::NotFound)?;
+    let write = input.apply(current);
+    validate_vendor(&write)?;
+    let row = db
+        .update_vendor(id, &write)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let tags = tags_by_vendor(db.get_vendor_tags().await?)?
+        .remove(&id)
+        .unwrap_or_default();
+    Ok(Vendor::try_from(row)?.with_tags(tags))
+}
+
+pub async fn delete_vendor<R: VendorRepo>(db: &R, id: Uuid) -> Result<(), DomainError> {
+    match db.delete_vendor(id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(DomainError::NotFound),
+        Err(RepoError::ForeignKeyViolation { .. }) => {
+            Err(DomainError::Conflict("vendor still has schedule entries"))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(super) fn validate_vendor(write: &VendorWrite) -> Result<(), DomainError> {
+    let mut v = Validator::default();
+    v.non_empty("name", &write.name);
+    v.url("website", write.website.as_deref());
+    v.url("menu", write.menu.as_deref());
+    v.finish()
+}
+
+/// Admin input for a new food vendor.
+#[derive(Deserialize)]
+pub struct CreateVendor {
+    name: String,
+    notes: Option<String>,
+    website: Option<String>,
+    menu: Option<String>,
+}
+
+impl CreateVendor {
+    pub(super) fn into_write(self) -> VendorWrite {
+        VendorWrite {
+            name: self.name.trim().to_string(),
+            notes: self.notes,
+            website: self.website,
+            menu: self.menu,
+        }
+    }
+}
+
+/// Admin input for a partial vendor update. Absent fields are kept; `null`
+/// clears optional fields.
+#[derive(Deserialize)]
+pub struct UpdateVendor {
+    name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    notes: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    website: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    menu: Option<Option<String>>,
+}
+
+impl UpdateVendor {
+    fn apply(self, current: VendorRow) -> VendorWrite {
+        VendorWrite {
+            name: patch(current.name, self.name.map(|name| name.trim().to_string())),
+            notes: patch(current.notes, self.notes),
+            website: patch(current.website, self.website),
+            menu: patch(current.menu, self.menu),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -13,54 +137,138 @@ pub struct Vendor {
     id: String,
     name: String,
     website: Option<String>,
+    tags: Vec<Tag>,
+}
+
+impl Vendor {
+    fn with_tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
 impl TryFrom<VendorRow> for Vendor {
-    type Error = &'static str;
+    type Error = DomainError;
 
     fn try_from(value: VendorRow) -> Result<Self, Self::Error> {
         if value.name.is_empty() {
-            return Err("vendor name is empty");
+            return Err(DomainError::InvalidRow {
+                id: value.id,
+                reason: "vendor name is empty",
+            });
         }
 
         Ok(Vendor {
             id: value.id.to_string(),
             name: value.name,
             website: value.website,
+            tags: Vec::new(),
         })
     }
 }
 
+/// A vendor as listed, plus its links and images.
 #[derive(Serialize)]
-pub struct Vendors {
-    vendors: Vec<Vendor>,
+pub struct VendorDetail {
+    #[serde(flatten)]
+    vendor: Vendor,
+    #[serde(flatten)]
+    profile: Profile,
 }
 
-impl TryFrom<Vec<VendorRow>> for Vendors {
-    type Error = &'static str;
-
-    fn try_from(value: Vec<VendorRow>) -> Result<Self, Self::Error> {
-        let vendors = value
-            .into_iter()
-            .map(Vendor::try_from)
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Vendors { vendors })
-    }
+#[derive(Serialize)]
+pub struct Vendors {
+    vendors: Vec<Vendor>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::{TagRow, TagWrite, VendorTagRow};
     use std::sync::Mutex;
-    use uuid::Uuid;
 
     struct MockVendorRepo {
         rows: Mutex<Option<Vec<VendorRow>>>,
+        /// Tags of the nil vendor as kind and slug.
+        tags: Vec<(&'static str, &'static str)>,
+    }
+
+    impl TagRepo for MockVendorRepo {
+        async fn get_tags(&self) -> Result<Vec<TagRow>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn create_tag(&self, _tag: &TagWrite) -> Result<Option<TagRow>, RepoError> {
+            Ok(None)
+        }
+
+        async fn get_vendor_tags(&self) -> Result<Vec<VendorTagRow>, RepoError> {
+            Ok(self
+                .tags
+                .iter()
+                .map(|&(kind, slug)| VendorTagRow {
+                    food_vendor_id: Uuid::nil(),
+                    kind: kind.to_string(),
+                    slug: slug.to_string(),
+                    name: slug.to_string(),
+                })
+                .collect())
+        }
+
+        async fn set_vendor_tags(
+            &self,
+            _vendor_id: Uuid,
+            _tag_ids: &[Uuid],
+        ) -> Result<Vec<VendorTagRow>, RepoError> {
+            Ok(Vec::new())
+        }
     }
 
     impl VendorRepo for MockVendorRepo {
-        async fn get_vendors(&self) -> anyhow::Result<Vec<VendorRow>> {
-            Ok(self.rows.lock().expect("lock poisoned").take().unwrap_or_default())
+        async fn get_vendors(&self) -> Result<Vec<VendorRow>, RepoError> {
+            Ok(self
+                .rows
+                .lock()
+                .expect("lock poisoned")
+                .take()
+                .unwrap_or_default())
+        }
+
+        async fn get_vendor(&self, id: Uuid) -> Result<Option<VendorRow>, RepoError> {
+            let mut rows = self.rows.lock().expect("lock poisoned");
+            let rows = rows.get_or_insert_with(Vec::new);
+            Ok(rows
+                .iter()
+                .position(|row| row.id == id)
+                .map(|i| rows.remove(i)))
+        }
+
+        async fn create_vendor(&self, vendor: &VendorWrite) -> Result<VendorRow, RepoError> {
+            Ok(row_from_write(Uuid::from_u128(1), vendor))
+        }
+
+        async fn update_vendor(
+            &self,
+            id: Uuid,
+            vendor: &VendorWrite,
+        ) -> Result<Option<VendorRow>, RepoError> {
+            Ok(Some(row_from_write(id, vendor)))
+        }
+
+        async fn delete_vendor(&self, _id: Uuid) -> Result<bool, RepoError> {
+            Err(RepoError::ForeignKeyViolation {
+                constraint: "schedule_entries_food_vendor_id_fkey".to_string(),
+            })
+        }
+    }
+
+    fn row_from_write(id: Uuid, vendor: &VendorWrite) -> VendorRow {
+        VendorRow {
+            id,
+            name: vendor.name.clone(),
+            notes: vendor.notes.clone(),
+            website: vendor.website.clone(),
+            menu: vendor.menu.clone(),
         }
     }
 
@@ -78,19 +286,109 @@ mod tests {
     async fn get_vendors_maps_rows() {
         let repo = MockVendorRepo {
             rows: Mutex::new(Some(vec![sample_vendor_row("El Pirata Tortas Y Burritos")])),
+            tags: Vec::new(),
         };
 
-        let result = get_vendors(&repo).await.expect("valid result");
+        let result = get_vendors(&repo, &TagFilter::default())
+            .await
+            .expect("valid result");
         assert_eq!(result.vendors.len(), 1);
         let first = &result.vendors[0];
         assert_eq!(first.id, Uuid::nil().to_string());
         assert_eq!(first.name, "El Pirata Tortas Y Burritos");
     }
 
+    #[tokio::test]
+    async fn get_vendors_filters_by_tags() {
+        let rows = || Mutex::new(Some(vec![sample_vendor_row("Tacos & Beer")]));
+        let repo = MockVendorRepo {
+            rows: rows(),
+            tags: vec![("cuisine", "mexican"), ("dietary", "vegetarian")],
+        };
+        let filter = TagFilter {
+            cuisines: vec!["mexican".to_string()],
+            dietary: vec!["vegetarian".to_string()],
+        };
+        let result = get_vendors(&repo, &filter).await.expect("valid result");
+        assert_eq!(result.vendors.len(), 1);
+        assert_eq!(result.vendors[0].tags.len(), 2);
+
+        let repo = MockVendorRepo {
+            rows: rows(),
+            tags: vec![("cuisine", "mexican")],
+        };
+        let filter = TagFilter {
+            cuisines: Vec::new(),
+            dietary: vec!["vegan".to_string()],
+        };
+        let result = get_vendors(&repo, &filter).await.expect("valid result");
+        assert!(result.vendors.is_empty());
+    }
+
     #[test]
     fn vendor_try_from_rejects_empty_name() {
         let row = sample_vendor_row("");
         let result = Vendor::try_from(row);
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn create_vendor_rejects_blank_name() {
+        let repo = MockVendorRepo {
+            rows: Mutex::new(None),
+            tags: Vec::new(),
+        };
+        let input: CreateVendor = serde_json::from_str(r#"{"name": "   "}"#).expect("valid json");
+
+        let result = create_vendor(&repo, input).await;
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        assert_eq!(errors[0].field, "name");
+    }
+
+    #[tokio::test]
+    async fn create_vendor_rejects_malformed_urls() {
+        let repo = MockVendorRepo {
+            rows: Mutex::new(None),
+            tags: Vec::new(),
+        };
+        let input: CreateVendor = serde_json::from_str(
+            r#"{"name": "Nosh", "website": "nosh.co", "menu": "https://nosh.co/menu"}"#,
+        )
+        .expect("valid json");
+
+        let result = create_vendor(&repo, input).await;
+        let Err(DomainError::Validation(errors)) = result else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, ["website"]);
+    }
+
+    #[tokio::test]
+    async fn update_vendor_clears_website() {
+        let repo = MockVendorRepo {
+            rows: Mutex::new(Some(vec![sample_vendor_row("Where Ya At Matt")])),
+            tags: Vec::new(),
+        };
+        let input: UpdateVendor = serde_json::from_str(r#"{"website": null}"#).expect("valid json");
+
+        let vendor = update_vendor(&repo, Uuid::nil(), input)
+            .await
+            .expect("valid vendor");
+        assert_eq!(vendor.name, "Where Ya At Matt");
+        assert_eq!(vendor.website, None);
+    }
+
+    #[tokio::test]
+    async fn delete_vendor_with_schedules_is_conflict() {
+        let repo = MockVendorRepo {
+            rows: Mutex::new(None),
+            tags: Vec::new(),
+        };
+
+        let result = delete_vendor(&repo, Uuid::nil()).await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+    }
 }
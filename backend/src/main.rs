@@ -1,13 +1,27 @@
 mod api;
+mod cli;
+mod config;
 mod db;
 mod domain;
+mod metrics;
 mod middleware;
+mod scraper;
+mod state;
 
-use crate::api::{get_breweries, get_schedules, get_vendors};
+use crate::api::{
+    get_breweries, get_brewery, get_brewery_stats, get_brewery_taplist, get_schedules, get_tags,
+    get_vendor, get_vendor_menu, get_vendor_stats, get_vendors,
+};
+use crate::cli::{Cli, Command};
+use crate::config::Config;
 use crate::db::Db;
 use crate::middleware::request_id_middleware;
-use axum::routing::get;
+use crate::state::AppState;
 use axum::Router;
+use axum::routing::get;
+use clap::Parser;
+use log::info;
+use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -27,16 +41,49 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    let db = Db::init().await?;
+    let cli = Cli::parse();
+    match cli.command {
+        None | Some(Command::Serve) => serve(Db::init().await?).await,
+        Some(Command::Migrate(command)) => cli::run_migrate(&Db::init().await?, command).await,
+        Some(Command::Keys(command)) => cli::run_keys(&Db::init().await?, command).await,
+        Some(Command::Import(args)) => cli::run_import(&Db::init().await?, args).await,
+        Some(Command::Recurring(command)) => cli::run_recurring(&Db::init().await?, command).await,
+        Some(Command::Repair(command)) => cli::run_repair(&Db::init().await?, command).await,
+        // Dry runs must work without a database, so scrape commands connect themselves.
+        Some(Command::Scrape(command)) => cli::run_scrape(command).await,
+    }
+}
+
+async fn serve(db: Db) -> anyhow::Result<()> {
+    let config = Config::from_env()?;
+    for version in db.run_migrations().await? {
+        info!("Applied migration {}", version);
+    }
+    let state = AppState {
+        db,
+        config: Arc::new(config),
+    };
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    axum::serve(listener, app(state)).await?;
+    Ok(())
+}
 
-    let app = Router::new()
+fn app(state: AppState) -> Router {
+    Router::new()
         .route("/api/health", get(|| async { "ok" }))
+        .route("/api/metrics", get(|| async { metrics::render() }))
         .route("/api/schedules", get(get_schedules))
         .route("/api/breweries", get(get_breweries))
+        .route("/api/breweries/{id}", get(get_brewery))
+        .route("/api/breweries/{id}/taplist", get(get_brewery_taplist))
         .route("/api/vendors", get(get_vendors))
+        .route("/api/vendors/{id}", get(get_vendor))
+        .route("/api/vendors/{id}/menu", get(get_vendor_menu))
+        .route("/api/tags", get(get_tags))
+        .route("/api/stats/vendors", get(get_vendor_stats))
+        .route("/api/stats/breweries", get(get_brewery_stats))
+        .nest("/api/admin", api::admin::router(state.clone()))
         .layer(axum::middleware::from_fn(request_id_middleware))
-        .with_state(db);
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
-    axum::serve(listener, app).await?;
-    Ok(())
+        .with_state(state)
 }
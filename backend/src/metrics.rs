@@ -0,0 +1,29 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static INVALID_SCHEDULE_ROWS: AtomicU64 = AtomicU64::new(0);
+
+/// Counts schedule rows dropped by lenient row mapping.
+pub fn record_invalid_schedule_rows(count: u64) {
+    INVALID_SCHEDULE_ROWS.fetch_add(count, Ordering::Relaxed);
+}
+
+pub fn invalid_schedule_rows() -> u64 {
+    INVALID_SCHEDULE_ROWS.load(Ordering::Relaxed)
+}
+
+/// Renders all counters in the Prometheus text exposition format.
+pub fn render() -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# HELP ballard_invalid_schedule_rows_total Schedule rows dropped because they failed validation."
+    );
+    let _ = writeln!(out, "# TYPE ballard_invalid_schedule_rows_total counter");
+    let _ = writeln!(
+        out,
+        "ballard_invalid_schedule_rows_total {}",
+        invalid_schedule_rows()
+    );
+    out
+}
@@ -1,11 +1,16 @@
-use axum::extract::Request;
+use crate::db::Db;
+use crate::domain::{ApiKey, DomainError, Scope, authenticate};
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
 use axum::http::{HeaderName, HeaderValue};
 use axum::middleware::Next;
 use axum::response::Response;
+use log::{info, warn};
+use sqlx::types::chrono::Utc;
 use tracing::Instrument;
 use uuid::Uuid;
 
-const X_REQUEST_ID: &str = "x-request-id";
+pub const X_REQUEST_ID: &str = "x-request-id";
 
 /// Propagates or generates `x-request-id` and attaches it to request span/response headers.
 pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
@@ -38,3 +43,48 @@ pub async fn request_id_middleware(mut request: Request, next: Next) -> Response
 
     next.run(request).await
 }
+
+/// Authenticates the `Authorization: Bearer` API key on admin routes and stores the
+/// resulting `ApiKey` in request extensions for `require_scope`.
+pub async fn admin_auth_middleware(
+    State(db): State<Db>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, DomainError> {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(DomainError::Unauthorized)?;
+
+    let key = match authenticate(&db, token, Utc::now()).await {
+        Ok(key) => key,
+        Err(e) => {
+            warn!("Rejected admin request: {}", e);
+            return Err(e);
+        }
+    };
+    info!(
+        "Admin request authenticated with key {} ({})",
+        key.id, key.name
+    );
+    request.extensions_mut().insert(key);
+    Ok(next.run(request).await)
+}
+
+/// Rejects requests whose API key lacks `scope`. Must run inside `admin_auth_middleware`.
+pub async fn require_scope(
+    scope: Scope,
+    request: Request,
+    next: Next,
+) -> Result<Response, DomainError> {
+    let key = request
+        .extensions()
+        .get::<ApiKey>()
+        .ok_or(DomainError::Unauthorized)?;
+    if !key.has_scope(scope) {
+        return Err(DomainError::Forbidden(scope));
+    }
+    Ok(next.run(request).await)
+}
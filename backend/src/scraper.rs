@@ -0,0 +1,204 @@
+mod ics;
+mod menu;
+
+use crate::domain::{MenuItem, ParsedEntry, SourceKind};
+use jiff::tz::TimeZone;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use thiserror::Error;
+
+/// Parser for one kind of schedule page or feed, selected by name on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Source {
+    /// An iCalendar feed where each event's summary names the vendor.
+    Ics,
+}
+
+impl Source {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Source::Ics => "ics",
+        }
+    }
+
+    pub fn kind(self) -> SourceKind {
+        match self {
+            Source::Ics => SourceKind::Ics,
+        }
+    }
+
+    /// Recorded on the entries a parser writes. Bump it when a parser's output changes so
+    /// bad entries can be traced to the version that wrote them.
+    pub fn parser_version(self) -> &'static str {
+        match self {
+            Source::Ics => "ics/1",
+        }
+    }
+
+    /// Fixture file extension, used by the golden tests to find inputs.
+    #[cfg(test)]
+    fn extension(self) -> &'static str {
+        match self {
+            Source::Ics => "ics",
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ScrapeError {
+    #[error("failed to fetch {url}: {source}")]
+    Fetch {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("line {line}: {reason}")]
+    Parse { line: usize, reason: String },
+}
+
+/// Downloads a page or feed.
+pub async fn fetch(url: &str) -> Result<String, ScrapeError> {
+    let fetch_error = |source| ScrapeError::Fetch {
+        url: url.to_string(),
+        source,
+    };
+    reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(fetch_error)?
+        .text()
+        .await
+        .map_err(fetch_error)
+}
+
+/// Names the exact content of a fetched page or feed, recorded with the run that parsed it.
+pub fn snapshot_id(body: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(body.as_bytes()))
+}
+
+/// Parses `body` into entries sorted by start time. Times without a zone are read in
+/// `local_tz`.
+pub fn parse(
+    source: Source,
+    body: &str,
+    local_tz: &TimeZone,
+) -> Result<Vec<ParsedEntry>, ScrapeError> {
+    let mut entries = match source {
+        Source::Ics => ics::parse(body, local_tz)?,
+    };
+    entries.sort_by(|a, b| (a.start_at, &a.vendor_name).cmp(&(b.start_at, &b.vendor_name)));
+    Ok(entries)
+}
+
+/// Parses a menu or taplist page, or a JSON-LD document, into items in menu order.
+pub fn parse_menu(body: &str) -> Result<Vec<MenuItem>, ScrapeError> {
+    menu::parse(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn snapshot_id_hashes_the_body() {
+        assert_eq!(
+            snapshot_id(""),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(
+            snapshot_id("BEGIN:VCALENDAR"),
+            snapshot_id("BEGIN:VCALENDAR\n")
+        );
+    }
+
+    /// Compares parser output for every fixture under `fixtures/<source>/`, and for menus
+    /// under `fixtures/menu/`, with the neighbouring `<fixture>.golden.json`. Run with
+    /// `UPDATE_GOLDEN=1` to rewrite the golden files after an intended parser change,
+    /// then review the diff.
+    #[test]
+    fn parsers_match_golden_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let local_tz = TimeZone::get("America/Los_Angeles").expect("bundled tzdb");
+        let mut checked = 0;
+
+        for source in Source::value_variants() {
+            checked +=
+                check_golden_files(&root.join(source.as_str()), &[source.extension()], |body| {
+                    parse(*source, body, &local_tz).map(|entries| to_json(&entries))
+                });
+        }
+        checked += check_golden_files(&root.join("menu"), &["html", "json"], |body| {
+            parse_menu(body).map(|items| to_json(&items))
+        });
+
+        assert!(checked > 0, "no fixtures found under {}", root.display());
+    }
+
+    fn to_json<T: serde::Serialize>(value: &T) -> String {
+        serde_json::to_string_pretty(value).expect("serializes") + "\n"
+    }
+
+    /// Checks or, with `UPDATE_GOLDEN` set, rewrites the golden file of each fixture in
+    /// `dir` with one of `extensions`. Returns how many fixtures were checked.
+    fn check_golden_files(
+        dir: &Path,
+        extensions: &[&str],
+        parse: impl Fn(&str) -> Result<String, ScrapeError>,
+    ) -> usize {
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let Ok(files) = fs::read_dir(dir) else {
+            return 0;
+        };
+        let mut fixtures: Vec<_> = files
+            .map(|file| file.expect("readable fixtures dir").path())
+            .filter(|path| {
+                let name = path.to_string_lossy();
+                !name.ends_with(".golden.json")
+                    && path
+                        .extension()
+                        .is_some_and(|ext| extensions.iter().any(|e| ext == *e))
+            })
+            .collect();
+        fixtures.sort();
+
+        for fixture in &fixtures {
+            let body = fs::read_to_string(fixture).expect("readable fixture");
+            let actual = parse(&body).unwrap_or_else(|e| panic!("{}: {}", fixture.display(), e));
+
+            let golden = fixture.with_extension("golden.json");
+            if update {
+                fs::write(&golden, &actual).expect("writable golden file");
+            } else {
+                let expected = fs::read_to_string(&golden).unwrap_or_else(|_| {
+                    panic!(
+                        "{} is missing; run with UPDATE_GOLDEN=1 to create it",
+                        golden.display()
+                    )
+                });
+                assert_eq!(
+                    actual,
+                    expected,
+                    "{} no longer matches {}",
+                    fixture.display(),
+                    golden.display()
+                );
+            }
+        }
+        fixtures.len()
+    }
+
+    #[test]
+    fn menu_without_structured_data_is_rejected() {
+        let result = parse_menu("<html><body><h1>Menu</h1><p>Tacos $4</p></body></html>");
+        assert!(matches!(result, Err(ScrapeError::Parse { .. })));
+    }
+}
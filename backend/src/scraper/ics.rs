@@ -0,0 +1,399 @@
+use crate::domain::{ParsedEntry, ScheduleStatus};
+use crate::scraper::ScrapeError;
+use jiff::civil;
+use jiff::tz::TimeZone;
+
+/// Reads each `VEVENT` as one entry, with the summary as the vendor name and `STATUS` as
+/// its status, so cancelled events are kept as cancelled. Recurring events are rejected rather than read as their first
+/// occurrence only.
+pub fn parse(body: &str, local_tz: &TimeZone) -> Result<Vec<ParsedEntry>, ScrapeError> {
+    let mut calendar_tz = local_tz.clone();
+    let mut entries = Vec::new();
+    let mut event: Option<Event> = None;
+
+    for (line, content) in unfold(body) {
+        let property = Property::split(&content).ok_or_else(|| ScrapeError::Parse {
+            line,
+            reason: "expected NAME:value".to_string(),
+        })?;
+        let is_vevent = property.value.eq_ignore_ascii_case("VEVENT");
+
+        match (property.name.as_str(), event.as_mut()) {
+            ("BEGIN", None) if is_vevent => event = Some(Event::new(line)),
+            ("END", Some(current)) if is_vevent && current.nested == 0 => {
+                let finished = event.take().expect("matched Some");
+                if let Some(entry) = finished.into_entry(&calendar_tz)? {
+                    entries.push(entry);
+                }
+            }
+            // Alarms and other components inside an event have their own summaries.
+            ("BEGIN", Some(current)) => current.nested += 1,
+            ("END", Some(current)) => current.nested = current.nested.saturating_sub(1),
+            (_, Some(current)) if current.nested == 0 => current.set(property, line)?,
+            ("X-WR-TIMEZONE", None) => {
+                calendar_tz = TimeZone::get(&property.value).map_err(|_| ScrapeError::Parse {
+                    line,
+                    reason: format!("unknown time zone '{}'", property.value),
+                })?;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(event) = event {
+        return Err(ScrapeError::Parse {
+            line: event.line,
+            reason: "VEVENT is never closed".to_string(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Joins folded lines (continuations start with a space or tab), keeping the number of
+/// the line each property starts on.
+fn unfold(body: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in body.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ if raw.is_empty() => {}
+            _ => lines.push((index + 1, raw.to_string())),
+        }
+    }
+    lines
+}
+
+struct Property {
+    /// Upper-cased property name.
+    name: String,
+    /// Upper-cased parameter names with their unquoted values.
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    /// Splits `NAME;PARAM=value:value`, ignoring colons inside quoted parameters.
+    fn split(line: &str) -> Option<Self> {
+        let mut quoted = false;
+        let colon = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        if name.is_empty() {
+            return None;
+        }
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| {
+                (
+                    key.to_ascii_uppercase(),
+                    value.trim_matches('"').to_string(),
+                )
+            })
+            .collect();
+        Some(Property {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A `DTSTART`/`DTEND` value.
+enum EventTime {
+    /// `VALUE=DATE`: the event lasts whole days.
+    Date(civil::Date),
+    DateTime {
+        value: civil::DateTime,
+        /// Times with neither a `TZID` nor a `Z` suffix use the calendar's zone.
+        tzid: Option<String>,
+        utc: bool,
+    },
+}
+
+impl EventTime {
+    fn parse(property: &Property, line: usize) -> Result<Self, ScrapeError> {
+        let invalid = || ScrapeError::Parse {
+            line,
+            reason: format!("invalid {} '{}'", property.name, property.value),
+        };
+        let value = property.value.trim();
+        if property.param("VALUE") == Some("DATE") {
+            let date = civil::Date::strptime("%Y%m%d", value).map_err(|_| invalid())?;
+            return Ok(EventTime::Date(date));
+        }
+        let (value, utc) = match value.strip_suffix('Z') {
+            Some(value) => (value, true),
+            None => (value, false),
+        };
+        let value = civil::DateTime::strptime("%Y%m%dT%H%M%S", value).map_err(|_| invalid())?;
+        Ok(EventTime::DateTime {
+            value,
+            tzid: property.param("TZID").map(str::to_string),
+            utc,
+        })
+    }
+
+    fn timestamp(
+        &self,
+        calendar_tz: &TimeZone,
+        line: usize,
+    ) -> Result<jiff::Timestamp, ScrapeError> {
+        let (value, tz) = match self {
+            EventTime::Date(date) => (date.to_datetime(civil::Time::midnight()), None),
+            EventTime::DateTime {
+                value, utc: true, ..
+            } => (*value, Some(TimeZone::UTC)),
+            EventTime::DateTime {
+                value,
+                tzid: Some(tzid),
+                ..
+            } => {
+                let tz = TimeZone::get(tzid).map_err(|_| ScrapeError::Parse {
+                    line,
+                    reason: format!("unknown time zone '{}'", tzid),
+                })?;
+                (*value, Some(tz))
+            }
+            EventTime::DateTime { value, .. } => (*value, None),
+        };
+        let tz = tz.unwrap_or_else(|| calendar_tz.clone());
+        value
+            .to_zoned(tz)
+            .map(|zoned| zoned.timestamp())
+            .map_err(|e| ScrapeError::Parse {
+                line,
+                reason: e.to_string(),
+            })
+    }
+}
+
+struct Event {
+    /// Line of `BEGIN:VEVENT`, for error messages.
+    line: usize,
+    summary: Option<String>,
+    start: Option<(usize, EventTime)>,
+    end: Option<(usize, EventTime)>,
+    status: ScheduleStatus,
+    recurring: bool,
+    /// Depth of components (such as `VALARM`) opened inside the event.
+    nested: usize,
+}
+
+impl Event {
+    fn new(line: usize) -> Self {
+        Event {
+            line,
+            summary: None,
+            start: None,
+            end: None,
+            status: ScheduleStatus::Confirmed,
+            recurring: false,
+            nested: 0,
+        }
+    }
+
+    fn set(&mut self, property: Property, line: usize) -> Result<(), ScrapeError> {
+        match property.name.as_str() {
+            "SUMMARY" => self.summary = Some(unescape(&property.value)),
+            "DTSTART" => self.start = Some((line, EventTime::parse(&property, line)?)),
+            "DTEND" => self.end = Some((line, EventTime::parse(&property, line)?)),
+            "STATUS" => {
+                self.status = match property.value.trim().to_ascii_uppercase().as_str() {
+                    "CANCELLED" => ScheduleStatus::Cancelled,
+                    "TENTATIVE" => ScheduleStatus::Tentative,
+                    _ => ScheduleStatus::Confirmed,
+                }
+            }
+            "RRULE" | "RDATE" => self.recurring = true,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn into_entry(self, calendar_tz: &TimeZone) -> Result<Option<ParsedEntry>, ScrapeError> {
+        let error = |line, reason: &str| ScrapeError::Parse {
+            line,
+            reason: reason.to_string(),
+        };
+        if self.recurring {
+            return Err(error(self.line, "recurring events are not supported"));
+        }
+        let vendor_name = self
+            .summary
+            .filter(|summary| !summary.is_empty())
+            .ok_or_else(|| error(self.line, "VEVENT has no SUMMARY"))?;
+        let (start_line, start) = self
+            .start
+            .ok_or_else(|| error(self.line, "VEVENT has no DTSTART"))?;
+
+        let all_day = matches!(start, EventTime::Date(_));
+        let start_at = start.timestamp(calendar_tz, start_line)?;
+        let end_at = match (&start, self.end) {
+            (EventTime::Date(_), Some((line, end @ EventTime::Date(_))))
+            | (EventTime::DateTime { .. }, Some((line, end @ EventTime::DateTime { .. }))) => {
+                Some(end.timestamp(calendar_tz, line)?)
+            }
+            (_, Some((line, _))) => {
+                return Err(error(line, "DTSTART and DTEND mix dates and date-times"));
+            }
+            // A date without an end lasts one day.
+            (EventTime::Date(date), None) => Some(
+                EventTime::Date(
+                    date.tomorrow()
+                        .map_err(|e| error(start_line, &e.to_string()))?,
+                )
+                .timestamp(calendar_tz, start_line)?,
+            ),
+            (EventTime::DateTime { .. }, None) => None,
+        };
+        if end_at.is_some_and(|end| end <= start_at) {
+            return Err(error(start_line, "DTEND is not after DTSTART"));
+        }
+
+        Ok(Some(ParsedEntry {
+            vendor_name,
+            start_at,
+            end_at,
+            all_day,
+            status: self.status,
+        }))
+    }
+}
+
+/// Undoes iCalendar TEXT escaping and collapses whitespace.
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => text.push(' '),
+                Some(escaped) => text.push(escaped),
+                None => {}
+            },
+            (c, false) => text.push(c),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_tz() -> TimeZone {
+        TimeZone::get("America/Los_Angeles").expect("bundled tzdb")
+    }
+
+    fn calendar(events: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n",
+            events.replace('\n', "\r\n")
+        )
+    }
+
+    #[test]
+    fn parse_reads_zoned_utc_and_floating_times() {
+        let body = calendar(
+            "BEGIN:VEVENT\nSUMMARY:Where Ya At Matt\nDTSTART;TZID=America/Los_Angeles:20260204T170000\nDTEND;TZID=America/Los_Angeles:20260204T210000\nEND:VEVENT\n\
+             BEGIN:VEVENT\nSUMMARY:Tacos & Beer\nDTSTART:20260205T010000Z\nEND:VEVENT\n\
+             BEGIN:VEVENT\nSUMMARY:El Pirata\nDTSTART:20260206T120000\nEND:VEVENT\n",
+        );
+
+        let entries = parse(&body, &local_tz()).expect("parsed");
+
+        let starts: Vec<_> = entries.iter().map(|e| e.start_at.to_string()).collect();
+        assert_eq!(
+            starts,
+            vec![
+                "2026-02-05T01:00:00Z",
+                "2026-02-05T01:00:00Z",
+                "2026-02-06T20:00:00Z"
+            ]
+        );
+        assert_eq!(
+            entries[0].end_at.map(|end| end.to_string()).as_deref(),
+            Some("2026-02-05T05:00:00Z")
+        );
+        assert_eq!(entries[1].end_at, None);
+    }
+
+    #[test]
+    fn parse_unfolds_and_unescapes_summaries_and_skips_alarms() {
+        let body = calendar(
+            "BEGIN:VEVENT\nSUMMARY:Birrieria Pepe\n  El Toro\\, Ballard\nDTSTART:20260204T170000Z\n\
+             BEGIN:VALARM\nSUMMARY:Reminder\nEND:VALARM\nEND:VEVENT\n",
+        );
+
+        let entries = parse(&body, &local_tz()).expect("parsed");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].vendor_name, "Birrieria Pepe El Toro, Ballard");
+    }
+
+    #[test]
+    fn parse_treats_dates_as_all_day_in_the_calendar_zone() {
+        let body = calendar(
+            "X-WR-TIMEZONE:America/New_York\nBEGIN:VEVENT\nSUMMARY:Where Ya At Matt\nDTSTART;VALUE=DATE:20260207\nEND:VEVENT\n",
+        );
+
+        let entries = parse(&body, &local_tz()).expect("parsed");
+
+        assert!(entries[0].all_day);
+        assert_eq!(entries[0].start_at.to_string(), "2026-02-07T05:00:00Z");
+        assert_eq!(
+            entries[0].end_at.map(|end| end.to_string()).as_deref(),
+            Some("2026-02-08T05:00:00Z")
+        );
+    }
+
+    #[test]
+    fn parse_keeps_cancelled_and_rejects_broken_events() {
+        let cancelled = calendar(
+            "BEGIN:VEVENT\nSUMMARY:Tacos & Beer\nSTATUS:CANCELLED\nDTSTART:20260204T170000Z\nEND:VEVENT\n",
+        );
+        let entries = parse(&cancelled, &local_tz()).expect("parsed");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, ScheduleStatus::Cancelled);
+
+        for (events, reason) in [
+            (
+                "BEGIN:VEVENT\nDTSTART:20260204T170000Z\nEND:VEVENT\n",
+                "no SUMMARY",
+            ),
+            ("BEGIN:VEVENT\nSUMMARY:Tacos\nEND:VEVENT\n", "no DTSTART"),
+            (
+                "BEGIN:VEVENT\nSUMMARY:Tacos\nDTSTART:20260204T170000Z\nRRULE:FREQ=WEEKLY\nEND:VEVENT\n",
+                "recurring",
+            ),
+            (
+                "BEGIN:VEVENT\nSUMMARY:Tacos\nDTSTART:20260204T170000Z\nDTEND:20260204T160000Z\nEND:VEVENT\n",
+                "not after",
+            ),
+            (
+                "BEGIN:VEVENT\nSUMMARY:Tacos\nDTSTART:yesterday\nEND:VEVENT\n",
+                "invalid DTSTART",
+            ),
+            ("BEGIN:VEVENT\nSUMMARY:Tacos\n", "never closed"),
+        ] {
+            let err = parse(&calendar(events), &local_tz()).expect_err(reason);
+            assert!(err.to_string().contains(reason), "{}: {}", reason, err);
+        }
+    }
+}
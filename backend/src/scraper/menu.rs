@@ -0,0 +1,172 @@
+use crate::domain::MenuItem;
+use crate::scraper::ScrapeError;
+use serde_json::Value;
+
+/// Reads schema.org `Menu` structured data, either a JSON-LD document or a page with
+/// `<script type="application/ld+json">` blocks. Items keep menu order; nested sections
+/// are named after the innermost one.
+pub fn parse(body: &str) -> Result<Vec<MenuItem>, ScrapeError> {
+    let trimmed = body.trim_start();
+    let documents = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        vec![(1, trimmed.to_string())]
+    } else {
+        json_ld_scripts(body)
+    };
+
+    let mut items = Vec::new();
+    let mut menus = 0;
+    for (line, document) in documents {
+        let value: Value = serde_json::from_str(&document).map_err(|e| ScrapeError::Parse {
+            line: line + e.line() - 1,
+            reason: format!("invalid JSON-LD: {}", e),
+        })?;
+        for menu in find_typed(&value, "Menu") {
+            menus += 1;
+            read_section(menu, None, &mut items, line)?;
+        }
+    }
+    if menus == 0 {
+        return Err(ScrapeError::Parse {
+            line: 1,
+            reason: "no schema.org Menu found".to_string(),
+        });
+    }
+    Ok(items)
+}
+
+/// Contents of every JSON-LD script block with the line it starts on.
+fn json_ld_scripts(body: &str) -> Vec<(usize, String)> {
+    let lower = body.to_ascii_lowercase();
+    let mut scripts = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = lower[rest..].find("<script") {
+        let open = rest + open;
+        let Some(tag_end) = lower[open..].find('>').map(|i| open + i + 1) else {
+            break;
+        };
+        let Some(close) = lower[tag_end..].find("</script").map(|i| tag_end + i) else {
+            break;
+        };
+        if lower[open..tag_end].contains("application/ld+json") {
+            let line = body[..tag_end].matches('\n').count() + 1;
+            scripts.push((line, body[tag_end..close].to_string()));
+        }
+        rest = close;
+    }
+    scripts
+}
+
+/// Objects of `type_name` anywhere in `value`, not looking inside matches.
+fn find_typed<'a>(value: &'a Value, type_name: &str) -> Vec<&'a Value> {
+    match value {
+        Value::Object(_) if has_type(value, type_name) => vec![value],
+        Value::Array(values) => values
+            .iter()
+            .flat_map(|v| find_typed(v, type_name))
+            .collect(),
+        Value::Object(object) => object
+            .values()
+            .flat_map(|v| find_typed(v, type_name))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn has_type(value: &Value, type_name: &str) -> bool {
+    match value.get("@type") {
+        Some(Value::String(t)) => t == type_name,
+        Some(Value::Array(types)) => types.iter().any(|t| t.as_str() == Some(type_name)),
+        _ => false,
+    }
+}
+
+/// A property that may hold one value or an array of them.
+fn many<'a>(value: &'a Value, key: &str) -> Vec<&'a Value> {
+    match value.get(key) {
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(value) => vec![value],
+    }
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn read_section(
+    section: &Value,
+    name: Option<&str>,
+    items: &mut Vec<MenuItem>,
+    line: usize,
+) -> Result<(), ScrapeError> {
+    for item in many(section, "hasMenuItem") {
+        items.push(read_item(item, name, line)?);
+    }
+    for subsection in many(section, "hasMenuSection") {
+        let sub_name = text(subsection, "name");
+        read_section(subsection, sub_name.as_deref().or(name), items, line)?;
+    }
+    Ok(())
+}
+
+fn read_item(item: &Value, section: Option<&str>, line: usize) -> Result<MenuItem, ScrapeError> {
+    let name = text(item, "name").ok_or_else(|| ScrapeError::Parse {
+        line,
+        reason: "MenuItem without a name".to_string(),
+    })?;
+    let price_cents = match many(item, "offers").first() {
+        Some(offer) => offer
+            .get("price")
+            .map(|price| {
+                price_cents(price).ok_or_else(|| ScrapeError::Parse {
+                    line,
+                    reason: format!("{}: unreadable price {}", name, price),
+                })
+            })
+            .transpose()?,
+        None => None,
+    };
+    let mut dietary: Vec<String> = many(item, "suitableForDiet")
+        .into_iter()
+        .filter_map(Value::as_str)
+        .filter_map(diet_slug)
+        .map(str::to_string)
+        .collect();
+    dietary.sort();
+    dietary.dedup();
+
+    Ok(MenuItem {
+        section: section.map(str::to_string),
+        name,
+        description: text(item, "description"),
+        price_cents,
+        dietary,
+    })
+}
+
+/// Reads `12.5`, `"12.50"` or `"$12.50"` as cents.
+fn price_cents(price: &Value) -> Option<i32> {
+    let amount = match price {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => s.trim().trim_start_matches('$').trim().parse().ok()?,
+        _ => return None,
+    };
+    (0.0..1_000_000.0)
+        .contains(&amount)
+        .then(|| (amount * 100.0).round() as i32)
+}
+
+/// Maps schema.org `RestrictedDiet` values to dietary tag slugs.
+fn diet_slug(diet: &str) -> Option<&'static str> {
+    match diet.rsplit(['/', ':']).next()? {
+        "VeganDiet" => Some("vegan"),
+        "VegetarianDiet" => Some("vegetarian"),
+        "GlutenFreeDiet" => Some("gluten-free"),
+        _ => None,
+    }
+}
@@ -0,0 +1,11 @@
+use crate::config::Config;
+use crate::db::Db;
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+/// Router state; handlers extract the parts they need via `FromRef`.
+#[derive(Clone, FromRef)]
+pub struct AppState {
+    pub db: Db,
+    pub config: Arc<Config>,
+}
@@ -1,3 +1,6 @@
+-- Attributes the audit events for seeded rows.
+SET app.actor = 'seed';
+
 INSERT INTO breweries (id, name, notes, website, address, lat, lng, drink_menu, food_schedule)
 VALUES
   ('ddbef262-8ae4-413c-9fa3-e4fbc40175b5', 'Stoup Brewing', 'Brewery', 'https://www.stoupbrewing.com/ballard/', '1108 NW 52nd St, Seattle, WA 98107', 47.66653130601593, -122.3711539291425, 'https://www.stoupbrewing.com/ballard/#whats-on-tap-ballard', 'https://www.stoupbrewing.com/ballard/#food-truck-schedule-ballard'),
@@ -12,18 +15,43 @@ VALUES
   ('09cfecc0-60a9-4d0a-a1ec-b18f8fbae752', 'Tacos & Beer', 'Authentic Cocina Mexicana', 'https://www.tacosandbeerseattle.com', 'https://www.tacosandbeerseattle.com/menu'),
   ('1c89db87-e201-4295-a94a-34430d1dd2d3', 'Birrieria Pepe El Toro', 'Taco! Tortas! Burritos! Quesadillas!', 'https://www.birrieria-pepeeltoro.com', 'https://www.birrieria-pepeeltoro.com/menu');
 
+INSERT INTO tags (kind, slug, name)
+VALUES
+  ('cuisine', 'mexican', 'Mexican'),
+  ('cuisine', 'cajun', 'Cajun & Creole'),
+  ('cuisine', 'seafood', 'Seafood');
+
+INSERT INTO food_vendor_tags (food_vendor_id, tag_id)
+SELECT v.id, t.id
+FROM (
+  VALUES
+    ('def4c743-7ca0-444c-8294-bfc454e57461'::uuid, 'mexican'),
+    ('def4c743-7ca0-444c-8294-bfc454e57461'::uuid, 'vegetarian'),
+    ('def4c743-7ca0-444c-8294-bfc454e57461'::uuid, 'price-1'),
+    ('c65ebe31-6b68-4373-b41d-760ba01476e2'::uuid, 'cajun'),
+    ('c65ebe31-6b68-4373-b41d-760ba01476e2'::uuid, 'price-2'),
+    ('8ba304ab-3d7c-445a-b77c-bde1768c89b2'::uuid, 'seafood'),
+    ('8ba304ab-3d7c-445a-b77c-bde1768c89b2'::uuid, 'gluten-free'),
+    ('8ba304ab-3d7c-445a-b77c-bde1768c89b2'::uuid, 'price-3'),
+    ('09cfecc0-60a9-4d0a-a1ec-b18f8fbae752'::uuid, 'mexican'),
+    ('09cfecc0-60a9-4d0a-a1ec-b18f8fbae752'::uuid, 'vegetarian'),
+    ('09cfecc0-60a9-4d0a-a1ec-b18f8fbae752'::uuid, 'vegan'),
+    ('09cfecc0-60a9-4d0a-a1ec-b18f8fbae752'::uuid, 'price-1'),
+    ('1c89db87-e201-4295-a94a-34430d1dd2d3'::uuid, 'mexican'),
+    ('1c89db87-e201-4295-a94a-34430d1dd2d3'::uuid, 'price-1')
+) AS v (id, slug)
+JOIN tags t ON t.slug = v.slug;
+
 INSERT INTO schedule_entries (
   id,
   brewery_id,
-  brewery_name,
   food_vendor_id,
-  food_vendor_name,
   open_hours,
   source
 )
 VALUES
-  ('1f2fa18e-96e6-4ba4-aa91-f967f2ce270a', 'ddbef262-8ae4-413c-9fa3-e4fbc40175b5', 'Stoup Brewing', 'def4c743-7ca0-444c-8294-bfc454e57461', 'El Pirata Tortas Y Burritos', '[2026-02-01 17:00:00+00, 2026-02-02 03:00:00+00]', 'seed'),
-  ('9426bb02-07ba-4c2e-a436-b433a9afff96', 'ddbef262-8ae4-413c-9fa3-e4fbc40175b5', 'Stoup Brewing', 'c65ebe31-6b68-4373-b41d-760ba01476e2', 'Where Ya At Matt', '[2026-02-03 01:00:00+00, 2026-02-03 04:00:00+00]', 'seed'),
-  ('315ff286-d5be-48d7-978e-be385b634d4b', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', 'Bale Breaker x Yonder Cider', '8ba304ab-3d7c-445a-b77c-bde1768c89b2', 'The Little Pearl Oyster Bar', '[2026-02-01 16:00:00+00, 2026-02-02 01:00:00+00]', 'seed'),
-  ('2130bc93-efd1-4347-8d25-631c4b96e13f', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', 'Bale Breaker x Yonder Cider', '09cfecc0-60a9-4d0a-a1ec-b18f8fbae752', 'Tacos & Beer', '[2026-02-01 17:00:00+00, 2026-02-02 04:00:00+00]', 'seed'),
-  ('e06d9f6a-cd9c-40f5-8854-1b77d0a3b734', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', 'Bale Breaker x Yonder Cider', '1c89db87-e201-4295-a94a-34430d1dd2d3', 'Birrieria Pepe El Toro', '[2026-02-04 00:00:00+00, 2026-02-04 04:00:00+00]', 'seed');
+  ('1f2fa18e-96e6-4ba4-aa91-f967f2ce270a', 'ddbef262-8ae4-413c-9fa3-e4fbc40175b5', 'def4c743-7ca0-444c-8294-bfc454e57461', '[2026-02-01 17:00:00+00, 2026-02-02 03:00:00+00)', 'seed'),
+  ('9426bb02-07ba-4c2e-a436-b433a9afff96', 'ddbef262-8ae4-413c-9fa3-e4fbc40175b5', 'c65ebe31-6b68-4373-b41d-760ba01476e2', '[2026-02-03 01:00:00+00, 2026-02-03 04:00:00+00)', 'seed'),
+  ('315ff286-d5be-48d7-978e-be385b634d4b', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', '8ba304ab-3d7c-445a-b77c-bde1768c89b2', '[2026-02-01 16:00:00+00, 2026-02-02 01:00:00+00)', 'seed'),
+  ('2130bc93-efd1-4347-8d25-631c4b96e13f', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', '09cfecc0-60a9-4d0a-a1ec-b18f8fbae752', '[2026-02-01 17:00:00+00, 2026-02-02 04:00:00+00)', 'seed'),
+  ('e06d9f6a-cd9c-40f5-8854-1b77d0a3b734', 'c8176998-6c38-4813-a9ec-1e45a710e6dc', '1c89db87-e201-4295-a94a-34430d1dd2d3', '[2026-02-04 00:00:00+00, 2026-02-04 04:00:00+00)', 'seed');
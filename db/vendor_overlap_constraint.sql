@@ -0,0 +1,11 @@
+-- Optional: reject overlapping slots for one vendor outright instead of reporting them as
+-- `vendor_overlap` schedule issues. Apply to a migrated database with
+--   psql -f db/vendor_overlap_constraint.sql
+-- Writes that would overlap fail with 409, and a scrape run that reports an overlap is
+-- recorded as failed without changing anything. Cancelled entries are ignored.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE schedule_entries
+  ADD CONSTRAINT schedule_entries_vendor_overlap
+  EXCLUDE USING GIST (food_vendor_id WITH =, open_hours WITH &&)
+  WHERE (status <> 'cancelled');